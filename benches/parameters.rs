@@ -0,0 +1,79 @@
+//! Counts the allocations of reusing a parameter set with a long `FELDER` list, as done for
+//! every page of a cursor.
+//!
+//! Run with `cargo bench --bench parameters`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use wwsvc_rs::requests::ServiceFunctionParameter;
+use wwsvc_rs::Parameters;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and returns the number of allocations and the allocated bytes.
+fn measure(f: impl FnOnce()) -> (usize, usize) {
+    let (allocations, bytes) = (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    );
+    f();
+    (
+        ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+    )
+}
+
+fn main() {
+    const PAGES: usize = 1000;
+
+    let fields = (1..=2000)
+        .map(|number| format!("ART_{}_80", number))
+        .collect::<Vec<_>>()
+        .join(",");
+    let parameters = Parameters::new()
+        .param("FELDER", &fields)
+        .param("ARTNR", "A*");
+
+    let (allocations, bytes) = measure(|| {
+        for _ in 0..PAGES {
+            std::hint::black_box(parameters.clone());
+        }
+    });
+    println!(
+        "clone parameters ({} bytes of FELDER) {} times: {} allocations, {} bytes",
+        fields.len(),
+        PAGES,
+        allocations,
+        bytes
+    );
+
+    let (allocations, bytes) = measure(|| {
+        for _ in 0..PAGES {
+            let body: Vec<ServiceFunctionParameter> = (&parameters).into();
+            std::hint::black_box(body);
+        }
+    });
+    println!(
+        "convert parameters to the request body {} times: {} allocations, {} bytes",
+        PAGES, allocations, bytes
+    );
+}
@@ -0,0 +1,28 @@
+use wwsvc_rs::WebwareClient;
+
+#[tokio::main]
+async fn main() {
+    let vendor_hash = std::env::var("WW_VENDOR_HASH").expect("WW_VENDOR_HASH not set");
+    let app_hash = std::env::var("WW_APP_HASH").expect("WW_APP_HASH not set");
+    let revision = std::env::var("WW_REVISION")
+        .expect("WW_REVISION not set")
+        .parse()
+        .unwrap();
+    let secret = std::env::var("WW_SECRET").expect("WW_SECRET not set");
+    let webware_url = std::env::var("WW_WEBWARE_URL").expect("WW_WEBWARE_URL not set");
+
+    let client = WebwareClient::builder()
+        .webware_url(&webware_url)
+        .vendor_hash(&vendor_hash)
+        .app_hash(&app_hash)
+        .secret(&secret)
+        .revision(revision)
+        .build();
+
+    let report = wwsvc_rs::conformance::run(client).await;
+    println!("{}", report);
+
+    if !report.passed() {
+        std::process::exit(1);
+    }
+}
@@ -34,4 +34,11 @@ async fn main() {
         .unwrap();
 
     println!("{:#?}", articles);
+
+    // request all articles page by page
+    let mut cursor_client = registered_client.create_cursor(500);
+    let mut pages = ArticleData::get_cursored(&mut cursor_client, collection! {});
+    while let Some(page) = pages.next().await {
+        println!("{:#?}", page.unwrap());
+    }
 }
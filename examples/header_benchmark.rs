@@ -0,0 +1,71 @@
+//! Compares building request headers with the client against building them from scratch for
+//! every request, like earlier versions did.
+//!
+//! Run with `cargo run --release --example header_benchmark`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+use wwsvc_rs::{AppHash, Credentials, WebwareClient};
+
+const ITERATIONS: u32 = 100_000;
+
+fn headers_from_scratch(request_id: u32, app_id: &str) -> HeaderMap {
+    let app_hash = AppHash::for_request(request_id, app_id);
+    let header_vec = [
+        ("WWSVC-ACCEPT-RESULT-TYPE", "JSON".to_string()),
+        ("WWSVC-EXECUTE-MODE", "SYNCHRON".to_string()),
+        ("WWSVC-REQID", request_id.to_string()),
+        ("WWSVC-TS", app_hash.date_formatted.to_string()),
+        ("WWSVC-HASH", format!("{:x}", app_hash)),
+        ("WWSVC-ACCEPT-RESULT-MAX-LINES", 1000.to_string()),
+    ];
+    let headers: HashMap<String, String> = header_vec
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    (&headers).try_into().unwrap()
+}
+
+fn measure(name: &str, mut f: impl FnMut(u32)) -> Duration {
+    let start = Instant::now();
+    for request_id in 1..=ITERATIONS {
+        f(request_id);
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<16} {:>10.2?} total, {:>8.2?} per request",
+        name,
+        elapsed,
+        elapsed / ITERATIONS
+    );
+    elapsed
+}
+
+#[tokio::main]
+async fn main() {
+    let client = WebwareClient::builder()
+        .webware_url("http://localhost:8080")
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    let from_scratch = measure("from scratch", |request_id| {
+        std::hint::black_box(headers_from_scratch(request_id, "app-id"));
+    });
+    let cached = measure("client", |request_id| {
+        std::hint::black_box(client.headers_for(request_id, None).unwrap());
+    });
+
+    println!(
+        "speedup: {:.2}x",
+        from_scratch.as_secs_f64() / cached.as_secs_f64()
+    );
+}
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use wwsvc_rs::idb::IdbTable;
+use wwsvc_rs::{Parameters, Registered, WebwareClient};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Tracking {
+    #[serde(rename = "IDB_1_20")]
+    pub tracking_number: String,
+}
+
+async fn get_idb_rows(client: &mut WebwareClient<Registered>, table: &IdbTable) {
+    let response = table
+        .get::<Tracking>(client, Parameters::new().param("FELDER", "IDB_1_20"))
+        .await
+        .unwrap();
+
+    println!("{}: {:#?}", table, response.items);
+}
+
+#[tokio::main]
+async fn main() {
+    let vendor_hash = std::env::var("WW_VENDOR_HASH").expect("WW_VENDOR_HASH not set");
+    let app_hash = std::env::var("WW_APP_HASH").expect("WW_APP_HASH not set");
+    let revision = std::env::var("WW_REVISION")
+        .expect("WW_REVISION not set")
+        .parse()
+        .unwrap();
+    let secret = std::env::var("WW_SECRET").expect("WW_SECRET not set");
+    let webware_url = std::env::var("WW_WEBWARE_URL").expect("WW_WEBWARE_URL not set");
+    let table_id = std::env::var("WW_IDB_TABLE")
+        .expect("WW_IDB_TABLE not set")
+        .parse()
+        .unwrap();
+
+    let client = WebwareClient::builder()
+        .webware_url(&webware_url)
+        .vendor_hash(&vendor_hash)
+        .app_hash(&app_hash)
+        .secret(&secret)
+        .revision(revision)
+        .build();
+
+    let mut registered_client = client.register().await.expect("failed to register");
+
+    get_idb_rows(&mut registered_client, &IdbTable::new(table_id)).await;
+
+    registered_client
+        .deregister()
+        .await
+        .expect("failed to deregister");
+}
@@ -0,0 +1,271 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::states::Ready;
+use crate::{Parameters, WWClientResult, WWResponse, WWSVCError, WebwareClient};
+
+/// Parameter containing the comma separated fields the rows are grouped by.
+pub const GROUP_PARAMETER: &str = "GRUPPIERUNG";
+/// Parameter containing the comma separated aggregates, e.g. `SUMME(ART_1_80)`.
+pub const AGGREGATE_PARAMETER: &str = "AGGREGATION";
+
+/// Function computed by the server over the rows of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AggregateFunction {
+    /// Sum of the values.
+    Sum,
+    /// Number of rows.
+    Count,
+    /// Smallest value.
+    Min,
+    /// Largest value.
+    Max,
+    /// Average of the values.
+    Avg,
+}
+
+impl AggregateFunction {
+    /// Returns the name of the function, as sent to the server.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AggregateFunction::Sum => "SUMME",
+            AggregateFunction::Count => "ANZAHL",
+            AggregateFunction::Min => "MIN",
+            AggregateFunction::Max => "MAX",
+            AggregateFunction::Avg => "DURCHSCHNITT",
+        }
+    }
+}
+
+impl Display for AggregateFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A function applied to a field, e.g. the sum of `ART_1_80`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Aggregate {
+    /// The function computed by the server.
+    pub function: AggregateFunction,
+    /// The code of the field, e.g. `ART_1_80`.
+    pub field: String,
+}
+
+impl Aggregate {
+    /// Returns the name of the column containing the aggregate in the response, e.g.
+    /// `SUMME_ART_1_80`.
+    pub fn column(&self) -> String {
+        format!("{}_{}", self.function, self.field)
+    }
+}
+
+/// Displays the aggregate as sent to the server, e.g. `SUMME(ART_1_80)`.
+impl Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", self.function, self.field)
+    }
+}
+
+/// Grouping and aggregates computed by the server instead of returning the raw rows, e.g. the
+/// stock per Warengruppe.
+///
+/// The aggregation is sent in the `GRUPPIERUNG` and `AGGREGATION` parameters, which are only
+/// supported by functions and installations providing server-side aggregation. Each returned row
+/// contains the group fields and one column per aggregate, see `Aggregate::column()`.
+///
+/// ## Example
+///
+/// ```
+/// use wwsvc_rs::aggregation::Aggregation;
+/// use wwsvc_rs::Parameters;
+///
+/// let aggregation = Aggregation::new().group_by("ART_1_10").sum("ART_1_80").count("ART_1_25");
+/// let mut parameters = Parameters::new();
+/// aggregation.apply(&mut parameters);
+/// assert_eq!(parameters.get("GRUPPIERUNG"), Some("ART_1_10"));
+/// assert_eq!(
+///     parameters.get("AGGREGATION"),
+///     Some("SUMME(ART_1_80),ANZAHL(ART_1_25)")
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aggregation {
+    group_by: Vec<String>,
+    aggregates: Vec<Aggregate>,
+}
+
+impl Aggregation {
+    /// Creates an aggregation without groups and aggregates.
+    pub fn new() -> Aggregation {
+        Aggregation::default()
+    }
+
+    /// Groups the rows by the given field, in addition to the previous group fields.
+    pub fn group_by(mut self, field: &str) -> Aggregation {
+        self.group_by.push(field.to_string());
+        self
+    }
+
+    /// Computes `function` over the given field for each group.
+    pub fn aggregate(mut self, function: AggregateFunction, field: &str) -> Aggregation {
+        self.aggregates.push(Aggregate {
+            function,
+            field: field.to_string(),
+        });
+        self
+    }
+
+    /// Computes the sum of the given field for each group.
+    pub fn sum(self, field: &str) -> Aggregation {
+        self.aggregate(AggregateFunction::Sum, field)
+    }
+
+    /// Computes the number of rows for each group, counting the given field.
+    pub fn count(self, field: &str) -> Aggregation {
+        self.aggregate(AggregateFunction::Count, field)
+    }
+
+    /// Computes the smallest value of the given field for each group.
+    pub fn min(self, field: &str) -> Aggregation {
+        self.aggregate(AggregateFunction::Min, field)
+    }
+
+    /// Computes the largest value of the given field for each group.
+    pub fn max(self, field: &str) -> Aggregation {
+        self.aggregate(AggregateFunction::Max, field)
+    }
+
+    /// Computes the average of the given field for each group.
+    pub fn avg(self, field: &str) -> Aggregation {
+        self.aggregate(AggregateFunction::Avg, field)
+    }
+
+    /// Returns the fields the rows are grouped by.
+    pub fn groups(&self) -> &[String] {
+        &self.group_by
+    }
+
+    /// Returns the aggregates computed for each group.
+    pub fn aggregates(&self) -> &[Aggregate] {
+        &self.aggregates
+    }
+
+    /// Sets the `GRUPPIERUNG` and `AGGREGATION` parameters. Parameters without content are
+    /// omitted.
+    pub fn apply(&self, parameters: &mut Parameters) {
+        if !self.group_by.is_empty() {
+            parameters.insert(GROUP_PARAMETER, self.group_by.join(","));
+        }
+        if !self.aggregates.is_empty() {
+            let aggregates = self
+                .aggregates
+                .iter()
+                .map(Aggregate::to_string)
+                .collect::<Vec<_>>();
+            parameters.insert(AGGREGATE_PARAMETER, aggregates.join(","));
+        }
+    }
+}
+
+/// Row of an aggregated response, containing the group fields and the aggregate columns.
+///
+/// Aggregates are accepted both as numbers and as strings, with a decimal point or comma.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AggregatedRow {
+    /// The members of the row.
+    #[serde(flatten)]
+    pub columns: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AggregatedRow {
+    /// Returns the value of a group field as a string, formatting numbers.
+    pub fn group(&self, field: &str) -> Option<String> {
+        match self.columns.get(field)? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            serde_json::Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Returns the value of the given aggregate.
+    pub fn value(&self, function: AggregateFunction, field: &str) -> Option<f64> {
+        let column = Aggregate {
+            function,
+            field: field.to_string(),
+        }
+        .column();
+        match self.columns.get(&column)? {
+            serde_json::Value::Number(number) => number.as_f64(),
+            serde_json::Value::String(value) => value.trim().replace(',', ".").parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the sum of the given field.
+    pub fn sum(&self, field: &str) -> Option<f64> {
+        self.value(AggregateFunction::Sum, field)
+    }
+
+    /// Returns the number of rows counted over the given field.
+    pub fn count(&self, field: &str) -> Option<u64> {
+        self.value(AggregateFunction::Count, field)
+            .filter(|count| *count >= 0.0)
+            .map(|count| count as u64)
+    }
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Requests `function` with the grouping and aggregates of `aggregation`, so the server
+    /// computes them instead of returning the raw rows.
+    ///
+    /// The rows are read from the first list of the response. Returns
+    /// `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status, e.g. because
+    /// the function doesn't support aggregation.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use wwsvc_rs::aggregation::Aggregation;
+    /// use wwsvc_rs::{Parameters, WebwareClient};
+    ///
+    /// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+    /// let rows = client
+    ///     .aggregate(
+    ///         "ARTIKEL.GET",
+    ///         1,
+    ///         Parameters::new(),
+    ///         &Aggregation::new().group_by("ART_1_10").sum("ART_1_80"),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// for row in rows {
+    ///     println!("{:?}: {:?}", row.group("ART_1_10"), row.sum("ART_1_80"));
+    /// }
+    /// # }
+    /// ```
+    pub async fn aggregate(
+        &mut self,
+        function: &str,
+        version: u32,
+        mut parameters: Parameters,
+        aggregation: &Aggregation,
+    ) -> WWClientResult<Vec<AggregatedRow>> {
+        aggregation.apply(&mut parameters);
+        let response = self
+            .request(None, function, version, parameters, None)
+            .await?;
+        if let Some(com_result) = WWResponse::com_result(&response).filter(|r| !r.is_success()) {
+            return Err(WWSVCError::FunctionFailed {
+                function: function.to_string(),
+                com_result: Box::new(com_result),
+            });
+        }
+        response
+            .into_items()
+            .into_iter()
+            .map(|row| Ok(serde_json::from_value(row)?))
+            .collect()
+    }
+}
@@ -15,15 +15,37 @@ pub struct AppHash {
 impl AppHash {
     /// Returns a new AppHash object from the current request ID and the application secret of a `WebwareClient`.
     ///
+    /// The returned object uses the request ID following `request_id`.
+    ///
     /// Can be formatted as lowercase hexadecimal for ease of use.
     pub fn new(request_id: u32, app_secret: &str) -> AppHash {
-        let now = fmt_http_date(SystemTime::now());
-        let new_request_id = request_id + 1;
+        AppHash::for_request(request_id + 1, app_secret)
+    }
+
+    /// Returns a new AppHash object for the request with the given ID.
+    ///
+    /// Can be formatted as lowercase hexadecimal for ease of use.
+    ///
+    /// With the `test-util` feature, the timestamp and the salt can be fixed with
+    /// `test_util::fix_app_hash()`.
+    pub fn for_request(request_id: u32, app_secret: &str) -> AppHash {
+        #[cfg(feature = "test-util")]
+        if let Some(fixed) = crate::test_util::fixed_app_hash() {
+            let salt = fixed.salt.as_deref().unwrap_or(app_secret);
+            return AppHash::at(request_id, salt, fixed.date);
+        }
+
+        AppHash::at(request_id, app_secret, SystemTime::now())
+    }
+
+    /// Returns a new AppHash object for the request with the given ID, signed at the given time.
+    pub fn at(request_id: u32, app_secret: &str, date: SystemTime) -> AppHash {
+        let now = fmt_http_date(date);
         let combined = format!("{}{}", app_secret, now);
         let (cow, _encoding_used, _had_errors) = WINDOWS_1252.encode(&combined[..]);
         let md5_hash = format!("{:x}", md5::compute(cow));
         AppHash {
-            request_id: new_request_id,
+            request_id,
             hash: md5_hash,
             date_formatted: now,
         }
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::responses::ComResult;
+
+/// Function which returns the state and the result of a ticket.
+pub const TICKET_FUNCTION: &str = "TICKET.GET";
+
+/// Ticket of a function call executed in `ASYNCHRON` mode, as returned in the `TICKET` object of
+/// the response.
+///
+/// The result is fetched with `WebwareClient::fetch_async_result()`, until the state of the
+/// ticket is terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AsyncTicket {
+    /// The ID of the ticket.
+    #[serde(rename = "ID")]
+    pub id: String,
+    /// The state of the execution.
+    #[serde(rename = "STATUS", default)]
+    pub state: AsyncState,
+}
+
+impl AsyncTicket {
+    /// Returns whether the execution has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        self.state.is_terminal()
+    }
+}
+
+/// Displays the ticket as `ID (STATE)`.
+impl Display for AsyncTicket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.id, self.state)
+    }
+}
+
+/// State of the execution of a ticket.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum AsyncState {
+    /// The function call is waiting to be executed.
+    #[default]
+    Queued,
+    /// The function call is being executed.
+    Running,
+    /// The function call has finished, the result is available.
+    Done,
+    /// The function call has failed.
+    Failed,
+    /// The function call has been cancelled.
+    Cancelled,
+    /// Any other state, in upper case. Considered not terminal.
+    Other(String),
+}
+
+impl AsyncState {
+    /// Returns whether the state is final, i.e. the ticket won't change anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            AsyncState::Done | AsyncState::Failed | AsyncState::Cancelled
+        )
+    }
+
+    /// Returns whether the function call has finished successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self, AsyncState::Done)
+    }
+}
+
+impl Display for AsyncState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AsyncState::Queued => f.write_str("QUEUED"),
+            AsyncState::Running => f.write_str("RUNNING"),
+            AsyncState::Done => f.write_str("DONE"),
+            AsyncState::Failed => f.write_str("FAILED"),
+            AsyncState::Cancelled => f.write_str("CANCELLED"),
+            AsyncState::Other(state) => f.write_str(state),
+        }
+    }
+}
+
+impl From<&str> for AsyncState {
+    fn from(state: &str) -> Self {
+        match state.trim().to_uppercase().as_str() {
+            "QUEUED" | "WAITING" => AsyncState::Queued,
+            "RUNNING" => AsyncState::Running,
+            "DONE" | "FINISHED" => AsyncState::Done,
+            "FAILED" | "ERROR" => AsyncState::Failed,
+            "CANCELLED" | "CANCELED" => AsyncState::Cancelled,
+            other => AsyncState::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for AsyncState {
+    fn from(state: String) -> Self {
+        AsyncState::from(state.as_str())
+    }
+}
+
+impl From<AsyncState> for String {
+    fn from(state: AsyncState) -> Self {
+        state.to_string()
+    }
+}
+
+/// Response to a function call executed in `ASYNCHRON` mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncTicketResponse {
+    /// The result of the call.
+    #[serde(rename = "COMRESULT")]
+    pub com_result: Option<ComResult>,
+    /// The ticket of the execution.
+    #[serde(rename = "TICKET")]
+    pub ticket: AsyncTicket,
+}
+
+/// Response of `TICKET.GET`, containing the current state of a ticket and, once the execution is
+/// done, the response of the function call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncResult<T> {
+    /// The result of the call.
+    #[serde(rename = "COMRESULT")]
+    pub com_result: Option<ComResult>,
+    /// The ticket with its current state.
+    #[serde(rename = "TICKET")]
+    pub ticket: AsyncTicket,
+    /// The response of the function call, if the execution is done.
+    #[serde(
+        rename = "RESULT",
+        default = "Option::default",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub result: Option<T>,
+}
+
+impl<T> AsyncResult<T> {
+    /// Returns whether the execution has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        self.ticket.is_terminal()
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+/// Policy for retrying a request with exponentially increasing delays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackoffPolicy {
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_delay: Duration,
+    /// Factor by which the delay grows after each failed attempt.
+    pub multiplier: f64,
+    /// Maximum amount of attempts, including the first one.
+    pub max_attempts: u32,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            max_attempts: 5,
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Returns the delay to wait after the given (zero-based) failed attempt.
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powf(attempt as f64);
+        let secs = self.initial_delay.as_secs_f64() * factor;
+        if secs.is_finite() && secs < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max_delay
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Deserializer};
+
+use crate::WWClientResult;
+
+/// Encodes binary data as base64, the way the WEBSERVICES expect binary content in `PCONTENT`.
+pub fn encode(data: &[u8]) -> String {
+    STANDARD.encode(data)
+}
+
+/// Decodes base64 encoded binary content returned by the WEBSERVICES.
+pub fn decode(data: &str) -> WWClientResult<Vec<u8>> {
+    Ok(STANDARD.decode(data.trim())?)
+}
+
+/// Deserializes a base64 encoded response field into bytes.
+///
+/// ## Example
+///
+/// ```
+/// #[derive(serde::Deserialize)]
+/// pub struct ArticleImage {
+///     #[serde(rename = "BILD", deserialize_with = "wwsvc_rs::binary::deserialize")]
+///     pub image: Vec<u8>,
+/// }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let encoded = String::deserialize(deserializer)?;
+    STANDARD
+        .decode(encoded.trim())
+        .map_err(serde::de::Error::custom)
+}
@@ -1,16 +1,43 @@
+use bytes::Bytes;
 use futures::future::BoxFuture;
-use reqwest::header::{HeaderMap, HeaderValue};
+use futures::FutureExt;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use reqwest::Response;
 use serde::de::DeserializeOwned;
 use std::collections::HashMap;
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
+use std::net::{IpAddr, SocketAddr};
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
 use typed_builder::TypedBuilder;
 use url::Url;
 
+use crate::asynchron::{AsyncResult, AsyncTicket, AsyncTicketResponse, TICKET_FUNCTION};
+use crate::backoff::BackoffPolicy;
 use crate::client::states::*;
-use crate::error::WWSVCError;
-use crate::responses::RegisterResponse;
-use crate::{AppHash, Credentials, Cursor, WWClientResult};
+use crate::compat::Compat;
+use crate::compression::{Compressor, RequestCompression};
+use crate::credentials::CredentialRotation;
+use crate::cursor::{AbandonedCursor, CursorSnapshot, CLOSE_HEADER};
+use crate::diagnostics::{self, RecentResult, RecentResults};
+use crate::error::{ErrorCategory, WWSVCError};
+use crate::events::{is_mutation, MutationEvent, MutationHooks, MutationReceiver};
+use crate::headers::{ResultType, ACCEPT_RESULT_TYPE, EXECUTE_MODE};
+use crate::lints::{self, Lint};
+use crate::logging;
+use crate::normalization::TextNormalization;
+use crate::parameters::MergePrecedence;
+use crate::registry::{Release, Reservation};
+use crate::request_id::{RequestId, RequestIdGenerator, SequentialIds};
+use crate::request_log::{RequestLogEntry, RequestLogSink};
+use crate::requests::{
+    is_read_only_function, BatchRequest, BodyOrder, BodySizeLimit, ExecJsonBatchRequest,
+    ExecJsonRequest, ExecuteMode, FunctionPolicy, MethodPolicy, ServiceFunction, ServicePassInfo,
+};
+use crate::responses::{BatchResponse, ComResult, RegisterResponse};
+use crate::{
+    AppHash, Credentials, CredentialsProvider, Cursor, Parameters, WWClientResult, WWResponse,
+};
 
 /// The internal builder for constructing a `WebwareClient`
 #[derive(TypedBuilder)]
@@ -18,11 +45,13 @@ use crate::{AppHash, Credentials, Cursor, WWClientResult};
 pub struct InternalWebwareClient {
     /// Full URL to the WEBWARE instance without the path to the WWSVC
     ///
-    /// Example: `https://localhost:8080`
-    #[builder(setter(transform = |url: &str| {
-        Url::parse(url).expect("Failed to parse URL").join("/WWSVC/").expect("Failed to join URL")
+    /// Example: `https://localhost:8080`, or `("localhost", 8080, "https")`
+    ///
+    /// An invalid URL is returned as an error by the first request.
+    #[builder(setter(transform = |url: impl IntoWebwareUrl| {
+        url.into_webware_url().and_then(|url| url.join("/WWSVC/"))
     }))]
-    webware_url: Url,
+    webware_url: Result<Url, url::ParseError>,
     /// Vendor hash of the application
     #[builder(setter(transform = |vendor_hash: &str| vendor_hash.to_string()))]
     vendor_hash: String,
@@ -37,15 +66,186 @@ pub struct InternalWebwareClient {
     /// Credentials of the client
     #[builder(default, setter(transform = |credentials: Credentials| Some(credentials)))]
     credentials: Option<Credentials>,
+    /// Source of stored credentials, consulted when registering and when the credentials are rejected
+    #[builder(default, setter(transform = |provider: impl CredentialsProvider + 'static| {
+        Some(Arc::new(provider) as Arc<dyn CredentialsProvider>)
+    }))]
+    credentials_provider: Option<Arc<dyn CredentialsProvider>>,
     /// Maximum amount of objects that are returned in a request
-    #[builder(default = 1000)]
+    #[builder(default = lints::DEFAULT_RESULT_MAX_LINES)]
     result_max_lines: u32,
+    /// Maximum amount of objects that are returned in a request, per function name
+    ///
+    /// Takes precedence over `result_max_lines`, unless a cursor is used or the header is set explicitly.
+    #[builder(default, setter(transform = |max_lines: HashMap<&str, u32>| {
+        max_lines.into_iter().map(|(function, lines)| (function.to_uppercase(), lines)).collect()
+    }))]
+    function_max_lines: HashMap<String, u32>,
+    /// Revisions of functions, used by types deriving the data traits with `version = "auto"`
+    #[builder(default, setter(transform = |versions: HashMap<&str, u32>| {
+        versions.into_iter().map(|(function, version)| (function.to_uppercase(), version)).collect()
+    }))]
+    function_versions: HashMap<String, u32>,
     /// Allow unsafe SSL certificates
     #[builder(default = false)]
     allow_insecure: bool,
     /// Timeout for the request
     #[builder(default = std::time::Duration::from_secs(60))]
     timeout: std::time::Duration,
+    /// Compatibility profile for the WEBWARE version
+    #[builder(default)]
+    compat: Compat,
+    /// Order of the members in the request body
+    #[builder(default)]
+    body_order: BodyOrder,
+    /// HTTP methods for requests without an explicit method
+    #[builder(default)]
+    method_policy: MethodPolicy,
+    /// Refuse all functions except `.GET` functions, see `requests::is_read_only_function()`
+    #[builder(default = false)]
+    read_only: bool,
+    /// Function names the client may call
+    #[builder(default)]
+    function_policy: FunctionPolicy,
+    /// Limits of the size of request bodies
+    #[builder(default)]
+    body_size_limit: BodySizeLimit,
+    /// Normalization of the strings in response bodies
+    #[builder(default)]
+    text_normalization: TextNormalization,
+    /// Strategy for generating request IDs
+    #[builder(
+        default = Arc::new(SequentialIds),
+        setter(transform = |generator: impl RequestIdGenerator + 'static| Arc::new(generator) as Arc<dyn RequestIdGenerator>)
+    )]
+    request_ids: Arc<dyn RequestIdGenerator>,
+    /// Sink of the log of sent requests
+    #[builder(
+        default,
+        setter(transform = |sink: impl RequestLogSink + 'static| Some(Arc::new(sink) as Arc<dyn RequestLogSink>))
+    )]
+    request_log: Option<Arc<dyn RequestLogSink>>,
+    /// Number of recent COMRESULTs kept for diagnostics
+    #[builder(default = diagnostics::DEFAULT_RECENT_RESULTS)]
+    recent_results: usize,
+    /// Mode in which function calls are executed
+    #[builder(default, setter(into))]
+    execute_mode: ExecuteMode,
+    /// Compression of EXECJSON request bodies
+    #[builder(default)]
+    request_compression: RequestCompression,
+}
+
+/// Types which can be used as the URL of a WEBWARE instance, i.e. strings, `Url`s and
+/// `(host, port, scheme)` tuples, where the host is a name or an IP address.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::net::{IpAddr, Ipv6Addr};
+/// use wwsvc_rs::client::IntoWebwareUrl;
+///
+/// let url = ("localhost", 8080, "https").into_webware_url().unwrap();
+/// assert_eq!(url.as_str(), "https://localhost:8080/");
+///
+/// let url = (IpAddr::V6(Ipv6Addr::LOCALHOST), 8080, "http").into_webware_url().unwrap();
+/// assert_eq!(url.as_str(), "http://[::1]:8080/");
+/// ```
+pub trait IntoWebwareUrl {
+    /// Converts the value to a URL.
+    fn into_webware_url(self) -> Result<Url, url::ParseError>;
+}
+
+impl IntoWebwareUrl for Url {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        Ok(self)
+    }
+}
+
+impl IntoWebwareUrl for &Url {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoWebwareUrl for &str {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(self)
+    }
+}
+
+impl IntoWebwareUrl for String {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(&self)
+    }
+}
+
+impl IntoWebwareUrl for &String {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(self)
+    }
+}
+
+impl IntoWebwareUrl for (&str, u16, &str) {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        let (host, port, scheme) = self;
+        Url::parse(&format!("{}://{}:{}", scheme, host, port))
+    }
+}
+
+impl IntoWebwareUrl for (IpAddr, u16, &str) {
+    fn into_webware_url(self) -> Result<Url, url::ParseError> {
+        let (ip, port, scheme) = self;
+        // formats IPv6 addresses in brackets
+        Url::parse(&format!("{}://{}", scheme, SocketAddr::new(ip, port)))
+    }
+}
+
+/// Returns the headers which are the same for all requests of a client.
+fn static_headers(compat: Compat, execute_mode: &ExecuteMode) -> HeaderMap {
+    let mut headers = HeaderMap::with_capacity(8);
+    if let Ok(value) = ResultType::Json.header_value() {
+        headers.insert(ACCEPT_RESULT_TYPE, value);
+    }
+    if compat.execute_mode_in_headers() {
+        if let Ok(value) = execute_mode.header_value() {
+            headers.insert(EXECUTE_MODE, value);
+        }
+    }
+    headers
+}
+
+/// Maximum number of characters of a response body quoted in `WWSVCError::UnexpectedContentType`.
+const BODY_SNIPPET_CHARS: usize = 200;
+
+/// Fails if a response body obviously isn't JSON, e.g. the HTML maintenance page of a load
+/// balancer, which would otherwise surface as a cryptic deserialization error.
+///
+/// A body is refused if it starts with `<`, or if it is declared as something else than JSON and
+/// doesn't start like a JSON object or array.
+fn check_json_body(status: u16, content_type: &str, body: &[u8]) -> WWClientResult<()> {
+    let start = body
+        .strip_prefix(b"\xEF\xBB\xBF".as_slice())
+        .unwrap_or(body)
+        .iter()
+        .find(|byte| !byte.is_ascii_whitespace());
+    let declared_json = content_type.is_empty() || content_type.to_lowercase().contains("json");
+    let looks_like_json = matches!(start, Some(b'{' | b'['));
+    if start == Some(&b'<') || (!declared_json && !looks_like_json && start.is_some()) {
+        let snippet = String::from_utf8_lossy(body)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(BODY_SNIPPET_CHARS)
+            .collect();
+        return Err(WWSVCError::UnexpectedContentType {
+            status,
+            content_type: content_type.to_string(),
+            snippet,
+        });
+    }
+    Ok(())
 }
 
 /// Contains the the states the client can be in
@@ -77,7 +277,7 @@ pub mod states {
 #[derive(Clone)]
 pub struct WebwareClient<State = Unregistered> {
     /// Full URL to the WEBWARE instance
-    webware_url: Url,
+    webware_url: Result<Url, url::ParseError>,
     /// Vendor hash of the application
     vendor_hash: String,
     /// Application hash of the application
@@ -88,27 +288,72 @@ pub struct WebwareClient<State = Unregistered> {
     revision: u32,
     /// Credentials of the client
     credentials: Option<Credentials>,
+    /// Source of stored credentials
+    credentials_provider: Option<Arc<dyn CredentialsProvider>>,
+    /// Credentials replaced at runtime, shared between clones
+    rotation: CredentialRotation,
     /// Maximum amount of objects that are returned in a request
     result_max_lines: u32,
+    /// Maximum amount of objects that are returned in a request, per function name
+    function_max_lines: HashMap<String, u32>,
+    /// Revisions of functions, per function name
+    function_versions: HashMap<String, u32>,
     /// Request cursor for pagination,
     cursor: Option<Cursor>,
-    /// Current request ID
+    /// Number of the current request
     current_request: u32,
-    /// The client
-    client: reqwest::Client,
+    /// ID of the current request
+    request_id: RequestId,
+    /// Strategy for generating request IDs
+    request_ids: Arc<dyn RequestIdGenerator>,
+    request_log: Option<Arc<dyn RequestLogSink>>,
+    /// Most recent COMRESULTs, shared between clones
+    recent_results: RecentResults,
+    /// Mode in which function calls are executed
+    execute_mode: ExecuteMode,
+    /// EXECJSON URL for the next request, instead of the computed one
+    endpoint_override: Option<Url>,
+    /// Timeout of the next EXECJSON request, replacing the timeout of the client
+    timeout_override: Option<std::time::Duration>,
+    /// The HTTP client, or the error building it failed with
+    client: Result<reqwest::Client, Arc<reqwest::Error>>,
+    /// Headers which are the same for all requests
+    static_headers: HeaderMap,
     /// Suspend the cursor
     suspend_cursor: bool,
+    /// Cursors dropped before their last page, which are closed before the next request
+    abandoned_cursors: Vec<AbandonedCursor>,
+    /// Subscribers for mutation events
+    hooks: MutationHooks,
+    /// Compatibility profile for the WEBWARE version
+    compat: Compat,
+    /// Order of the members in the request body
+    body_order: BodyOrder,
+    /// HTTP methods for requests without an explicit method
+    method_policy: MethodPolicy,
+    /// Refuse all functions except `.GET` functions, see `requests::is_read_only_function()`
+    read_only: bool,
+    /// Function names the client may call
+    function_policy: FunctionPolicy,
+    /// Limits of the size of request bodies
+    body_size_limit: BodySizeLimit,
+    /// Compression of request bodies, shared between clones once rejected
+    compressor: Compressor,
+    text_normalization: TextNormalization,
 
     state: std::marker::PhantomData<State>,
 }
 
 impl From<InternalWebwareClient> for WebwareClient<Unregistered> {
     fn from(client: InternalWebwareClient) -> Self {
-        let req_client = reqwest::Client::builder()
+        lints::warn(Lint::insecure_tls(client.allow_insecure));
+        let mut req_builder = reqwest::Client::builder()
             .danger_accept_invalid_certs(client.allow_insecure)
-            .timeout(client.timeout)
-            .build()
-            .expect("Failed to build client");
+            .timeout(client.timeout);
+        if client.compat.title_case_headers() {
+            req_builder = req_builder.http1_title_case_headers();
+        }
+        let req_client = req_builder.build().map_err(Arc::new);
 
         WebwareClient {
             webware_url: client.webware_url,
@@ -117,11 +362,33 @@ impl From<InternalWebwareClient> for WebwareClient<Unregistered> {
             secret: client.secret,
             revision: client.revision,
             credentials: client.credentials,
+            credentials_provider: client.credentials_provider,
+            rotation: CredentialRotation::default(),
             result_max_lines: client.result_max_lines,
+            function_max_lines: client.function_max_lines,
+            function_versions: client.function_versions,
             cursor: None,
             current_request: 0,
+            request_id: RequestId::default(),
+            request_ids: client.request_ids,
+            request_log: client.request_log,
+            recent_results: RecentResults::new(client.recent_results),
+            execute_mode: client.execute_mode.clone(),
+            endpoint_override: None,
+            timeout_override: None,
             client: req_client,
+            static_headers: static_headers(client.compat, &client.execute_mode),
             suspend_cursor: false,
+            abandoned_cursors: Vec::new(),
+            hooks: MutationHooks::default(),
+            compat: client.compat,
+            body_order: client.body_order,
+            method_policy: client.method_policy,
+            read_only: client.read_only,
+            function_policy: client.function_policy,
+            body_size_limit: client.body_size_limit,
+            compressor: Compressor::new(client.request_compression),
+            text_normalization: client.text_normalization,
             state: std::marker::PhantomData::<Unregistered>,
         }
     }
@@ -131,14 +398,20 @@ impl TryFrom<InternalWebwareClient> for WebwareClient<Registered> {
     type Error = WWSVCError;
 
     fn try_from(client: InternalWebwareClient) -> Result<Self, Self::Error> {
-        let req_client = reqwest::Client::builder()
+        lints::warn(Lint::insecure_tls(client.allow_insecure));
+        let mut req_builder = reqwest::Client::builder()
             .danger_accept_invalid_certs(client.allow_insecure)
-            .timeout(client.timeout)
-            .build()
-            .expect("Failed to build client");
+            .timeout(client.timeout);
+        if client.compat.title_case_headers() {
+            req_builder = req_builder.http1_title_case_headers();
+        }
+        let req_client = Ok(req_builder.build()?);
 
-        if client.credentials.is_none() {
-            return Err(WWSVCError::MissingCredentials);
+        match &client.credentials {
+            Some(credentials) => {
+                credentials.check_binding(&client.vendor_hash, &client.app_hash, client.revision)?
+            }
+            None => return Err(WWSVCError::MissingCredentials),
         }
 
         Ok(WebwareClient {
@@ -148,11 +421,33 @@ impl TryFrom<InternalWebwareClient> for WebwareClient<Registered> {
             secret: client.secret,
             revision: client.revision,
             credentials: client.credentials,
+            credentials_provider: client.credentials_provider,
+            rotation: CredentialRotation::default(),
             result_max_lines: client.result_max_lines,
+            function_max_lines: client.function_max_lines,
+            function_versions: client.function_versions,
             cursor: None,
             current_request: 0,
+            request_id: RequestId::default(),
+            request_ids: client.request_ids,
+            request_log: client.request_log,
+            recent_results: RecentResults::new(client.recent_results),
+            execute_mode: client.execute_mode.clone(),
+            endpoint_override: None,
+            timeout_override: None,
             client: req_client,
+            static_headers: static_headers(client.compat, &client.execute_mode),
             suspend_cursor: false,
+            abandoned_cursors: Vec::new(),
+            hooks: MutationHooks::default(),
+            compat: client.compat,
+            body_order: client.body_order,
+            method_policy: client.method_policy,
+            read_only: client.read_only,
+            function_policy: client.function_policy,
+            body_size_limit: client.body_size_limit,
+            compressor: Compressor::new(client.request_compression),
+            text_normalization: client.text_normalization,
             state: std::marker::PhantomData::<Registered>,
         })
     }
@@ -166,8 +461,20 @@ impl WebwareClient {
 
     /// Sends a `REGISTER` request to the WEBWARE instance and returns a registered client
     /// or an error
-    pub async fn register(self) -> WWClientResult<WebwareClient<Registered>> {
-        if self.credentials.is_some() {
+    ///
+    /// If a `CredentialsProvider` is configured and returns stored credentials, these are used instead.
+    ///
+    /// If the limit set with `registry::set_registration_limit()` has been reached,
+    /// `WWSVCError::RegistrationLimitExceeded` is returned without sending a request.
+    pub async fn register(mut self) -> WWClientResult<WebwareClient<Registered>> {
+        if self.credentials.is_none() {
+            if let Some(provider) = &self.credentials_provider {
+                self.credentials = provider.credentials().await?;
+            }
+        }
+
+        if let Some(credentials) = &self.credentials {
+            credentials.check_binding(&self.vendor_hash, &self.app_hash, self.revision)?;
             return Ok(WebwareClient {
                 webware_url: self.webware_url,
                 vendor_hash: self.vendor_hash,
@@ -175,11 +482,33 @@ impl WebwareClient {
                 secret: self.secret,
                 revision: self.revision,
                 credentials: self.credentials,
+                credentials_provider: self.credentials_provider,
+                rotation: self.rotation,
                 result_max_lines: self.result_max_lines,
+                function_max_lines: self.function_max_lines,
+                function_versions: self.function_versions,
                 cursor: self.cursor,
                 current_request: self.current_request,
+                request_id: self.request_id,
+                request_ids: self.request_ids,
+                request_log: self.request_log,
+                recent_results: self.recent_results,
+                execute_mode: self.execute_mode,
+                endpoint_override: self.endpoint_override,
+                timeout_override: self.timeout_override,
                 client: self.client,
+                static_headers: self.static_headers,
                 suspend_cursor: self.suspend_cursor,
+                abandoned_cursors: self.abandoned_cursors,
+                hooks: self.hooks,
+                compat: self.compat,
+                body_order: self.body_order,
+                method_policy: self.method_policy,
+                read_only: self.read_only,
+                function_policy: self.function_policy,
+                body_size_limit: self.body_size_limit,
+                compressor: self.compressor,
+                text_normalization: self.text_normalization,
                 state: std::marker::PhantomData::<Registered>,
             });
         }
@@ -187,15 +516,28 @@ impl WebwareClient {
         // join self.webware_url and the register path
         // example: "WWSERVICE", "REGISTER", &self.vendor_hash, &self.app_hash, &self.secret, &self.revision.to_string()
         let target_url = self
-            .webware_url
+            .base_url()?
             .join("WWSERVICE/")?
             .join("REGISTER/")?
             .join(&format!("{}/", self.vendor_hash))?
             .join(&format!("{}/", self.app_hash))?
             .join(&format!("{}/", self.secret))?
             .join(&format!("{}/", self.revision))?;
-        let response = self.client.get(target_url).send().await?;
+        let reservation = Reservation::new(&self.vendor_hash, &self.app_hash)?;
+        let response = self.http_client()?.get(target_url).send().await?;
         let response_obj = response.json::<RegisterResponse>().await?;
+        reservation.complete(&response_obj.service_pass.pass_id);
+        logging::debug_event!(
+            vendor_hash = %self.vendor_hash,
+            app_hash = %self.app_hash,
+            status = response_obj.com_result.status,
+            "registered"
+        );
+        let credentials = Credentials::new(
+            &response_obj.service_pass.pass_id,
+            &response_obj.service_pass.app_id,
+        )
+        .bound_to(&self.vendor_hash, &self.app_hash, self.revision);
 
         Ok(WebwareClient {
             webware_url: self.webware_url,
@@ -203,22 +545,78 @@ impl WebwareClient {
             app_hash: self.app_hash,
             secret: self.secret,
             revision: self.revision,
-            credentials: Some(Credentials {
-                service_pass: response_obj.service_pass.pass_id,
-                app_id: response_obj.service_pass.app_id,
-            }),
+            credentials: Some(credentials),
+            credentials_provider: self.credentials_provider,
+            rotation: self.rotation,
             result_max_lines: self.result_max_lines,
+            function_max_lines: self.function_max_lines,
+            function_versions: self.function_versions,
             cursor: self.cursor,
             current_request: self.current_request,
+            request_id: self.request_id,
+            request_ids: self.request_ids,
+            request_log: self.request_log,
+            recent_results: self.recent_results,
+            execute_mode: self.execute_mode,
+            endpoint_override: self.endpoint_override,
+            timeout_override: self.timeout_override,
             client: self.client,
+            static_headers: self.static_headers,
             suspend_cursor: self.suspend_cursor,
+            abandoned_cursors: self.abandoned_cursors,
+            hooks: self.hooks,
+            compat: self.compat,
+            body_order: self.body_order,
+            method_policy: self.method_policy,
+            read_only: self.read_only,
+            function_policy: self.function_policy,
+            body_size_limit: self.body_size_limit,
+            compressor: self.compressor,
+            text_normalization: self.text_normalization,
             state: std::marker::PhantomData::<Registered>,
         })
     }
 
+    /// Sends a `REGISTER` request like `register()`, but retries failed attempts with
+    /// exponentially increasing delays according to `policy`.
+    ///
+    /// Errors caused by invalid input or missing authentication are returned immediately, since
+    /// retrying them would only consume more of the license-limited `REGISTER` calls.
+    pub async fn register_with_backoff(
+        self,
+        policy: &BackoffPolicy,
+    ) -> WWClientResult<WebwareClient<Registered>> {
+        let mut attempt = 0;
+        loop {
+            match self.clone().register().await {
+                Ok(client) => return Ok(client),
+                Err(e) => {
+                    let retryable =
+                        !matches!(e.category(), ErrorCategory::Client | ErrorCategory::Auth);
+                    if !retryable || attempt + 1 >= policy.max_attempts {
+                        return Err(e);
+                    }
+
+                    let delay = policy.delay_for(attempt);
+                    logging::warn_event!(
+                        attempt = attempt + 1,
+                        max_attempts = policy.max_attempts,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "REGISTER failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
     /// Provides a harness for operating with the client by registering, running the provided closure and then deregistering
     /// the client.
     ///
+    /// If the closure panics, the client is deregistered before the panic is resumed.
+    ///
     /// ## Example
     ///
     /// ```rust,no_run
@@ -246,16 +644,228 @@ impl WebwareClient {
         F: for<'a> FnOnce(&'a mut WebwareClient<Registered>) -> BoxFuture<'a, T>,
     {
         let mut client = self.register().await?;
-        let result = f(&mut client).await;
-        let _ = client.deregister().await?;
-        Ok(result)
+        let result = AssertUnwindSafe(async { f(&mut client).await })
+            .catch_unwind()
+            .await;
+        let deregistered = client.deregister().await;
+
+        match result {
+            Ok(value) => deregistered.map(|_| value),
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+
+    /// Like `with_registered()`, but the closure has to complete within `timeout`.
+    ///
+    /// The client is deregistered even if the closure times out, so batch jobs don't leak service
+    /// passes. A timeout is returned as `WWSVCError::Timeout`. Deregistering has another `timeout`
+    /// to complete; if it times out, the service pass is released locally and invalidated by the
+    /// server after a while.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use wwsvc_rs::futures::FutureExt;
+    /// use wwsvc_rs::{Parameters, WebwareClient};
+    ///
+    /// # async fn example(client: WebwareClient) {
+    /// let articles = client
+    ///     .with_registered_timeout(Duration::from_secs(60), |client| {
+    ///         async move {
+    ///             client
+    ///                 .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+    ///                 .await
+    ///         }
+    ///         .boxed()
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn with_registered_timeout<F, T>(
+        self,
+        timeout: std::time::Duration,
+        f: F,
+    ) -> WWClientResult<T>
+    where
+        F: for<'a> FnOnce(&'a mut WebwareClient<Registered>) -> BoxFuture<'a, T>,
+    {
+        let mut client = self.register().await?;
+        let result = {
+            let run = AssertUnwindSafe(async { f(&mut client).await }).catch_unwind();
+            tokio::time::timeout(timeout, run).await
+        };
+        let deregistered = tokio::time::timeout(timeout, client.deregister())
+            .await
+            .map_err(|_| WWSVCError::Timeout(timeout))
+            .and_then(|deregistered| deregistered);
+
+        match result {
+            Ok(Ok(value)) => deregistered.map(|_| value),
+            Ok(Err(panic)) => std::panic::resume_unwind(panic),
+            Err(_) => Err(WWSVCError::Timeout(timeout)),
+        }
+    }
+}
+
+impl<State> WebwareClient<State> {
+    /// Subscribes to events which are emitted after a mutating function (see `events::is_mutation()`)
+    /// has been executed successfully through `request()`, `request_generic()` or `send_batch()`.
+    ///
+    /// A function has been executed successfully if both the HTTP status and the COMRESULT are
+    /// successful. Functions of a batch are only reported if the batch as a whole succeeded.
+    ///
+    /// Subscriptions are shared between clones of the client and survive state transitions.
+    /// Dropping the receiver unsubscribes.
+    pub fn subscribe_mutations(&self) -> MutationReceiver {
+        self.hooks.subscribe()
+    }
+
+    /// Returns the HTTP methods used for requests without an explicit method.
+    pub fn method_policy(&self) -> &MethodPolicy {
+        &self.method_policy
+    }
+
+    /// Persists the entries recorded by the request log of the client, if one is set.
+    pub async fn flush_request_log(&self) -> WWClientResult<()> {
+        match &self.request_log {
+            Some(log) => log.flush().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Reads the body of a response and applies the `TextNormalization` of the client.
+    ///
+    /// The returned future doesn't borrow the client, so it is `Send` for any state.
+    pub(crate) fn read_body(
+        &self,
+        response: Response,
+    ) -> impl std::future::Future<Output = WWClientResult<Bytes>> + Send + 'static {
+        let normalization = self.text_normalization;
+        async move {
+            let status = response.status().as_u16();
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let body = response.bytes().await?;
+            check_json_body(status, &content_type, &body)?;
+            Ok(normalization.apply_to_body(body))
+        }
+    }
+
+    /// Records a sent request in the request log of the client, if one is set.
+    fn log_request(&self, body: &ExecJsonRequest, response: &Result<Response, reqwest::Error>) {
+        if let Some(log) = &self.request_log {
+            log.record(RequestLogEntry {
+                request_id: body.pass_info.request_id.clone(),
+                function: body.function.function_name.clone(),
+                timestamp: body.pass_info.timestamp.clone(),
+                status: response.as_ref().ok().map(|r| r.status().as_u16()),
+            });
+        }
+    }
+
+    /// Sets the mode in which function calls are executed, sent in the headers and the request body
+    /// as the `Compat` profile requires.
+    ///
+    /// A `WWSVC-EXECUTE-MODE` header passed to a single request takes precedence for that request.
+    pub fn set_execute_mode(&mut self, execute_mode: impl Into<ExecuteMode>) {
+        self.execute_mode = execute_mode.into();
+        self.static_headers = static_headers(self.compat, &self.execute_mode);
+    }
+
+    /// Returns the mode in which function calls are executed.
+    pub fn execute_mode(&self) -> &ExecuteMode {
+        &self.execute_mode
+    }
+
+    /// Returns the compression of EXECJSON request bodies, which is `RequestCompression::None` once
+    /// the server has rejected a compressed body.
+    pub fn request_compression(&self) -> RequestCompression {
+        self.compressor.compression()
+    }
+
+    /// Returns the most recent COMRESULTs received by this client and its clones, the oldest first.
+    ///
+    /// The number of kept results is set with `recent_results()` on the builder.
+    pub fn recent_results(&self) -> Vec<RecentResult> {
+        self.recent_results.snapshot()
+    }
+
+    /// Records a COMRESULT of the current request for `recent_results()`.
+    fn record_result(&self, function: &str, com_result: &ComResult) {
+        self.recent_results.record(RecentResult {
+            request_id: self.request_id.clone(),
+            function: function.to_string(),
+            com_result: com_result.clone(),
+        });
+    }
+
+    /// Replaces the credentials of this client and all of its clones, e.g. after the service pass has been
+    /// rotated.
+    ///
+    /// Requests which have already been signed finish with the old credentials, clones take over the new
+    /// ones before signing their next request.
+    pub fn replace_credentials(&mut self, credentials: Credentials) {
+        self.rotation.publish(credentials.clone());
+        self.credentials = Some(credentials);
+    }
+
+    /// Takes over credentials which have been replaced by a clone of this client.
+    fn sync_credentials(&mut self) {
+        if let Some(credentials) = self.rotation.take_newer() {
+            self.credentials = Some(credentials);
+        }
+    }
+
+    /// Returns the URL of the WWSVC, or the error if the configured WEBWARE URL is invalid.
+    fn base_url(&self) -> WWClientResult<&Url> {
+        self.webware_url.as_ref().map_err(|e| (*e).into())
+    }
+
+    fn http_client(&self) -> WWClientResult<&reqwest::Client> {
+        self.client
+            .as_ref()
+            .map_err(|e| WWSVCError::HttpClientError(e.clone()))
+    }
+
+    /// Returns the URL EXECJSON requests are sent to, computed from the WEBWARE URL.
+    pub fn execjson_url(&self) -> WWClientResult<Url> {
+        Ok(self.base_url()?.join("EXECJSON")?)
+    }
+
+    /// Marks the cursor of a dropped `CursoredResponse` for closing with the next request.
+    pub(crate) fn abandon_cursor(&mut self, abandoned: AbandonedCursor) {
+        logging::debug_event!(
+            function = %abandoned.function,
+            cursor_id = %abandoned.cursor_id,
+            "cursor dropped before its last page, closing it with the next request"
+        );
+        self.abandoned_cursors.push(abandoned);
     }
 }
 
 impl<State: Ready> WebwareClient<State> {
     /// Creates a new pagination cursor and makes it available for the next requests (until it is closed)
     pub fn create_cursor(self, max_lines: u32) -> WebwareClient<OpenCursor> {
-        let cursor = Cursor::new(max_lines);
+        self.with_cursor(Cursor::new(max_lines))
+    }
+
+    /// Restores a cursor saved with `Cursor::to_state()` and makes it available for the next requests,
+    /// e.g. to continue reading pages with `request()` after a restart.
+    ///
+    /// `cursored_request()` starts a cursor of its own, use `CursoredResponse::with_cursor()` to
+    /// continue a saved cursor with it. The server only continues the cursor if it is still open,
+    /// which usually requires the same service pass.
+    pub fn restore_cursor(self, state: CursorSnapshot) -> WebwareClient<OpenCursor> {
+        self.with_cursor(Cursor::from_state(state))
+    }
+
+    fn with_cursor(self, cursor: Cursor) -> WebwareClient<OpenCursor> {
+        self.warn_unused_cursor();
         WebwareClient {
             webware_url: self.webware_url,
             vendor_hash: self.vendor_hash,
@@ -263,18 +873,49 @@ impl<State: Ready> WebwareClient<State> {
             secret: self.secret,
             revision: self.revision,
             credentials: self.credentials,
+            credentials_provider: self.credentials_provider,
+            rotation: self.rotation,
             result_max_lines: self.result_max_lines,
+            function_max_lines: self.function_max_lines,
+            function_versions: self.function_versions,
             cursor: Some(cursor),
             current_request: self.current_request,
+            request_id: self.request_id,
+            request_ids: self.request_ids,
+            request_log: self.request_log,
+            recent_results: self.recent_results,
+            execute_mode: self.execute_mode,
+            endpoint_override: self.endpoint_override,
+            timeout_override: self.timeout_override,
             client: self.client,
+            static_headers: self.static_headers,
             suspend_cursor: self.suspend_cursor,
+            abandoned_cursors: self.abandoned_cursors,
+            hooks: self.hooks,
+            compat: self.compat,
+            body_order: self.body_order,
+            method_policy: self.method_policy,
+            read_only: self.read_only,
+            function_policy: self.function_policy,
+            body_size_limit: self.body_size_limit,
+            compressor: self.compressor,
+            text_normalization: self.text_normalization,
             state: std::marker::PhantomData::<OpenCursor>,
         }
     }
 
+    /// Warns if the current cursor is dropped without a request having been sent with it.
+    fn warn_unused_cursor(&self) {
+        if self.cursor.as_ref().is_some_and(Cursor::unused) {
+            lints::warn(Some(Lint::UnusedCursor));
+        }
+    }
+
     /// Generates a set of credentials from the current client.
-    pub fn credentials(&self) -> &Credentials {
-        self.credentials.as_ref().unwrap()
+    pub fn credentials(&self) -> WWClientResult<&Credentials> {
+        self.credentials
+            .as_ref()
+            .ok_or(WWSVCError::MissingCredentials)
     }
 
     /// Sets the maximum amount of results that are returned in a response
@@ -282,80 +923,163 @@ impl<State: Ready> WebwareClient<State> {
         self.result_max_lines = max_lines;
     }
 
+    /// Sets the maximum amount of results that are returned in a response to the given function.
+    ///
+    /// Takes precedence over the value set with `set_result_max_lines()`, unless a cursor is used or the
+    /// `WWSVC-ACCEPT-RESULT-MAX-LINES` header is passed explicitly.
+    pub fn set_function_max_lines(&mut self, function: &str, max_lines: u32) {
+        self.function_max_lines
+            .insert(function.to_uppercase(), max_lines);
+    }
+
+    /// Sets the revision of the given function, which is used by types deriving the data traits with
+    /// `#[wwsvc(version = "auto")]`.
+    pub fn set_function_version(&mut self, function: &str, version: u32) {
+        self.function_versions
+            .insert(function.to_uppercase(), version);
+    }
+
+    /// Returns the revision set for the given function with `set_function_version()` or on the builder.
+    pub fn function_version(&self, function: &str) -> Option<u32> {
+        self.function_versions
+            .get(&function.to_uppercase())
+            .copied()
+    }
+
+    /// Sends the next EXECJSON request to `url` instead of `execjson_url()`, e.g. to route it through a
+    /// debugging proxy or to a canary instance.
+    ///
+    /// The override applies to exactly one request. The request is signed with the credentials of this
+    /// client, so the target has to accept them.
+    pub fn override_next_endpoint(&mut self, url: Url) {
+        self.endpoint_override = Some(url);
+    }
+
+    /// Sends the next EXECJSON request with the given timeout instead of the timeout of the client, e.g. for
+    /// functions which are known to take long.
+    ///
+    /// The override applies to exactly one request, including its repetition after refreshed credentials.
+    pub fn override_next_timeout(&mut self, timeout: std::time::Duration) {
+        self.timeout_override = Some(timeout);
+    }
+
     /// Returns a set of headers, that are required on all requests to the WEBSERVICES (except `REGISTER`).
     ///
-    /// This will automatically append necessary authentication headers and increase the request ID, if `register()` was successful.
+    /// This will automatically append necessary authentication headers and allocate a new request ID (see
+    /// `allocate_request_id()`), if `register()` was successful. Use `headers_for()` to build headers without
+    /// allocating a request ID.
     pub fn get_default_headers(
         &mut self,
         additional_headers: Option<HashMap<&str, &str>>,
     ) -> WWClientResult<HeaderMap> {
-        let mut max_lines = self.result_max_lines;
+        self.sync_credentials();
+        let request_id = if self.credentials.is_some() {
+            self.allocate_request_id()?
+        } else {
+            self.request_id.clone()
+        };
+        self.headers_for(request_id, additional_headers)
+    }
+
+    /// Allocates the next request ID and returns it.
+    ///
+    /// Every request sent by the client consumes exactly one request ID, which is allocated right before the
+    /// request is signed. With the default `SequentialIds`, request IDs increase by one with each request, so the
+    /// server never sees a gap or a duplicate, as long as IDs are only allocated for requests which are actually sent.
+    /// If the generator fails, no request ID is consumed.
+    pub fn allocate_request_id(&mut self) -> WWClientResult<RequestId> {
+        self.request_id = self.request_ids.generate(self.current_request + 1)?;
+        self.current_request += 1;
+        Ok(self.request_id.clone())
+    }
+
+    /// Returns the number of the most recently allocated request.
+    pub fn current_request_id(&self) -> u32 {
+        self.current_request
+    }
 
-        let mut header_vec = vec![
-            ("WWSVC-EXECUTE-MODE", "SYNCHRON".to_string()),
-            ("WWSVC-ACCEPT-RESULT-TYPE", "JSON".to_string()),
-        ];
+    /// Returns the headers for the request with the given ID, without changing the state of the client.
+    ///
+    /// Authentication headers are only included, if `register()` was successful. With the `opentelemetry`
+    /// feature enabled, the W3C `traceparent` and `tracestate` headers of the current span are appended as well.
+    pub fn headers_for(
+        &self,
+        request_id: impl Into<RequestId>,
+        additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<HeaderMap> {
+        let request_id = request_id.into();
+        let mut max_lines = self.result_max_lines;
+        let mut headers = self.static_headers.clone();
 
         if let Some(credentials) = &self.credentials {
-            let app_hash = AppHash::new(self.current_request, &credentials.app_id);
-            self.current_request = app_hash.request_id;
-            header_vec.append(&mut vec![
-                ("WWSVC-REQID", format!("{}", self.current_request)),
-                ("WWSVC-TS", app_hash.date_formatted.to_string()),
-                ("WWSVC-HASH", format!("{:x}", app_hash)),
-            ]);
+            let sequence = match request_id {
+                RequestId::Number(id) => id,
+                RequestId::Text(_) => self.current_request,
+            };
+            let app_hash = AppHash::for_request(sequence, &credentials.app_id);
+            headers.insert(
+                "WWSVC-REQID",
+                HeaderValue::from_str(&request_id.to_string())?,
+            );
+            headers.insert("WWSVC-TS", HeaderValue::from_str(&app_hash.date_formatted)?);
+            headers.insert("WWSVC-HASH", HeaderValue::from_str(&app_hash.hash)?);
 
             if !self.suspend_cursor {
                 if let Some(cursor) = &self.cursor {
                     if !cursor.closed() {
-                        header_vec
-                            .append(&mut vec![("WWSVC-CURSOR", cursor.cursor_id.to_string())]);
+                        headers.insert("WWSVC-CURSOR", HeaderValue::from_str(&cursor.cursor_id)?);
                         max_lines = cursor.max_lines;
                     }
                 }
             }
         }
 
-        header_vec.push(("WWSVC-ACCEPT-RESULT-MAX-LINES", max_lines.to_string()));
-
-        let mut headers: HashMap<String, String> = header_vec
-            .iter()
-            .map(|(s1, s2)| (s1.to_string(), s2.to_string()))
-            .collect();
+        headers.insert(
+            "WWSVC-ACCEPT-RESULT-MAX-LINES",
+            HeaderValue::from(max_lines),
+        );
 
         if let Some(additional_headers) = additional_headers {
-            headers.extend(
-                additional_headers
-                    .iter()
-                    .map(|(s1, s2)| (s1.to_string(), s2.to_string())),
-            );
+            for (name, value) in additional_headers {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| WWSVCError::InvalidHeader)?;
+                headers.insert(name, HeaderValue::from_str(value)?);
+            }
         }
 
-        (&headers).try_into().map_err(|_| WWSVCError::InvalidHeader)
+        #[cfg(feature = "opentelemetry")]
+        crate::trace_context::inject(&mut headers);
+
+        Ok(headers)
     }
 
     /// Returns the same set of headers, that `get_default_headers()` returns, except the result type header is set to `BIN` instead.
     pub fn get_bin_headers(
         &mut self,
         additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<HeaderMap> {
+        self.get_result_type_headers(ResultType::Bin, additional_headers)
+    }
+
+    /// Returns the same set of headers, that `get_default_headers()` returns, except the result type header is set to `result_type` instead.
+    pub fn get_result_type_headers(
+        &mut self,
+        result_type: ResultType,
+        additional_headers: Option<HashMap<&str, &str>>,
     ) -> WWClientResult<HeaderMap> {
         let mut headers = self.get_default_headers(additional_headers)?;
-        headers.remove("WWSVC-ACCEPT-RESULT-TYPE");
-        headers.append("WWSVC-ACCEPT-RESULT-TYPE", HeaderValue::from_str("BIN")?);
+        headers.insert(ACCEPT_RESULT_TYPE, result_type.header_value()?);
         Ok(headers)
     }
 
     /// Sends a `DEREGISTER` request to the WEBWARE instance, in order to invalidate the service pass.
+    ///
+    /// The service pass is released even if the request fails, since the server invalidates it
+    /// after a while anyway.
     pub async fn deregister(mut self) -> WWClientResult<WebwareClient<Unregistered>> {
-        if let Some(credentials) = &self.credentials {
-            let target_url = self
-                .webware_url
-                .join("WWSERVICE/")?
-                .join("DEREGISTER/")?
-                .join(&format!("{}/", &credentials.service_pass))?;
-            let headers = self.get_default_headers(None)?;
-            let _ = self.client.get(target_url).headers(headers).send().await;
-        }
+        self.warn_unused_cursor();
+        // failing to reach the server doesn't prevent deregistering locally
+        let _ = self.send_deregister().await?;
 
         Ok(WebwareClient {
             webware_url: self.webware_url,
@@ -364,22 +1088,78 @@ impl<State: Ready> WebwareClient<State> {
             secret: self.secret,
             revision: self.revision,
             credentials: None,
+            credentials_provider: self.credentials_provider,
+            rotation: self.rotation,
             result_max_lines: self.result_max_lines,
+            function_max_lines: self.function_max_lines,
+            function_versions: self.function_versions,
             cursor: self.cursor,
             current_request: self.current_request,
+            request_id: self.request_id,
+            request_ids: self.request_ids,
+            request_log: self.request_log,
+            recent_results: self.recent_results,
+            execute_mode: self.execute_mode,
+            endpoint_override: self.endpoint_override,
+            timeout_override: self.timeout_override,
             client: self.client,
+            static_headers: self.static_headers,
             suspend_cursor: self.suspend_cursor,
+            abandoned_cursors: Vec::new(),
+            hooks: self.hooks,
+            compat: self.compat,
+            body_order: self.body_order,
+            method_policy: self.method_policy,
+            read_only: self.read_only,
+            function_policy: self.function_policy,
+            body_size_limit: self.body_size_limit,
+            compressor: self.compressor,
+            text_normalization: self.text_normalization,
             state: std::marker::PhantomData::<Unregistered>,
         })
     }
 
+    /// Sends the `DEREGISTER` request and releases the service pass in the registry.
+    ///
+    /// Errors building the request are returned as the outer error, errors sending it as the
+    /// inner error, so callers can decide whether an unreachable server is a failure. The service
+    /// pass is released in any case, also if the returned future is dropped, e.g. on a timeout.
+    pub(crate) async fn send_deregister(&mut self) -> WWClientResult<Result<(), reqwest::Error>> {
+        self.sync_credentials();
+        let Some(service_pass) = self.credentials.as_ref().map(|c| c.service_pass.clone()) else {
+            return Ok(Ok(()));
+        };
+        let _release = Release::new(&self.vendor_hash, &self.app_hash, &service_pass);
+        let target_url = self
+            .base_url()?
+            .join("WWSERVICE/")?
+            .join("DEREGISTER/")?
+            .join(&format!("{}/", &service_pass))?;
+        let headers = self.get_default_headers(None)?;
+        let result = self
+            .http_client()?
+            .get(target_url)
+            .headers(headers)
+            .send()
+            .await;
+        logging::debug_event!(
+            vendor_hash = %self.vendor_hash,
+            app_hash = %self.app_hash,
+            failed = result.is_err(),
+            "deregistered"
+        );
+        Ok(result.map(|_| ()))
+    }
+
     /// Performs a request to the WEBSERVICES and returns a JSON value.
+    ///
+    /// If `method` is `None`, the method is chosen by the `MethodPolicy` of the client.
     pub async fn request(
         &mut self,
-        method: reqwest::Method,
+        method: impl Into<Option<reqwest::Method>>,
         function: &str,
         version: u32,
-        parameters: HashMap<&str, &str>,
+        parameters: Parameters,
         additional_headers: Option<HashMap<&str, &str>>,
     ) -> WWClientResult<serde_json::Value> {
         self.request_generic::<serde_json::Value>(
@@ -393,100 +1173,889 @@ impl<State: Ready> WebwareClient<State> {
     }
 
     /// Performs a request to the WEBSERVICES and returns a response object.
+    ///
+    /// If `method` is `None`, the method is chosen by the `MethodPolicy` of the client. If the server
+    /// responds with `401 Unauthorized` and the `CredentialsProvider` provides different credentials, the
+    /// request is sent once more with these.
     pub async fn request_as_response(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+        additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<Response> {
+        self.close_abandoned_cursors().await;
+        let method = method
+            .into()
+            .unwrap_or_else(|| self.method_policy.method_for(function));
+        let timeout = self.timeout_override.take();
+        let (mut response, compressed) = self
+            .send_execjson(
+                method.clone(),
+                function,
+                version,
+                &parameters,
+                additional_headers.clone(),
+                timeout,
+            )
+            .await?;
+        if compressed && response.status() == reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE {
+            logging::warn_event!(
+                function,
+                "compressed request body has been rejected, sending uncompressed bodies from now on"
+            );
+            self.compressor.reject();
+            (response, _) = self
+                .send_execjson(
+                    method.clone(),
+                    function,
+                    version,
+                    &parameters,
+                    additional_headers.clone(),
+                    timeout,
+                )
+                .await?;
+        }
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && self.refresh_credentials().await?
+        {
+            (response, _) = self
+                .send_execjson(
+                    method,
+                    function,
+                    version,
+                    &parameters,
+                    additional_headers,
+                    timeout,
+                )
+                .await?;
+        }
+
+        self.update_cursor(&response)?;
+
+        Ok(response)
+    }
+
+    /// Sends an EXECJSON request and returns the response and whether the body has been compressed.
+    async fn send_execjson(
         &mut self,
         method: reqwest::Method,
         function: &str,
         version: u32,
-        parameters: HashMap<&str, &str>,
+        parameters: &Parameters,
         additional_headers: Option<HashMap<&str, &str>>,
+        timeout: Option<std::time::Duration>,
+    ) -> WWClientResult<(Response, bool)> {
+        let (target_url, headers, body) =
+            self.prepare_request(function, version, parameters, additional_headers)?;
+        let bytes = self.serialize_body(&body)?;
+        logging::debug_event!(
+            function,
+            version,
+            method = %method,
+            request_id = %self.request_id,
+            size = bytes.len(),
+            "sending request"
+        );
+        let (response, is_compressed) = self
+            .send_body(method, target_url, headers, &body, bytes, timeout)
+            .await?;
+        logging::debug_event!(
+            function,
+            request_id = %self.request_id,
+            status = response.status().as_u16(),
+            "received response"
+        );
+        Ok((response, is_compressed))
+    }
+
+    /// Sends a serialized EXECJSON body, compressed according to the `RequestCompression` of the
+    /// client, see `dispatch()`. `body` is the request the body has been serialized from. Returns
+    /// the response and whether the body has been compressed.
+    async fn send_body(
+        &mut self,
+        method: reqwest::Method,
+        target_url: Url,
+        headers: HeaderMap,
+        body: &ExecJsonRequest,
+        bytes: Vec<u8>,
+        timeout: Option<std::time::Duration>,
+    ) -> WWClientResult<(Response, bool)> {
+        let compressed = self.compressor.compress(&bytes)?;
+        if let Some(compressed) = &compressed {
+            logging::debug_event!(
+                request_id = %self.request_id,
+                size = bytes.len(),
+                compressed_size = compressed.len(),
+                "compressed request body"
+            );
+        }
+        let is_compressed = compressed.is_some();
+        let request = self
+            .http_client()?
+            .request(method, target_url)
+            .headers(headers)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        let request = match compressed {
+            Some(compressed) => request
+                .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                .body(compressed),
+            None => request.body(bytes),
+        };
+        let response = self.dispatch(request, body, timeout).await?;
+        Ok((response, is_compressed))
+    }
+
+    /// Sends a request carrying the EXECJSON request `body`, applying the timeout and the injected
+    /// faults, and records it in the request log.
+    async fn dispatch(
+        &mut self,
+        mut request: reqwest::RequestBuilder,
+        body: &ExecJsonRequest,
+        timeout: Option<std::time::Duration>,
     ) -> WWClientResult<Response> {
-        if self.credentials.is_none() {
-            return Err(WWSVCError::NotAuthenticated);
-        }
-
-        let target_url = self.webware_url.join("EXECJSON")?;
-        let headers = self.get_default_headers(additional_headers)?;
-        let mut param_vec: Vec<HashMap<String, String>> = Vec::new();
-        let app_hash_header = headers.get("WWSVC-HASH");
-        let timestamp_header = headers.get("WWSVC-TS");
-        let app_hash: String = app_hash_header
-            .unwrap_or(&HeaderValue::from_str("").unwrap())
-            .to_str()
-            .map_err(|_| WWSVCError::HeaderValueToStrError)?
-            .to_string();
-        let timestamp: String = timestamp_header
-            .unwrap_or(&HeaderValue::from_str("").unwrap())
-            .to_str()
-            .map_err(|_| WWSVCError::HeaderValueToStrError)?
-            .to_string();
-
-        for (p_key, p_value) in parameters {
-            let mut map: HashMap<String, String> = HashMap::new();
-            map.insert("PNAME".to_string(), p_key.to_string());
-            map.insert("PCONTENT".to_string(), p_value.to_string());
-            param_vec.push(map);
-        }
-        let body = json!({
-            "WWSVC_FUNCTION": {
-                "FUNCTIONNAME": function,
-                "PARAMETER": param_vec,
-                "REVISION": version
-            },
-            "WWSVC_PASSINFO": {
-                "SERVICEPASS": self.credentials.as_ref().unwrap().service_pass,
-                "APPHASH": app_hash,
-                "TIMESTAMP": timestamp,
-                "REQUESTID": self.current_request,
-                "EXECUTE_MODE": "SYNCHRON"
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        #[cfg(feature = "test-util")]
+        let faults = crate::test_util::before_request(timeout).await?;
+        let response = request.send().await;
+        #[cfg(feature = "test-util")]
+        let response = response.map(|response| crate::test_util::after_response(faults, response));
+        self.log_request(body, &response);
+        Ok(response?)
+    }
+
+    /// Fetches the credentials from the `CredentialsProvider` again, after the server has rejected the
+    /// current ones. Returns whether different credentials have been provided.
+    async fn refresh_credentials(&mut self) -> WWClientResult<bool> {
+        let Some(provider) = &self.credentials_provider else {
+            return Ok(false);
+        };
+        match provider.credentials().await? {
+            Some(credentials) if self.credentials.as_ref() != Some(&credentials) => {
+                logging::info_event!(
+                    "credentials have been rejected, retrying with the provided ones"
+                );
+                self.replace_credentials(credentials);
+                Ok(true)
             }
-        });
+            _ => Ok(false),
+        }
+    }
+
+    /// Performs a `GET` request to a REST-style endpoint of the WEBSERVICES and deserializes the response
+    /// to the type `T`.
+    ///
+    /// `path` is relative to the WEBSERVICES root (e.g. `REST/ARTIKEL/`). The request is signed like an
+    /// EXECJSON request, the service pass is sent in the `WWSVC-SERVICEPASS` header.
+    pub async fn rest_get<T>(&mut self, path: &str, query: &[(&str, &str)]) -> WWClientResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.sync_credentials();
+        let service_pass = match &self.credentials {
+            Some(credentials) => credentials.service_pass.clone(),
+            None => return Err(WWSVCError::NotAuthenticated),
+        };
+
+        let target_url = self.base_url()?.join(path.trim_start_matches('/'))?;
+        let mut headers = self.get_default_headers(None)?;
+        headers.insert("WWSVC-SERVICEPASS", HeaderValue::from_str(&service_pass)?);
         let response = self
-            .client
-            .request(method, target_url)
+            .http_client()?
+            .get(target_url)
             .headers(headers)
-            .json(&body)
+            .query(query)
             .send()
             .await?;
 
+        self.update_cursor(&response)?;
+
+        let body = self.read_body(response).await?;
+        Ok(serde_json::from_slice::<T>(&body)?)
+    }
+
+    /// Uploads the content of `reader` together with a function call as a `multipart/form-data`
+    /// request, without loading the whole content into memory.
+    ///
+    /// The function call is sent as the `WWSVC_REQUEST` part, the content as the `FILE` part
+    /// named `file_name`. The method is chosen by the `MethodPolicy` of the client. As the content
+    /// can only be read once, the request isn't repeated with refreshed credentials.
+    pub async fn upload_stream<R>(
+        &mut self,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+        file_name: &str,
+        reader: R,
+    ) -> WWClientResult<Response>
+    where
+        R: tokio::io::AsyncRead + Send + Sync + 'static,
+    {
+        self.close_abandoned_cursors().await;
+        let method = self.method_policy.method_for(function);
+        let timeout = self.timeout_override.take();
+        let (target_url, headers, body) =
+            self.prepare_request(function, version, &parameters, None)?;
+        let file_part = reqwest::multipart::Part::stream(reqwest::Body::wrap_stream(
+            tokio_util::io::ReaderStream::new(reader),
+        ))
+        .file_name(file_name.to_string())
+        .mime_str("application/octet-stream")?;
+        let request_part = reqwest::multipart::Part::bytes(self.serialize_body(&body)?)
+            .mime_str("application/json")?;
+        let form = reqwest::multipart::Form::new()
+            .part("WWSVC_REQUEST", request_part)
+            .part("FILE", file_part);
+
+        logging::debug_event!(
+            function,
+            version,
+            method = %method,
+            request_id = %self.request_id,
+            "sending upload"
+        );
+        let request = self
+            .http_client()?
+            .request(method, target_url)
+            .headers(headers)
+            .multipart(form);
+        let response = self.dispatch(request, &body, timeout).await?;
+
+        self.update_cursor(&response)?;
+
+        Ok(response)
+    }
+
+    /// Serializes the EXECJSON body, checking its size against the `BodySizeLimit`.
+    fn serialize_body(&self, body: &ExecJsonRequest) -> WWClientResult<Vec<u8>> {
+        let bytes = body.to_vec(self.body_order)?;
+        self.body_size_limit.check(body, bytes.len())?;
+        Ok(bytes)
+    }
+
+    /// Checks whether the function may be called by this client.
+    fn check_function(&self, function: &str) -> WWClientResult<()> {
+        if self.read_only && !is_read_only_function(function) {
+            return Err(WWSVCError::ReadOnly(function.to_string()));
+        }
+        if !self.function_policy.permits(function) {
+            return Err(WWSVCError::FunctionNotPermitted(function.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Builds the target URL, headers and EXECJSON body for a function call.
+    pub(crate) fn prepare_request(
+        &mut self,
+        function: &str,
+        version: u32,
+        parameters: &Parameters,
+        additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<(Url, HeaderMap, ExecJsonRequest)> {
+        self.check_function(function)?;
+
+        self.sync_credentials();
+        let service_pass = match &self.credentials {
+            Some(credentials) => credentials.service_pass.clone(),
+            None => return Err(WWSVCError::NotAuthenticated),
+        };
+
+        crate::revisions::warn_unsupported_parameters(function, version, parameters);
+
+        let target_url = match self.endpoint_override.take() {
+            Some(url) => url,
+            None => self.execjson_url()?,
+        };
+        let explicit_max_lines = additional_headers.as_ref().is_some_and(|headers| {
+            headers
+                .keys()
+                .any(|key| key.eq_ignore_ascii_case("WWSVC-ACCEPT-RESULT-MAX-LINES"))
+        });
+        // an execute mode passed in the headers applies to this request only
+        let execute_mode = additional_headers
+            .as_ref()
+            .and_then(|headers| {
+                headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case(EXECUTE_MODE))
+                    .map(|(_, mode)| ExecuteMode::from(*mode))
+            })
+            .unwrap_or_else(|| self.execute_mode.clone());
+        let cursor_active =
+            !self.suspend_cursor && self.cursor.as_ref().is_some_and(|cursor| !cursor.closed());
+        let mut headers = self.get_default_headers(additional_headers)?;
+        if !explicit_max_lines && !cursor_active {
+            let max_lines = match self.function_max_lines.get(&function.to_uppercase()) {
+                Some(max_lines) => {
+                    headers.insert(
+                        "WWSVC-ACCEPT-RESULT-MAX-LINES",
+                        HeaderValue::from(*max_lines),
+                    );
+                    *max_lines
+                }
+                None => self.result_max_lines,
+            };
+            lints::warn(Lint::unpaginated_get(function, parameters, max_lines));
+        }
+        let header_str = |name: &str| -> WWClientResult<String> {
+            match headers.get(name) {
+                Some(value) => Ok(value
+                    .to_str()
+                    .map_err(|_| WWSVCError::HeaderValueToStrError)?
+                    .to_string()),
+                None => Ok(String::new()),
+            }
+        };
+        let app_hash = header_str("WWSVC-HASH")?;
+        let timestamp = header_str("WWSVC-TS")?;
+        if self.compat.execute_mode_in_headers() {
+            headers.insert(EXECUTE_MODE, execute_mode.header_value()?);
+        } else {
+            headers.remove(EXECUTE_MODE);
+        }
+
+        let body = ExecJsonRequest {
+            function: ServiceFunction {
+                function_name: function.to_string(),
+                parameters: parameters.into(),
+                revision: version,
+            },
+            pass_info: ServicePassInfo {
+                service_pass,
+                app_hash,
+                timestamp,
+                request_id: self.request_id.clone(),
+                execute_mode: self.compat.execute_mode_in_body().then_some(execute_mode),
+            },
+        };
+
+        Ok((target_url, headers, body))
+    }
+
+    /// Takes over the cursor ID and state returned by the server, if a cursor is in use.
+    fn update_cursor(&mut self, response: &Response) -> WWClientResult<()> {
         if !self.suspend_cursor {
             if let Some(cursor) = &mut self.cursor {
-                if !cursor.closed() && response.headers().contains_key("WWSVC-CURSOR") {
-                    cursor.set_cursor_id(
-                        response
-                            .headers()
-                            .get("WWSVC-CURSOR")
-                            .unwrap()
-                            .to_str()
-                            .unwrap()
-                            .to_string(),
-                    );
+                if !cursor.closed() {
+                    cursor.update_from_headers(response.headers())?;
                 }
             }
         }
+        Ok(())
+    }
 
+    /// Performs a request with the given cursor instead of the cursor of the client and takes over the
+    /// cursor headers of the response into it.
+    ///
+    /// The cursor ID and page size are passed in the headers of this request only, the cursor of the
+    /// client isn't used or changed.
+    pub(crate) async fn request_with_cursor(
+        &mut self,
+        cursor: &mut Cursor,
+        method: Option<reqwest::Method>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+    ) -> WWClientResult<Response> {
+        let max_lines = cursor.max_lines.to_string();
+        let headers = HashMap::from([
+            ("WWSVC-CURSOR", cursor.cursor_id.as_str()),
+            ("WWSVC-ACCEPT-RESULT-MAX-LINES", max_lines.as_str()),
+        ]);
+        let suspended = std::mem::replace(&mut self.suspend_cursor, true);
+        let result = self
+            .request_as_response(method, function, version, parameters, Some(headers))
+            .await;
+        self.suspend_cursor = suspended;
+        let response = result?;
+
+        cursor.update_from_headers(response.headers())?;
         Ok(response)
     }
 
+    /// Returns a new cursor with the page size of the cursor of the client, or of `result_max_lines`
+    /// if the client doesn't have one.
+    pub(crate) fn request_cursor(&self) -> Cursor {
+        Cursor::new(
+            self.cursor
+                .as_ref()
+                .map_or(self.result_max_lines, |cursor| cursor.max_lines),
+        )
+    }
+
+    /// Closes the cursors of `CursoredResponse`s which have been dropped before their last page, by
+    /// requesting their function once more with the cursor ID and the `WWSVC-CURSOR-STATE: CLOSED`
+    /// header.
+    ///
+    /// This happens before each request, so it only has to be called to close them earlier, e.g.
+    /// before the client is dropped. Failures are logged, since the server closes abandoned cursors
+    /// after a while anyway.
+    pub async fn close_abandoned_cursors(&mut self) {
+        for abandoned in std::mem::take(&mut self.abandoned_cursors) {
+            let method = abandoned
+                .method
+                .unwrap_or_else(|| self.method_policy.method_for(&abandoned.function));
+            let headers = HashMap::from([
+                ("WWSVC-CURSOR", abandoned.cursor_id.as_str()),
+                ("WWSVC-ACCEPT-RESULT-MAX-LINES", "1"),
+                CLOSE_HEADER,
+            ]);
+            let suspended = std::mem::replace(&mut self.suspend_cursor, true);
+            let result = self
+                .send_execjson(
+                    method,
+                    &abandoned.function,
+                    abandoned.version,
+                    &Parameters::new(),
+                    Some(headers),
+                    None,
+                )
+                .await;
+            self.suspend_cursor = suspended;
+            match result {
+                Ok((response, _)) if response.status().is_success() => {
+                    logging::debug_event!(
+                        function = %abandoned.function,
+                        cursor_id = %abandoned.cursor_id,
+                        "abandoned cursor closed"
+                    );
+                }
+                Ok((response, _)) => {
+                    logging::warn_event!(
+                        function = %abandoned.function,
+                        cursor_id = %abandoned.cursor_id,
+                        status = response.status().as_u16(),
+                        "closing abandoned cursor failed"
+                    );
+                }
+                Err(e) => {
+                    logging::warn_event!(
+                        function = %abandoned.function,
+                        cursor_id = %abandoned.cursor_id,
+                        error = %e,
+                        "closing abandoned cursor failed"
+                    );
+                }
+            }
+        }
+    }
+
     /// Performs a request to the WEBSERVICES and deserializes the response to the type `T`.
     ///
     /// **NOTE:** Due to the nature of the WEBSERVICES, deserialization might fail due to structural issues. In that case, use `request()` instead.
+    ///
+    /// If `method` is `None`, the method is chosen by the `MethodPolicy` of the client.
     pub async fn request_generic<T>(
         &mut self,
-        method: reqwest::Method,
+        method: impl Into<Option<reqwest::Method>>,
         function: &str,
         version: u32,
-        parameters: HashMap<&str, &str>,
+        parameters: Parameters,
         additional_headers: Option<HashMap<&str, &str>>,
     ) -> WWClientResult<T>
     where
         T: DeserializeOwned,
     {
+        let (response, _) = self
+            .request_generic_with_headers(method, function, version, parameters, additional_headers)
+            .await?;
+        Ok(response)
+    }
+
+    /// Performs a request like `request_generic()` and returns the response headers along with the
+    /// deserialized response, e.g. to read rate-limit, cursor or timing headers.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let (response, headers) = client
+    ///     .request_generic_with_headers::<serde_json::Value>(None, "ARTIKEL.GET", 1, parameters, None)
+    ///     .await?;
+    /// let remaining = headers.get("X-RateLimit-Remaining");
+    /// ```
+    pub async fn request_generic_with_headers<T>(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+        additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<(T, HeaderMap)>
+    where
+        T: DeserializeOwned,
+    {
+        let event_parameters = if is_mutation(function) && self.hooks.has_subscribers() {
+            Some(parameters.clone())
+        } else {
+            None
+        };
+
         let response = self
             .request_as_response(method, function, version, parameters, additional_headers)
             .await?;
-        let response_obj = response.json::<T>().await?;
-        Ok(response_obj)
+        let success = response.status().is_success();
+        let headers = response.headers().clone();
+        let body = self.read_body(response).await?;
+        let response_obj = serde_json::from_slice::<T>(&body)?;
+
+        #[derive(serde::Deserialize)]
+        struct ComResultEnvelope {
+            #[serde(rename = "COMRESULT")]
+            com_result: ComResult,
+        }
+
+        if let Ok(envelope) = serde_json::from_slice::<ComResultEnvelope>(&body) {
+            self.record_result(function, &envelope.com_result);
+            if let Some(parameters) = event_parameters.filter(|_| success) {
+                if envelope.com_result.is_success() {
+                    self.hooks.emit(MutationEvent {
+                        function: function.to_string(),
+                        version,
+                        parameters,
+                        com_result: envelope.com_result,
+                    });
+                }
+            }
+        }
+
+        Ok((response_obj, headers))
+    }
+
+    /// Executes a function call in `ASYNCHRON` mode and returns the ticket of the execution.
+    ///
+    /// The result is fetched with `fetch_async_result()`. If `method` is `None`, the method is chosen by
+    /// the `MethodPolicy` of the client.
+    pub async fn request_async(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+        additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<AsyncTicket> {
+        let mut headers = additional_headers.unwrap_or_default();
+        headers.insert(EXECUTE_MODE, ExecuteMode::Asynchron.as_str());
+        let response = self
+            .request_generic::<AsyncTicketResponse>(
+                method,
+                function,
+                version,
+                parameters,
+                Some(headers),
+            )
+            .await?;
+        Ok(response.ticket)
+    }
+
+    /// Fetches the state of a ticket returned by `request_async()` and, once the execution is done, the
+    /// response of the function call deserialized to `T`.
+    ///
+    /// The ticket is final once `AsyncResult::is_terminal()` returns true, until then it has to be
+    /// fetched again.
+    pub async fn fetch_async_result<T>(
+        &mut self,
+        ticket: &AsyncTicket,
+    ) -> WWClientResult<AsyncResult<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.request_generic(
+            None,
+            TICKET_FUNCTION,
+            1,
+            Parameters::new().param("TICKET", &ticket.id),
+            None,
+        )
+        .await
+    }
+
+    /// Updates the record identified by `key_parameters` with the members of the JSON object `patch`,
+    /// for integrations whose fields aren't known at compile time, and returns the COMRESULT.
+    ///
+    /// `function` is the `.PUT` function, e.g. `ARTIKEL.PUT`; `.PUT` is appended to a function group
+    /// like `ARTIKEL`. String members are sent as they are, `null` members are sent empty to clear the
+    /// field and other members are sent as JSON. The key parameters take precedence over the patch.
+    /// Returns `WWSVCError::InvalidPatch` without sending a request if `patch` isn't an object, and
+    /// `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// let com_result = client
+    ///     .put_patch(
+    ///         "ARTIKEL",
+    ///         collection! { "ARTNR" => "A1" },
+    ///         serde_json::json!({"ART_1_10": "Schraube", "ART_1_80": 12, "ART_2_10": null}),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn put_patch(
+        &mut self,
+        function: &str,
+        key_parameters: Parameters,
+        patch: serde_json::Value,
+    ) -> WWClientResult<ComResult> {
+        #[derive(serde::Deserialize)]
+        struct PutResponse {
+            #[serde(rename = "COMRESULT")]
+            com_result: ComResult,
+        }
+
+        let members = match patch {
+            serde_json::Value::Object(members) => members,
+            serde_json::Value::Null => return Err(WWSVCError::InvalidPatch("null".to_string())),
+            serde_json::Value::Bool(_) => {
+                return Err(WWSVCError::InvalidPatch("a boolean".to_string()))
+            }
+            serde_json::Value::Number(_) => {
+                return Err(WWSVCError::InvalidPatch("a number".to_string()))
+            }
+            serde_json::Value::String(_) => {
+                return Err(WWSVCError::InvalidPatch("a string".to_string()))
+            }
+            serde_json::Value::Array(_) => {
+                return Err(WWSVCError::InvalidPatch("an array".to_string()))
+            }
+        };
+        let function = match function.contains('.') {
+            true => function.to_string(),
+            false => format!("{}.PUT", function),
+        };
+        let mut parameters = Parameters::new();
+        for (name, value) in members {
+            match value {
+                serde_json::Value::Null => parameters.insert(&name, ""),
+                serde_json::Value::String(content) => parameters.insert(&name, content),
+                other => parameters.insert(&name, other),
+            }
+        }
+        parameters.merge(&key_parameters, MergePrecedence::Overwrite);
+
+        let version = self.function_version(&function).unwrap_or(1);
+        let response: PutResponse = self
+            .request_generic(None, &function, version, parameters, None)
+            .await?;
+        if !response.com_result.is_success() {
+            return Err(WWSVCError::FunctionFailed {
+                function,
+                com_result: Box::new(response.com_result),
+            });
+        }
+        Ok(response.com_result)
+    }
+
+    /// Executes the function calls of `batch` in one EXECJSON request and one transaction and returns
+    /// the COMRESULT of every call.
+    ///
+    /// Each function is checked against the read-only mode and the `FunctionPolicy` before anything is
+    /// sent. The method is chosen by the `MethodPolicy`, for mutations if any function mutates. An
+    /// empty batch isn't sent. Batches are sent like single function calls: the body is compressed
+    /// according to the `RequestCompression` of the client, and if the server responds with
+    /// `401 Unauthorized` and the `CredentialsProvider` provides different credentials, the batch
+    /// is sent once more with these.
+    pub async fn send_batch(&mut self, batch: &BatchRequest) -> WWClientResult<BatchResponse> {
+        if batch.is_empty() {
+            return Ok(BatchResponse::default());
+        }
+        for function in batch.functions() {
+            self.check_function(&function.function_name)?;
+        }
+
+        self.close_abandoned_cursors().await;
+        let timeout = self.timeout_override.take();
+        let (mut response, compressed) = self.send_batch_once(batch, timeout).await?;
+        if compressed && response.status() == reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE {
+            logging::warn_event!(
+                functions = batch.len(),
+                "compressed batch body has been rejected, sending uncompressed bodies from now on"
+            );
+            self.compressor.reject();
+            (response, _) = self.send_batch_once(batch, timeout).await?;
+        }
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            && self.refresh_credentials().await?
+        {
+            (response, _) = self.send_batch_once(batch, timeout).await?;
+        }
+        self.update_cursor(&response)?;
+
+        let body = self.read_body(response).await?;
+        let response: BatchResponse = serde_json::from_slice(&body)?;
+        for result in &response.results {
+            self.record_result(&result.function, &result.com_result);
+        }
+        // the batch is one transaction, so nothing has changed if the batch as a whole failed
+        if response.com_result.is_success() && self.hooks.has_subscribers() {
+            for (function, result) in batch.functions().iter().zip(&response.results) {
+                if is_mutation(&function.function_name) && result.com_result.is_success() {
+                    self.hooks.emit(MutationEvent {
+                        function: function.function_name.clone(),
+                        version: function.revision,
+                        parameters: function.parameters.iter().fold(
+                            Parameters::new(),
+                            |parameters, parameter| {
+                                parameters.param(&parameter.name, &parameter.content)
+                            },
+                        ),
+                        com_result: result.com_result.clone(),
+                    });
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    /// Sends the batch request and returns the response and whether the body has been compressed.
+    async fn send_batch_once(
+        &mut self,
+        batch: &BatchRequest,
+        timeout: Option<std::time::Duration>,
+    ) -> WWClientResult<(Response, bool)> {
+        let to_parameters = |function: &ServiceFunction| {
+            function
+                .parameters
+                .iter()
+                .fold(Parameters::new(), |parameters, parameter| {
+                    parameters.param(&parameter.name, &parameter.content)
+                })
+        };
+        let (first, rest) = (&batch.functions()[0], &batch.functions()[1..]);
+        let (target_url, headers, body) = self.prepare_request(
+            &first.function_name,
+            first.revision,
+            &to_parameters(first),
+            None,
+        )?;
+        // prepare_request() has checked the first function, the others share its headers
+        let max_lines = headers
+            .get("WWSVC-ACCEPT-RESULT-MAX-LINES")
+            .and_then(|value| value.to_str().ok()?.parse().ok())
+            .unwrap_or(self.result_max_lines);
+        for function in rest {
+            let parameters = to_parameters(function);
+            crate::revisions::warn_unsupported_parameters(
+                &function.function_name,
+                function.revision,
+                &parameters,
+            );
+            if !headers.contains_key("WWSVC-CURSOR") {
+                lints::warn(Lint::unpaginated_get(
+                    &function.function_name,
+                    &parameters,
+                    max_lines,
+                ));
+            }
+        }
+
+        let batch_body =
+            ExecJsonBatchRequest::new(batch.functions().to_vec(), body.pass_info.clone());
+        let bytes = batch_body.to_vec(self.body_order)?;
+        self.body_size_limit.check_batch(&batch_body, bytes.len())?;
+
+        let method = if batch
+            .functions()
+            .iter()
+            .any(|function| is_mutation(&function.function_name))
+        {
+            self.method_policy.mutate.clone()
+        } else {
+            self.method_policy.get.clone()
+        };
+        logging::debug_event!(
+            functions = batch.len(),
+            method = %method,
+            request_id = %self.request_id,
+            size = bytes.len(),
+            "sending batch"
+        );
+        self.send_body(method, target_url, headers, &body, bytes, timeout)
+            .await
+    }
+
+    /// Performs one request per set of parameters, e.g. as returned by
+    /// `Parameters::param_in_chunked()`, and returns the items of all responses in order.
+    ///
+    /// Stops at the first failed request.
+    pub async fn request_chunks<R>(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        chunks: impl IntoIterator<Item = Parameters>,
+    ) -> WWClientResult<Vec<R::Item>>
+    where
+        R: DeserializeOwned + WWResponse,
+    {
+        let method = method.into();
+        let mut items = Vec::new();
+        for parameters in chunks {
+            let response = self
+                .request_generic::<R>(method.clone(), function, version, parameters, None)
+                .await?;
+            items.extend(response.into_items());
+        }
+        Ok(items)
+    }
+
+    /// Returns the number of rows matching `parameters`, without transferring them.
+    ///
+    /// A cursor limited to one row is requested, the total number of rows is read from the
+    /// `WWSVC-CURSOR-COUNT` header and the cursor is closed again. The cursor of the client isn't
+    /// used or changed. The version of the function is taken from `function_version()`, falling back
+    /// to 1. Returns `WWSVCError::MissingField` if the server doesn't return the count, and
+    /// `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status.
+    pub async fn count(&mut self, function: &str, parameters: Parameters) -> WWClientResult<u64> {
+        #[derive(serde::Deserialize)]
+        struct CountResponse {
+            #[serde(rename = "COMRESULT")]
+            com_result: Option<ComResult>,
+        }
+
+        let version = self.function_version(function).unwrap_or(1);
+        let headers = HashMap::from([
+            ("WWSVC-CURSOR", "CREATE"),
+            ("WWSVC-ACCEPT-RESULT-MAX-LINES", "1"),
+        ]);
+        let suspended = std::mem::replace(&mut self.suspend_cursor, true);
+        let result = self
+            .request_generic_with_headers::<CountResponse>(
+                None,
+                function,
+                version,
+                parameters,
+                Some(headers),
+            )
+            .await;
+        self.suspend_cursor = suspended;
+        let (response, headers) = result?;
+
+        // the count is all that is needed from the cursor, so it is closed right away
+        let mut cursor = Cursor::new(1);
+        cursor.update_from_headers(&headers)?;
+        if !cursor.closed() && !cursor.unused() {
+            self.abandon_cursor(AbandonedCursor {
+                cursor_id: cursor.cursor_id,
+                method: None,
+                function: function.to_string(),
+                version,
+            });
+            self.close_abandoned_cursors().await;
+        }
+
+        if let Some(com_result) = response.com_result.filter(|result| !result.is_success()) {
+            return Err(WWSVCError::FunctionFailed {
+                function: function.to_string(),
+                com_result: Box::new(com_result),
+            });
+        }
+        headers
+            .get("WWSVC-CURSOR-COUNT")
+            .and_then(|count| count.to_str().ok())
+            .and_then(|count| count.trim().parse().ok())
+            .ok_or_else(|| WWSVCError::MissingField("WWSVC-CURSOR-COUNT".to_string()))
     }
 }
 
@@ -503,8 +2072,13 @@ impl WebwareClient<OpenCursor> {
 
     /// Returns whether the current cursor is closed.
     ///
-    /// Returns None, if no cursor is available.
+    /// Returns true, if no cursor is available.
     pub fn cursor_closed(&self) -> bool {
-        self.cursor.as_ref().unwrap().closed()
+        self.cursor.as_ref().map_or(true, Cursor::closed)
+    }
+
+    /// Returns the current cursor.
+    pub fn cursor(&self) -> Option<&Cursor> {
+        self.cursor.as_ref()
     }
 }
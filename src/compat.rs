@@ -0,0 +1,34 @@
+/// Compatibility profile for different WEBWARE versions.
+///
+/// Older WEBWARE versions are picky about the casing of header names and about where the execute
+/// mode is transmitted. The profile is set on the builder via `.compat()` and defaults to
+/// `Compat::Latest`. All profiles currently sign requests identically.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Compat {
+    /// WEBWARE 2021 and older: header names are sent title-cased and the execute mode is only
+    /// transmitted in the request body.
+    Ww2021,
+    /// WEBWARE 2023: the execute mode is transmitted in the headers and the request body.
+    Ww2023,
+    /// The latest WEBWARE version.
+    #[default]
+    Latest,
+}
+
+impl Compat {
+    /// Returns whether header names are sent title-cased (e.g. `Wwsvc-Reqid`) instead of
+    /// lowercase over HTTP/1.
+    pub fn title_case_headers(&self) -> bool {
+        matches!(self, Compat::Ww2021)
+    }
+
+    /// Returns whether the `WWSVC-EXECUTE-MODE` header is sent.
+    pub fn execute_mode_in_headers(&self) -> bool {
+        !matches!(self, Compat::Ww2021)
+    }
+
+    /// Returns whether `EXECUTE_MODE` is sent in the `WWSVC_PASSINFO` of the request body.
+    pub fn execute_mode_in_body(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use flate2::write::GzEncoder;
+
+use crate::WWClientResult;
+
+/// Size in bytes from which bodies are compressed by `RequestCompression::gzip()`.
+pub const DEFAULT_MIN_SIZE: usize = 16 * 1024;
+
+/// Compression of EXECJSON request bodies, e.g. for `BELEG.INSERT` calls with many positions on
+/// slow links.
+///
+/// Compressed bodies are sent with the `Content-Encoding: gzip` header. If the server responds
+/// with `415 Unsupported Media Type`, the request is sent again uncompressed and the client and
+/// its clones stop compressing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RequestCompression {
+    /// Bodies are sent uncompressed.
+    #[default]
+    None,
+    /// Bodies of at least `min_size` bytes are compressed with gzip.
+    Gzip {
+        /// Size in bytes from which bodies are compressed.
+        min_size: usize,
+    },
+}
+
+impl RequestCompression {
+    /// Compresses bodies of at least `DEFAULT_MIN_SIZE` bytes with gzip.
+    pub fn gzip() -> Self {
+        RequestCompression::Gzip {
+            min_size: DEFAULT_MIN_SIZE,
+        }
+    }
+}
+
+/// Compresses request bodies according to a `RequestCompression`, until the server has rejected
+/// a compressed body. The rejection is shared between clones of a client.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Compressor {
+    compression: RequestCompression,
+    rejected: Arc<AtomicBool>,
+}
+
+impl Compressor {
+    pub(crate) fn new(compression: RequestCompression) -> Self {
+        Self {
+            compression,
+            rejected: Arc::default(),
+        }
+    }
+
+    /// Returns the compression in effect, which is `RequestCompression::None` once the server has
+    /// rejected a compressed body.
+    pub(crate) fn compression(&self) -> RequestCompression {
+        if self.rejected.load(Ordering::Relaxed) {
+            RequestCompression::None
+        } else {
+            self.compression
+        }
+    }
+
+    /// Returns the compressed body, or `None` if the body is to be sent uncompressed.
+    pub(crate) fn compress(&self, body: &[u8]) -> WWClientResult<Option<Vec<u8>>> {
+        match self.compression() {
+            RequestCompression::Gzip { min_size } if body.len() >= min_size => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(body)?;
+                Ok(Some(encoder.finish()?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Stops compressing bodies, after the server has rejected a compressed one.
+    pub(crate) fn reject(&self) {
+        self.rejected.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::client::states::{Ready, Registered};
+use crate::{Parameters, WWClientResult, WWResponse, WebwareClient};
+
+/// The function used for the checks. Every WEBWARE instance has articles.
+const CHECK_FUNCTION: &str = "ARTIKEL.GET";
+
+/// Outcome of a single conformance check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    /// The instance behaved as expected.
+    Passed,
+    /// The instance deviated from the expected behavior.
+    Failed(String),
+    /// The check could not be run, e.g. because a previous check failed.
+    Skipped(String),
+}
+
+/// Result of a single conformance check.
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    /// Name of the check.
+    pub name: &'static str,
+    /// Outcome of the check.
+    pub outcome: CheckOutcome,
+    /// Time it took to run the check.
+    pub duration: Duration,
+}
+
+/// Report of a conformance run against a WEBWARE instance.
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    /// Results of all checks in the order they were run.
+    pub checks: Vec<CheckResult>,
+}
+
+impl ConformanceReport {
+    /// Returns whether no check has failed.
+    pub fn passed(&self) -> bool {
+        !self
+            .checks
+            .iter()
+            .any(|check| matches!(check.outcome, CheckOutcome::Failed(_)))
+    }
+
+    fn record(&mut self, name: &'static str, started: Instant, outcome: CheckOutcome) {
+        self.checks.push(CheckResult {
+            name,
+            outcome,
+            duration: started.elapsed(),
+        });
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let (status, detail) = match &check.outcome {
+                CheckOutcome::Passed => ("PASS", ""),
+                CheckOutcome::Failed(reason) => ("FAIL", reason.as_str()),
+                CheckOutcome::Skipped(reason) => ("SKIP", reason.as_str()),
+            };
+            write!(
+                f,
+                "[{}] {} ({} ms)",
+                status,
+                check.name,
+                check.duration.as_millis()
+            )?;
+            if !detail.is_empty() {
+                write!(f, ": {}", detail)?;
+            }
+            writeln!(f)?;
+        }
+        write!(
+            f,
+            "{}",
+            if self.passed() {
+                "All checks passed."
+            } else {
+                "Some checks failed."
+            }
+        )
+    }
+}
+
+/// Runs a battery of protocol checks against the instance the client is configured for.
+///
+/// The checks cover registration, hash acceptance, max-lines handling, cursor behavior, the
+/// shape of error responses and deregistration. The client is registered for the run and
+/// deregistered afterwards.
+pub async fn run(client: WebwareClient) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+
+    let started = Instant::now();
+    let mut client = match client.register().await {
+        Ok(client) => {
+            report.record("register", started, CheckOutcome::Passed);
+            client
+        }
+        Err(e) => {
+            report.record("register", started, CheckOutcome::Failed(e.to_string()));
+            for name in ["hash", "max_lines", "cursor", "error_shape", "deregister"] {
+                report.record(
+                    name,
+                    Instant::now(),
+                    CheckOutcome::Skipped("registration failed".to_string()),
+                );
+            }
+            return report;
+        }
+    };
+
+    let started = Instant::now();
+    let outcome = check_hash(&mut client).await;
+    report.record("hash", started, outcome);
+
+    let started = Instant::now();
+    let outcome = check_max_lines(&mut client).await;
+    report.record("max_lines", started, outcome);
+
+    let started = Instant::now();
+    let (outcome, client) = check_cursor(client).await;
+    report.record("cursor", started, outcome);
+
+    let started = Instant::now();
+    let mut client = client;
+    let outcome = check_error_shape(&mut client).await;
+    report.record("error_shape", started, outcome);
+
+    let started = Instant::now();
+    let outcome = match client.deregister().await {
+        Ok(_) => CheckOutcome::Passed,
+        Err(e) => CheckOutcome::Failed(e.to_string()),
+    };
+    report.record("deregister", started, outcome);
+
+    report
+}
+
+async fn request_articles(
+    client: &mut WebwareClient<impl Ready>,
+) -> WWClientResult<serde_json::Value> {
+    client
+        .request(
+            reqwest::Method::PUT,
+            CHECK_FUNCTION,
+            1,
+            Parameters::new().param("FELDER", "ART_1_25"),
+            None,
+        )
+        .await
+}
+
+fn expect_success(response: &serde_json::Value) -> Result<(), CheckOutcome> {
+    match response.com_result() {
+        Some(com_result) if (200..300).contains(&com_result.status) => Ok(()),
+        Some(com_result) => Err(CheckOutcome::Failed(format!(
+            "unexpected COMRESULT {} {}: {}",
+            com_result.status, com_result.code, com_result.info
+        ))),
+        None => Err(CheckOutcome::Failed(
+            "response contains no COMRESULT".to_string(),
+        )),
+    }
+}
+
+async fn check_hash(client: &mut WebwareClient<Registered>) -> CheckOutcome {
+    match request_articles(client).await {
+        Ok(response) => expect_success(&response)
+            .err()
+            .unwrap_or(CheckOutcome::Passed),
+        Err(e) => CheckOutcome::Failed(e.to_string()),
+    }
+}
+
+async fn check_max_lines(client: &mut WebwareClient<Registered>) -> CheckOutcome {
+    client.set_result_max_lines(1);
+    let result = request_articles(client).await;
+    client.set_result_max_lines(1000);
+
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => return CheckOutcome::Failed(e.to_string()),
+    };
+    if let Err(outcome) = expect_success(&response) {
+        return outcome;
+    }
+    match response.items().len() {
+        0 => CheckOutcome::Skipped("the instance has no articles".to_string()),
+        1 => CheckOutcome::Passed,
+        n => CheckOutcome::Failed(format!("requested 1 line, received {}", n)),
+    }
+}
+
+async fn check_cursor(
+    client: WebwareClient<Registered>,
+) -> (CheckOutcome, WebwareClient<Registered>) {
+    let mut cursor_client = client.clone().create_cursor(1);
+    let outcome = match request_articles(&mut cursor_client).await {
+        Ok(response) => match expect_success(&response) {
+            Err(outcome) => outcome,
+            Ok(()) if response.items().len() > 1 => CheckOutcome::Failed(format!(
+                "cursor page of 1 line contained {} items",
+                response.items().len()
+            )),
+            Ok(()) => {
+                let cursor_id = cursor_client
+                    .cursor()
+                    .map(|cursor| cursor.cursor_id.as_str())
+                    .unwrap_or_default();
+                if cursor_id == "CREATE" || cursor_id.is_empty() {
+                    CheckOutcome::Failed("the server did not return a cursor ID".to_string())
+                } else {
+                    CheckOutcome::Passed
+                }
+            }
+        },
+        Err(e) => CheckOutcome::Failed(e.to_string()),
+    };
+
+    (outcome, client)
+}
+
+async fn check_error_shape(client: &mut WebwareClient<Registered>) -> CheckOutcome {
+    let response = client
+        .request(
+            reqwest::Method::PUT,
+            "WWSVCRS.CONFORMANCE.GET",
+            1,
+            Parameters::new(),
+            None,
+        )
+        .await;
+    match response {
+        Ok(response) => match response.com_result() {
+            Some(com_result) if (200..300).contains(&com_result.status) => {
+                CheckOutcome::Failed("an unknown function was reported as successful".to_string())
+            }
+            Some(_) => CheckOutcome::Passed,
+            None => CheckOutcome::Failed("error response contains no COMRESULT".to_string()),
+        },
+        Err(e) => CheckOutcome::Failed(e.to_string()),
+    }
+}
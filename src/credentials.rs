@@ -1,12 +1,31 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Clone)]
+use crate::{WWClientResult, WWSVCError};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
 /// Credentials for the client.
+///
+/// Credentials are only valid for the application they have been registered for. If they carry
+/// the vendor hash, the app hash or the revision, `register()` refuses them for a client with
+/// different values, which happens when persisted credentials are reused by another application.
 pub struct Credentials {
     /// The service pass for the client.
     pub service_pass: String,
     /// The app id for the client.
     pub app_id: String,
+    /// The vendor hash the credentials have been registered with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vendor_hash: Option<String>,
+    /// The app hash the credentials have been registered with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_hash: Option<String>,
+    /// The revision the credentials have been registered with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
 }
 
 impl Credentials {
@@ -15,6 +34,154 @@ impl Credentials {
         Credentials {
             service_pass: service_pass.to_string(),
             app_id: app_id.to_string(),
+            vendor_hash: None,
+            app_hash: None,
+            revision: None,
+        }
+    }
+
+    /// Binds the credentials to the application they have been registered for.
+    pub fn bound_to(mut self, vendor_hash: &str, app_hash: &str, revision: u32) -> Credentials {
+        self.vendor_hash = Some(vendor_hash.to_string());
+        self.app_hash = Some(app_hash.to_string());
+        self.revision = Some(revision);
+        self
+    }
+
+    /// Checks that the credentials can be used by the application with the given values.
+    ///
+    /// Values the credentials don't carry aren't checked.
+    pub fn check_binding(
+        &self,
+        vendor_hash: &str,
+        app_hash: &str,
+        revision: u32,
+    ) -> WWClientResult<()> {
+        let mismatch = |field: &str, expected: String, found: &String| {
+            Err(WWSVCError::CredentialsMismatch {
+                field: field.to_string(),
+                expected,
+                found: found.clone(),
+            })
+        };
+        match (&self.vendor_hash, &self.app_hash, self.revision) {
+            (Some(found), _, _) if found != vendor_hash => {
+                mismatch("vendor hash", vendor_hash.to_string(), found)
+            }
+            (_, Some(found), _) if found != app_hash => {
+                mismatch("app hash", app_hash.to_string(), found)
+            }
+            (_, _, Some(found)) if found != revision => {
+                mismatch("revision", revision.to_string(), &found.to_string())
+            }
+            _ => Ok(()),
         }
     }
 }
+
+/// Credentials replaced at runtime, shared between clones of a client.
+///
+/// Each clone remembers the generation of the credentials it has seen and takes over newer ones
+/// before signing its next request.
+#[derive(Clone, Default)]
+pub(crate) struct CredentialRotation {
+    shared: Arc<Mutex<(u64, Option<Credentials>)>>,
+    seen: u64,
+}
+
+impl CredentialRotation {
+    /// Publishes new credentials to all clones.
+    pub(crate) fn publish(&mut self, credentials: Credentials) {
+        let mut shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        shared.0 += 1;
+        shared.1 = Some(credentials);
+        self.seen = shared.0;
+    }
+
+    /// Returns the credentials published since the last call, if any.
+    pub(crate) fn take_newer(&mut self) -> Option<Credentials> {
+        let shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        if shared.0 > self.seen {
+            self.seen = shared.0;
+            shared.1.clone()
+        } else {
+            None
+        }
+    }
+}
+
+/// Source of stored credentials, e.g. a secret store, which the client consults when registering
+/// and when the server rejects the current credentials.
+///
+/// Returning `Ok(None)` means that no credentials are stored, in which case `register()` sends a
+/// `REGISTER` request as usual.
+pub trait CredentialsProvider: std::fmt::Debug + Send + Sync {
+    /// Fetches the current credentials.
+    fn credentials(&self) -> BoxFuture<'_, WWClientResult<Option<Credentials>>>;
+}
+
+/// Reads the credentials from environment variables, by default `WWSVC_SERVICE_PASS` and
+/// `WWSVC_APP_ID`.
+///
+/// The variables are read each time the credentials are requested, so the process environment
+/// doesn't have to be set up before the client is built.
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    service_pass_var: String,
+    app_id_var: String,
+}
+
+impl Default for EnvCredentials {
+    fn default() -> Self {
+        Self::new("WWSVC_SERVICE_PASS", "WWSVC_APP_ID")
+    }
+}
+
+impl EnvCredentials {
+    /// Reads the credentials from the given environment variables.
+    pub fn new(service_pass_var: &str, app_id_var: &str) -> Self {
+        Self {
+            service_pass_var: service_pass_var.to_string(),
+            app_id_var: app_id_var.to_string(),
+        }
+    }
+}
+
+impl CredentialsProvider for EnvCredentials {
+    fn credentials(&self) -> BoxFuture<'_, WWClientResult<Option<Credentials>>> {
+        Box::pin(async move {
+            let service_pass = std::env::var(&self.service_pass_var).ok();
+            let app_id = std::env::var(&self.app_id_var).ok();
+            Ok(service_pass
+                .zip(app_id)
+                .map(|(service_pass, app_id)| Credentials::new(&service_pass, &app_id)))
+        })
+    }
+}
+
+/// Reads the credentials from a JSON file containing `service_pass` and `app_id`, e.g. a file
+/// mounted from a secret store.
+#[derive(Debug, Clone)]
+pub struct FileCredentials {
+    path: PathBuf,
+}
+
+impl FileCredentials {
+    /// Reads the credentials from the file at `path`. A missing file means that no credentials
+    /// are stored.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CredentialsProvider for FileCredentials {
+    fn credentials(&self) -> BoxFuture<'_, WWClientResult<Option<Credentials>>> {
+        Box::pin(async move {
+            match tokio::fs::read(&self.path).await {
+                Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+}
@@ -1,3 +1,20 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures::channel::mpsc;
+use futures::{Future, SinkExt, Stream};
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::client::states::{OpenCursor, Ready};
+use crate::logging;
+use crate::responses::ComResult;
+use crate::{Parameters, WWClientResult, WWResponse, WWSVCError, WebwareClient};
+
 /// Pagination cursor, denoted by a cursor ID.
 ///
 /// In order to create a cursor, simply call `Cursor::new()`.
@@ -5,6 +22,13 @@
 ///
 /// Once you send a request containing the cursor, the WEBWARE server returns a new cursor ID.
 /// If there are no more results, the cursor ID will be "CLOSED".
+///
+/// Depending on the WEBWARE version, the server also returns the state of the cursor and the
+/// number of rows in the `WWSVC-CURSOR-STATE`, `WWSVC-CURSOR-REMAINING` and `WWSVC-CURSOR-COUNT`
+/// headers. These are `None` until a response contained them.
+///
+/// A cursor can be saved with `to_state()` and restored with `from_state()`, so a long-running
+/// export can continue after a restart, as long as the server keeps the cursor open.
 #[derive(Clone)]
 pub struct Cursor {
     /// The cursor ID.
@@ -14,15 +38,15 @@ pub struct Cursor {
     pub cursor_id: String,
     /// The maximum amount of results that will be returned.
     pub max_lines: u32,
-}
-
-impl Default for Cursor {
-    fn default() -> Self {
-        Self {
-            cursor_id: "CREATE".to_string(),
-            max_lines: 500,
-        }
-    }
+    /// The state of the cursor, as returned in the `WWSVC-CURSOR-STATE` header.
+    pub state: Option<CursorState>,
+    /// The number of rows which haven't been returned yet, as returned in the
+    /// `WWSVC-CURSOR-REMAINING` header.
+    pub remaining_rows: Option<u64>,
+    /// The total number of rows of the cursor, as returned in the `WWSVC-CURSOR-COUNT` header.
+    pub total_rows: Option<u64>,
+    /// The number of pages returned with the cursor so far.
+    pub pages: u32,
 }
 
 impl Cursor {
@@ -31,16 +55,724 @@ impl Cursor {
         Cursor {
             cursor_id: "CREATE".to_string(),
             max_lines,
+            state: None,
+            remaining_rows: None,
+            total_rows: None,
+            pages: 0,
         }
     }
 
-    /// Returns whether the cursor is closed.
+    /// Returns the state of the cursor, which can be persisted to resume reading its pages after
+    /// a restart.
+    ///
+    /// The server advances the cursor as soon as a page has been sent, so the state should be
+    /// taken once the last page has been processed.
+    pub fn to_state(&self) -> CursorSnapshot {
+        CursorSnapshot {
+            cursor_id: self.cursor_id.clone(),
+            max_lines: self.max_lines,
+            state: self.state.clone(),
+            remaining_rows: self.remaining_rows,
+            total_rows: self.total_rows,
+            pages: self.pages,
+        }
+    }
+
+    /// Restores a cursor from a state returned by `to_state()`.
+    pub fn from_state(state: CursorSnapshot) -> Cursor {
+        Cursor {
+            cursor_id: state.cursor_id,
+            max_lines: state.max_lines,
+            state: state.state,
+            remaining_rows: state.remaining_rows,
+            total_rows: state.total_rows,
+            pages: state.pages,
+        }
+    }
+
+    /// Returns whether the cursor is closed, either by its ID or by its state.
     pub fn closed(&self) -> bool {
-        &self.cursor_id == "CLOSED"
+        &self.cursor_id == "CLOSED" || self.state == Some(CursorState::Closed)
+    }
+
+    /// Returns whether no request has been sent with the cursor yet.
+    pub(crate) fn unused(&self) -> bool {
+        self.cursor_id == "CREATE" && self.state.is_none()
     }
 
     /// Sets the cursor ID.
     pub fn set_cursor_id(&mut self, cursor_id: String) {
         self.cursor_id = cursor_id;
     }
+
+    /// Takes over the cursor headers of a response. Headers which are missing keep their
+    /// previous value, counts which aren't numbers are ignored.
+    pub(crate) fn update_from_headers(&mut self, headers: &HeaderMap) -> WWClientResult<()> {
+        let header_str = |name: &str| {
+            headers
+                .get(name)
+                .map(|value| {
+                    value
+                        .to_str()
+                        .map_err(|_| WWSVCError::HeaderValueToStrError)
+                })
+                .transpose()
+        };
+
+        if let Some(state) = header_str("WWSVC-CURSOR-STATE")? {
+            self.state = Some(CursorState::from(state));
+        }
+        if let Some(remaining) = header_str("WWSVC-CURSOR-REMAINING")? {
+            self.remaining_rows = remaining.trim().parse().ok();
+        }
+        if let Some(total) = header_str("WWSVC-CURSOR-COUNT")? {
+            self.total_rows = total.trim().parse().ok();
+        }
+        if let Some(cursor_id) = header_str("WWSVC-CURSOR")? {
+            self.set_cursor_id(cursor_id.to_string());
+            self.pages += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Displays the cursor ID, the page size and the state and counts if known, e.g.
+/// `C1 (500 lines, OPEN, 20 of 520 rows remaining)`.
+impl Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} lines", self.cursor_id, self.max_lines)?;
+        if let Some(state) = &self.state {
+            write!(f, ", {}", state)?;
+        }
+        match (self.remaining_rows, self.total_rows) {
+            (Some(remaining), Some(total)) => {
+                write!(f, ", {} of {} rows remaining", remaining, total)?
+            }
+            (Some(remaining), None) => write!(f, ", {} rows remaining", remaining)?,
+            (None, Some(total)) => write!(f, ", {} rows", total)?,
+            (None, None) => {}
+        }
+        f.write_str(")")
+    }
+}
+
+/// Saved state of a `Cursor`, as returned by `Cursor::to_state()`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use wwsvc_rs::cursor::{Cursor, CursorSnapshot};
+/// use wwsvc_rs::{Parameters, WebwareClient};
+///
+/// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+/// let saved = std::fs::read_to_string("cursor.json").unwrap();
+/// let state: CursorSnapshot = serde_json::from_str(&saved).unwrap();
+/// // continue where the previous process stopped
+/// let mut pages = client
+///     .cursored_request::<wwsvc_rs::Value, wwsvc_rs::Value>(
+///         None,
+///         "ARTIKEL.GET",
+///         1,
+///         Parameters::new(),
+///     )
+///     .with_cursor(Cursor::from_state(state));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CursorSnapshot {
+    /// The cursor ID.
+    pub cursor_id: String,
+    /// The maximum amount of results that will be returned.
+    pub max_lines: u32,
+    /// The state of the cursor, as returned in the `WWSVC-CURSOR-STATE` header.
+    pub state: Option<CursorState>,
+    /// The number of rows which haven't been returned yet.
+    pub remaining_rows: Option<u64>,
+    /// The total number of rows of the cursor.
+    pub total_rows: Option<u64>,
+    /// The number of pages returned with the cursor so far.
+    pub pages: u32,
+}
+
+/// State of a cursor, as returned in the `WWSVC-CURSOR-STATE` header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum CursorState {
+    /// The cursor has more rows.
+    Open,
+    /// All rows have been returned.
+    Closed,
+    /// Any other state, in upper case.
+    Other(String),
+}
+
+impl Display for CursorState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CursorState::Open => f.write_str("OPEN"),
+            CursorState::Closed => f.write_str("CLOSED"),
+            CursorState::Other(state) => f.write_str(state),
+        }
+    }
+}
+
+impl From<&str> for CursorState {
+    fn from(state: &str) -> Self {
+        match state.trim().to_uppercase().as_str() {
+            "OPEN" => CursorState::Open,
+            "CLOSED" => CursorState::Closed,
+            other => CursorState::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for CursorState {
+    fn from(state: String) -> Self {
+        CursorState::from(state.as_str())
+    }
+}
+
+impl From<CursorState> for String {
+    fn from(state: CursorState) -> Self {
+        state.to_string()
+    }
+}
+
+/// Checks that the fields of the `SORTIERUNG` parameter are contained in `FELDER`, if both are set.
+fn check_sort_fields(parameters: &Parameters) -> WWClientResult<()> {
+    let (Some(sort), Some(fields)) = (parameters.get("SORTIERUNG"), parameters.get("FELDER"))
+    else {
+        return Ok(());
+    };
+    let selected = |field: &str| {
+        fields
+            .split(',')
+            .any(|selected| selected.trim().eq_ignore_ascii_case(field))
+    };
+    match sort
+        .split(',')
+        .map(str::trim)
+        .find(|field| !field.is_empty() && !selected(field))
+    {
+        Some(field) => Err(WWSVCError::SortFieldNotSelected(field.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Strategy for choosing the page size of a `CursoredResponse`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PaginationStrategy {
+    /// Every page uses the `max_lines` of the cursor.
+    #[default]
+    Fixed,
+    /// Starts with `initial` lines and doubles the page size up to `max` lines, as long as a page
+    /// takes less than `latency_threshold` to fetch. Once a page is slower, the page size is kept.
+    ///
+    /// Useful for exports of unknown size, where small pages waste round trips on large result
+    /// sets and large pages delay the first results.
+    Ramp {
+        /// Page size of the first page.
+        initial: u32,
+        /// Maximum page size.
+        max: u32,
+        /// Latency up to which the page size keeps growing.
+        latency_threshold: Duration,
+    },
+}
+
+impl PaginationStrategy {
+    /// Creates a ramp starting at `initial` lines and growing up to `max` lines, as long as a page
+    /// takes less than `latency_threshold`.
+    pub fn ramp(initial: u32, max: u32, latency_threshold: Duration) -> Self {
+        PaginationStrategy::Ramp {
+            initial,
+            max,
+            latency_threshold,
+        }
+    }
+
+    /// Returns the page size of the first page, or `None` if the size of the cursor is used.
+    fn initial_page_size(&self) -> Option<u32> {
+        match self {
+            PaginationStrategy::Fixed => None,
+            PaginationStrategy::Ramp { initial, max, .. } => Some((*initial).clamp(1, *max)),
+        }
+    }
+
+    /// Returns the page size following a page of `page_size` lines which took `latency`.
+    fn next_page_size(&self, page_size: u32, latency: Duration) -> u32 {
+        match self {
+            PaginationStrategy::Fixed => page_size,
+            PaginationStrategy::Ramp {
+                max,
+                latency_threshold,
+                ..
+            } => {
+                if latency < *latency_threshold {
+                    page_size.saturating_mul(2).min(*max)
+                } else {
+                    page_size
+                }
+            }
+        }
+    }
+}
+
+/// Statistics of a `CursoredResponse`, as returned by `CursoredResponse::stats()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CursorStats {
+    /// Number of pages fetched successfully.
+    pub pages: u32,
+    /// Number of rows over all pages.
+    pub rows: u64,
+    /// Number of bytes of all response bodies.
+    pub bytes: u64,
+    /// Time spent fetching pages.
+    pub elapsed: Duration,
+}
+
+/// Page of a `CursoredResponse`, as returned by `CursoredResponse::next_page()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    /// The items of the page.
+    pub items: Vec<T>,
+    /// The COMRESULT of the page, if the response type provides it.
+    pub com_result: Option<ComResult>,
+    /// The cursor ID returned with the page, e.g. `CLOSED` for the last page.
+    pub cursor_id: String,
+    /// The index of the page, starting at 0. Counts the pages returned with a restored cursor
+    /// before it has been saved.
+    pub page_index: u32,
+}
+
+impl<T> Page<T> {
+    /// Returns the number of rows of the page.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the page doesn't contain any row.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// Paginated response of a function, which is requested page by page with a cursor of its own.
+///
+/// Created by `WebwareClient::cursored_request()`. `T` is the type of the items, `R` the response
+/// type each page is deserialized into and `State` the state of the client.
+///
+/// The cursor ID and page size are sent in the headers of each page request, so the cursor isn't
+/// shared with other requests of the client or other cursored responses. It starts as a new cursor
+/// with the page size of the cursor of the client, if the client has one.
+///
+/// If the response is dropped before its last page, the cursor is closed with the next request of
+/// the client, unless it has been taken with `into_cursor()`.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "presets", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "presets"), doc = "```rust,ignore")]
+/// use wwsvc_rs::{responses::ArtikelGetResponse, Parameters, WebwareClient};
+///
+/// #[derive(Debug, serde::Deserialize, Clone)]
+/// pub struct ArticleData {
+///     #[serde(rename = "ART_1_25")]
+///     pub article_number: String,
+/// }
+///
+/// # async fn example(client: WebwareClient<wwsvc_rs::Registered>) {
+/// let mut client = client.create_cursor(500);
+/// let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+///     reqwest::Method::PUT,
+///     "ARTIKEL.GET",
+///     1,
+///     Parameters::new().param("FELDER", "ART_1_25"),
+/// );
+/// while let Some(page) = pages.next().await {
+///     println!("{:#?}", page.unwrap());
+/// }
+/// # }
+/// ```
+pub struct CursoredResponse<'a, T, R, State = OpenCursor> {
+    client: &'a mut WebwareClient<State>,
+    cursor: Cursor,
+    method: Option<reqwest::Method>,
+    function: String,
+    version: u32,
+    parameters: Parameters,
+    finished: bool,
+    strategy: PaginationStrategy,
+    page_size: Option<u32>,
+    timeout: Option<Duration>,
+    stats: CursorStats,
+    first_page: u32,
+    last_page: Option<(Bytes, Duration, String)>,
+    detached: bool,
+    marker: PhantomData<fn() -> (T, R)>,
+}
+
+impl<'a, T, R, State> CursoredResponse<'a, T, R, State>
+where
+    R: DeserializeOwned + WWResponse<Item = T>,
+    State: Ready,
+{
+    /// Requests the pages with the given cursor instead of a new one, e.g. a cursor restored with
+    /// `Cursor::from_state()` or taken from another response with `into_cursor()`.
+    pub fn with_cursor(mut self, cursor: Cursor) -> Self {
+        self.first_page = cursor.pages;
+        self.cursor = cursor;
+        self
+    }
+
+    /// Returns the cursor the pages are requested with.
+    pub fn cursor(&self) -> &Cursor {
+        &self.cursor
+    }
+
+    /// Returns the cursor without closing it, to request its remaining pages later, e.g. with
+    /// `with_cursor()`.
+    ///
+    /// A response which is dropped before its last page has been requested closes its cursor
+    /// instead, see `WebwareClient::close_abandoned_cursors()`.
+    pub fn into_cursor(mut self) -> Cursor {
+        self.detached = true;
+        let max_lines = self.cursor.max_lines;
+        std::mem::replace(&mut self.cursor, Cursor::new(max_lines))
+    }
+
+    /// Sets the strategy used to choose the page size. Defaults to `PaginationStrategy::Fixed`.
+    pub fn with_strategy(mut self, strategy: PaginationStrategy) -> Self {
+        self.page_size = strategy.initial_page_size();
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the page size, replacing the `max_lines` of the cursor. Has no effect on
+    /// `PaginationStrategy::Ramp`, which chooses its own page sizes.
+    pub fn with_page_size(mut self, max_lines: u32) -> Self {
+        if self.strategy == PaginationStrategy::Fixed {
+            self.page_size = Some(max_lines);
+        }
+        self
+    }
+
+    /// Sends each page request with the given timeout instead of the timeout of the client.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sorts the rows by the given fields, by setting the `SORTIERUNG` parameter.
+    ///
+    /// Without a sort order, the server may return rows on several pages or skip them. The fields
+    /// should form a unique key, so the order is stable. If `FELDER` is set, it has to contain the
+    /// sort fields, which is checked before the first page is requested.
+    pub fn sorted_by(mut self, fields: &[&str]) -> Self {
+        self.parameters.insert("SORTIERUNG", fields.join(","));
+        self
+    }
+
+    /// Requests the next page and returns its items.
+    ///
+    /// Returns `None` once the cursor has been closed by the server or a previous page has
+    /// failed. A page which couldn't be deserialized has to be consumed with `retry_last_page()`
+    /// before `next()` continues. If the server stops returning a cursor ID,
+    /// `WWSVCError::CursorLost` is returned instead of requesting the same page again.
+    pub async fn next(&mut self) -> Option<WWClientResult<Vec<T>>> {
+        Some(self.next_page().await?.map(|page| page.items))
+    }
+
+    /// Requests the next page like `next()` and returns it with its COMRESULT, cursor ID and index.
+    pub async fn next_page(&mut self) -> Option<WWClientResult<Page<T>>> {
+        if self.finished || self.cursor.closed() {
+            return None;
+        }
+
+        let result = self.fetch_page().await;
+        if result.is_err() {
+            self.finished = true;
+        }
+        if self.finished || self.cursor.closed() {
+            logging::info_event!(
+                function = %self.function,
+                pages = self.stats.pages,
+                rows = self.stats.rows,
+                bytes = self.stats.bytes,
+                elapsed_ms = self.stats.elapsed.as_millis() as u64,
+                failed = result.is_err(),
+                "cursor finished"
+            );
+        }
+        Some(result)
+    }
+
+    /// Deserializes the last page again, if it couldn't be consumed.
+    ///
+    /// The server advances the cursor as soon as a page has been sent, so the body of each page is
+    /// kept until it has been deserialized successfully. Once it has, `next()` continues with the
+    /// following page. Returns `None` if there is no such page.
+    pub fn retry_last_page(&mut self) -> Option<WWClientResult<Vec<T>>> {
+        self.last_page.as_ref()?;
+        let result = self.consume_last_page().map(|page| page.items);
+        if result.is_ok() {
+            self.finished = false;
+        }
+        Some(result)
+    }
+
+    /// Returns the body of the last page, if it couldn't be consumed.
+    pub fn last_page(&self) -> Option<&Bytes> {
+        self.last_page.as_ref().map(|(body, _, _)| body)
+    }
+
+    /// Bridges the pages into a channel holding up to `buffer` items, for consumers with their
+    /// own pipelines.
+    ///
+    /// Returns the receiver and the driver, a future which fetches the pages and has to be
+    /// polled alongside the consumer, e.g. with `futures::join!`. The driver only fetches the
+    /// next page once the channel has room, waits while it is full, and stops when the receiver
+    /// is dropped. It resolves to the statistics, or to the error of a failed page after the
+    /// items received so far have been delivered.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use wwsvc_rs::futures::StreamExt;
+    /// use wwsvc_rs::{Parameters, WebwareClient};
+    ///
+    /// # async fn example(client: WebwareClient<wwsvc_rs::Registered>) {
+    /// let mut client = client.create_cursor(500);
+    /// let pages = client.cursored_request::<wwsvc_rs::Value, wwsvc_rs::Value>(
+    ///     None,
+    ///     "ARTIKEL.GET",
+    ///     1,
+    ///     Parameters::new(),
+    /// );
+    /// let (rx, driver) = pages.into_channel(100);
+    /// let consumer = rx.for_each(|article| async move { println!("{}", article) });
+    /// let (stats, ()) = wwsvc_rs::futures::join!(driver, consumer);
+    /// println!("{:?}", stats.unwrap());
+    /// # }
+    /// ```
+    pub fn into_channel(
+        mut self,
+        buffer: usize,
+    ) -> (
+        mpsc::Receiver<T>,
+        impl Future<Output = WWClientResult<CursorStats>> + 'a,
+    )
+    where
+        T: 'a,
+        R: 'a,
+    {
+        let (mut tx, rx) = mpsc::channel(buffer);
+        let driver = async move {
+            while let Some(page) = self.next().await {
+                for item in page? {
+                    if tx.send(item).await.is_err() {
+                        return Ok(self.stats);
+                    }
+                }
+            }
+            Ok(self.stats)
+        };
+        (rx, driver)
+    }
+
+    /// Turns the pages into a stream of their items, to be used with the combinators of
+    /// `futures::StreamExt` and `futures::TryStreamExt`.
+    ///
+    /// Pages are requested when the items of the previous page have been consumed. The stream
+    /// ends after the last page, or after the error of a failed page.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use wwsvc_rs::futures::TryStreamExt;
+    /// use wwsvc_rs::{Parameters, WebwareClient};
+    ///
+    /// # async fn example(client: WebwareClient<wwsvc_rs::Registered>) {
+    /// let mut client = client.create_cursor(500);
+    /// let articles: Vec<wwsvc_rs::Value> = client
+    ///     .cursored_request::<wwsvc_rs::Value, wwsvc_rs::Value>(
+    ///         None,
+    ///         "ARTIKEL.GET",
+    ///         1,
+    ///         Parameters::new(),
+    ///     )
+    ///     .into_stream()
+    ///     .try_filter(|article| std::future::ready(article["ART_1_80"] != "0"))
+    ///     .try_collect()
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> impl Stream<Item = WWClientResult<T>> + 'a
+    where
+        T: 'a,
+        R: 'a,
+    {
+        futures::stream::unfold(
+            (self, VecDeque::new()),
+            |(mut pages, mut buffer)| async move {
+                loop {
+                    if let Some(item) = buffer.pop_front() {
+                        return Some((Ok(item), (pages, buffer)));
+                    }
+                    match pages.next().await? {
+                        Ok(items) => buffer.extend(items),
+                        Err(error) => return Some((Err(error), (pages, buffer))),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Returns the state of the cursor, which can be persisted to resume after a restart with
+    /// `with_cursor()` and `Cursor::from_state()`.
+    pub fn cursor_state(&self) -> CursorSnapshot {
+        self.cursor.to_state()
+    }
+
+    /// Returns the statistics of the pages fetched so far.
+    ///
+    /// Once the last page has been fetched, the statistics are also emitted as a `tracing` event.
+    pub fn stats(&self) -> CursorStats {
+        self.stats
+    }
+
+    async fn fetch_page(&mut self) -> WWClientResult<Page<T>> {
+        check_sort_fields(&self.parameters)?;
+        if let Some(page_size) = self.page_size {
+            self.cursor.max_lines = page_size;
+        }
+
+        if let Some(timeout) = self.timeout {
+            self.client.override_next_timeout(timeout);
+        }
+        let started = Instant::now();
+        let response = self
+            .client
+            .request_with_cursor(
+                &mut self.cursor,
+                self.method.clone(),
+                &self.function,
+                self.version,
+                self.parameters.clone(),
+            )
+            .await?;
+        if !response.headers().contains_key("WWSVC-CURSOR") {
+            return Err(WWSVCError::CursorLost);
+        }
+
+        let body = self.client.read_body(response).await?;
+        let latency = started.elapsed();
+        if let Some(page_size) = self.page_size {
+            self.page_size = Some(self.strategy.next_page_size(page_size, latency));
+        }
+        logging::debug_event!(
+            function = %self.function,
+            bytes = body.len(),
+            latency_ms = latency.as_millis() as u64,
+            "page fetched"
+        );
+        let cursor_id = self.cursor.cursor_id.clone();
+        self.last_page = Some((body, latency, cursor_id));
+        self.consume_last_page()
+    }
+
+    fn consume_last_page(&mut self) -> WWClientResult<Page<T>> {
+        let Some((body, latency, cursor_id)) = &self.last_page else {
+            return Ok(Page {
+                items: Vec::new(),
+                com_result: None,
+                cursor_id: String::new(),
+                page_index: self.first_page + self.stats.pages,
+            });
+        };
+        let response = serde_json::from_slice::<R>(body)?;
+        let com_result = response.com_result();
+        let items = response.into_items();
+        let page = Page {
+            items,
+            com_result,
+            cursor_id: cursor_id.clone(),
+            page_index: self.first_page + self.stats.pages,
+        };
+
+        self.stats.pages += 1;
+        self.stats.rows += page.items.len() as u64;
+        self.stats.bytes += body.len() as u64;
+        self.stats.elapsed += *latency;
+        self.last_page = None;
+        Ok(page)
+    }
+}
+
+/// Closes the cursor with the next request of the client if it hasn't been read to the end, so it
+/// isn't kept open on the server.
+impl<T, R, State> Drop for CursoredResponse<'_, T, R, State> {
+    fn drop(&mut self) {
+        if self.detached || self.cursor.closed() || self.cursor.unused() {
+            return;
+        }
+        self.client.abandon_cursor(AbandonedCursor {
+            cursor_id: self.cursor.cursor_id.clone(),
+            method: self.method.clone(),
+            function: self.function.clone(),
+            version: self.version,
+        });
+    }
+}
+
+/// Header sent with the ID of an abandoned cursor to close it.
+pub(crate) const CLOSE_HEADER: (&str, &str) = ("WWSVC-CURSOR-STATE", "CLOSED");
+
+/// Cursor of a `CursoredResponse` which has been dropped before its last page, closed before the
+/// next request of the client.
+#[derive(Debug, Clone)]
+pub(crate) struct AbandonedCursor {
+    pub(crate) cursor_id: String,
+    pub(crate) method: Option<reqwest::Method>,
+    pub(crate) function: String,
+    pub(crate) version: u32,
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Creates a paginated request. Each call to `CursoredResponse::next()` requests one page.
+    ///
+    /// Each response starts from a fresh `Cursor::new(max_lines)`, where `max_lines` is the page
+    /// size of the cursor of the client, or `result_max_lines` if the client doesn't have one.
+    /// The cursor of the client is neither used nor advanced. The page size can be adjusted
+    /// between pages with `CursoredResponse::with_strategy()`.
+    pub fn cursored_request<T, R>(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+    ) -> CursoredResponse<'_, T, R, State>
+    where
+        R: DeserializeOwned + WWResponse<Item = T>,
+    {
+        let cursor = self.request_cursor();
+        CursoredResponse {
+            first_page: cursor.pages,
+            cursor,
+            client: self,
+            method: method.into(),
+            function: function.to_string(),
+            version,
+            parameters,
+            finished: false,
+            strategy: PaginationStrategy::Fixed,
+            page_size: None,
+            timeout: None,
+            stats: CursorStats::default(),
+            last_page: None,
+            detached: false,
+            marker: PhantomData,
+        }
+    }
 }
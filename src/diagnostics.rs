@@ -0,0 +1,59 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::request_id::RequestId;
+use crate::responses::ComResult;
+
+/// Default number of COMRESULTs kept by a client.
+pub const DEFAULT_RECENT_RESULTS: usize = 32;
+
+/// COMRESULT of a request, as returned by `WebwareClient::recent_results()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentResult {
+    /// The ID of the request.
+    pub request_id: RequestId,
+    /// The full function name, e.g. `ARTIKEL.GET`.
+    pub function: String,
+    /// The COMRESULT returned by the server.
+    pub com_result: ComResult,
+}
+
+/// Ring buffer of the most recent COMRESULTs, shared between clones of a client, so an operator
+/// endpoint can show what the server answered lately without enabling verbose logging.
+#[derive(Debug, Clone)]
+pub(crate) struct RecentResults {
+    shared: Arc<Mutex<VecDeque<RecentResult>>>,
+    capacity: usize,
+}
+
+impl RecentResults {
+    /// Keeps the `capacity` most recent results. A capacity of 0 keeps none.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            shared: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Records a result, dropping the oldest one if the buffer is full.
+    pub(crate) fn record(&self, result: RecentResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut results = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        if results.len() == self.capacity {
+            results.pop_front();
+        }
+        results.push_back(result);
+    }
+
+    /// Returns the recorded results, the oldest first.
+    pub(crate) fn snapshot(&self) -> Vec<RecentResult> {
+        self.shared
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
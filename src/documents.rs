@@ -0,0 +1,189 @@
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+
+use crate::client::states::Ready;
+use crate::headers::{ResultType, ACCEPT_RESULT_TYPE};
+use crate::{collection, Parameters, WWClientResult, WebwareClient};
+
+/// Binary document returned by the WEBSERVICES, e.g. an article image.
+#[derive(Debug, Clone)]
+pub struct Document {
+    /// The content of the document.
+    pub bytes: Bytes,
+    /// The detected content type of the document, e.g. `image/png`.
+    pub content_type: String,
+}
+
+impl Document {
+    /// Creates a document from its content, detecting the content type from the leading bytes.
+    ///
+    /// `declared_type` is used if the content type couldn't be detected.
+    pub fn new(bytes: Bytes, declared_type: Option<&str>) -> Document {
+        let content_type = detect_content_type(&bytes)
+            .or(declared_type)
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        Document {
+            bytes,
+            content_type,
+        }
+    }
+}
+
+/// Detects the content type of common image and document formats from their magic bytes.
+pub fn detect_content_type(bytes: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"II*\x00", "image/tiff"),
+        (b"MM\x00*", "image/tiff"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+    ];
+
+    if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+
+    SIGNATURES
+        .iter()
+        .find(|(signature, _)| bytes.starts_with(signature))
+        .map(|(_, content_type)| *content_type)
+}
+
+/// Disk cache for documents, keyed by an identifier and a change timestamp.
+///
+/// Changing the timestamp of a record (e.g. after the image has been replaced) results in a new
+/// cache entry, so stale entries are never returned. Entries are named by the SHA-256 of the key
+/// and the timestamp, so distinct keys never share an entry, and the content type of each
+/// document is stored next to it.
+#[derive(Debug, Clone)]
+pub struct DocumentCache {
+    directory: PathBuf,
+}
+
+impl DocumentCache {
+    /// Creates a cache storing its entries in `directory`.
+    pub fn new(directory: impl AsRef<Path>) -> DocumentCache {
+        DocumentCache {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the cached document for the key and change timestamp, if present.
+    pub async fn get(&self, key: &str, changed_at: &str) -> WWClientResult<Option<Document>> {
+        let path = self.path(key, changed_at);
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let content_type = match tokio::fs::read_to_string(path.with_extension("type")).await {
+            Ok(content_type) => Some(content_type),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Some(Document::new(
+            Bytes::from(bytes),
+            content_type.as_deref(),
+        )))
+    }
+
+    /// Stores the document and its content type for the key and change timestamp.
+    pub async fn put(
+        &self,
+        key: &str,
+        changed_at: &str,
+        document: &Document,
+    ) -> WWClientResult<()> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let path = self.path(key, changed_at);
+        tokio::fs::write(path.with_extension("type"), &document.content_type).await?;
+        tokio::fs::write(path, &document.bytes).await?;
+        Ok(())
+    }
+
+    fn path(&self, key: &str, changed_at: &str) -> PathBuf {
+        let mut hasher = Sha256::new();
+        for value in [key, changed_at] {
+            hasher.update((value.len() as u64).to_le_bytes());
+            hasher.update(value);
+        }
+        let name = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        self.directory.join(name).with_extension("bin")
+    }
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Requests a binary document from the WEBSERVICES, using the result type `BIN`.
+    ///
+    /// The method is chosen by the `MethodPolicy` of the client.
+    pub async fn fetch_document(
+        &mut self,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+    ) -> WWClientResult<Document> {
+        let response = self
+            .request_as_response(
+                None,
+                function,
+                version,
+                parameters,
+                Some(collection! {
+                    ACCEPT_RESULT_TYPE => ResultType::Bin.as_str(),
+                }),
+            )
+            .await?
+            .error_for_status()?;
+        let declared_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        let bytes = response.bytes().await?;
+
+        Ok(Document::new(bytes, declared_type.as_deref()))
+    }
+
+    /// Requests the image of an article via `ARTIKELBILD.GET`.
+    ///
+    /// If a cache and the change timestamp of the article are provided, the image is read from and
+    /// stored into the cache.
+    pub async fn fetch_article_image(
+        &mut self,
+        article_number: &str,
+        changed_at: Option<&str>,
+        cache: Option<&DocumentCache>,
+    ) -> WWClientResult<Document> {
+        let cache = cache.zip(changed_at);
+        if let Some((cache, changed_at)) = cache {
+            if let Some(document) = cache.get(article_number, changed_at).await? {
+                return Ok(document);
+            }
+        }
+
+        let document = self
+            .fetch_document(
+                "ARTIKELBILD.GET",
+                1,
+                Parameters::new().param("ARTNR", article_number),
+            )
+            .await?;
+
+        if let Some((cache, changed_at)) = cache {
+            cache.put(article_number, changed_at, &document).await?;
+        }
+
+        Ok(document)
+    }
+}
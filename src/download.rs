@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use futures::StreamExt;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+
+use crate::client::states::Ready;
+use crate::{Parameters, WWClientResult, WebwareClient};
+
+/// File written by `WebwareClient::request_to_file()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDownload {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The SHA-256 checksum of the file, in lower case hex.
+    pub sha256: String,
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Performs a request to the WEBSERVICES and streams the response body to the file at
+    /// `path`, without holding it in memory.
+    ///
+    /// The body is written to `path` with the extension `.part` appended and renamed once it is
+    /// complete, so an existing file is only replaced by a complete download. Responses with an
+    /// error status are returned as an error without writing a file.
+    ///
+    /// If `method` is `None`, the method is chosen by the `MethodPolicy` of the client.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use wwsvc_rs::{Parameters, WebwareClient};
+    ///
+    /// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+    /// let download = client
+    ///     .request_to_file(None, "ARTIKEL.GET", 1, Parameters::new(), "articles.json")
+    ///     .await
+    ///     .unwrap();
+    /// println!("{} bytes, SHA-256 {}", download.size, download.sha256);
+    /// # }
+    /// ```
+    pub async fn request_to_file(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+        path: impl AsRef<Path>,
+    ) -> WWClientResult<FileDownload> {
+        let path = path.as_ref().to_path_buf();
+        let response = self
+            .request_as_response(method, function, version, parameters, None)
+            .await?
+            .error_for_status()?;
+
+        let mut part_path = path.clone().into_os_string();
+        part_path.push(".part");
+        let part_path = PathBuf::from(part_path);
+        let written = write_body(response, &part_path).await;
+        let (size, sha256) = match written {
+            Ok(written) => written,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&part_path).await;
+                return Err(e);
+            }
+        };
+        tokio::fs::rename(&part_path, &path).await?;
+
+        Ok(FileDownload { path, size, sha256 })
+    }
+}
+
+/// Writes the body of `response` to `path` and returns its size and SHA-256 checksum.
+async fn write_body(response: reqwest::Response, path: &Path) -> WWClientResult<(u64, String)> {
+    let mut file = tokio::fs::File::create(path).await?;
+    let mut hasher = Sha256::new();
+    let mut size = 0;
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        hasher.update(&chunk);
+        size += chunk.len() as u64;
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    let sha256 = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    Ok((size, sha256))
+}
@@ -0,0 +1,86 @@
+use serde::de::DeserializeOwned;
+
+use crate::client::states::Ready;
+use crate::{Parameters, WWClientResult, WWSVCError, WWValueExt, WebwareClient};
+
+/// Field of an EANCODE row containing the number of the linked article.
+const ARTICLE_NUMBER_FIELD: &str = "ART_1_25";
+
+/// Article found by its EAN, as returned by `WebwareClient::resolve_by_ean()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EanMatch<T> {
+    /// The number of the article.
+    pub article_number: String,
+    /// The matching row of `EANCODE.GET`, e.g. for the quantity the EAN stands for.
+    pub ean: serde_json::Value,
+    /// The article, as returned by `ARTIKEL.GET`.
+    pub article: T,
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Looks up the article with the given EAN, by requesting `EANCODE.GET` and then `ARTIKEL.GET`
+    /// for the matched article.
+    ///
+    /// Returns `None` if no EAN or no article matches.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use wwsvc_rs::WebwareClient;
+    ///
+    /// #[derive(Debug, serde::Deserialize, Clone)]
+    /// pub struct ArticleData {
+    ///     #[serde(rename = "ART_1_25")]
+    ///     pub article_number: String,
+    /// }
+    ///
+    /// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+    /// if let Some(found) = client.resolve_by_ean::<ArticleData>("4006381333931").await.unwrap() {
+    ///     println!("{:#?}", found.article);
+    /// }
+    /// # }
+    /// ```
+    pub async fn resolve_by_ean<T: DeserializeOwned>(
+        &mut self,
+        ean: &str,
+    ) -> WWClientResult<Option<EanMatch<T>>> {
+        let response = self
+            .request(
+                None,
+                "EANCODE.GET",
+                1,
+                Parameters::new().param("EANCODE", ean),
+                None,
+            )
+            .await?;
+        let Some(row) = response.list("EANCODELISTE", "EANCODE")?.first() else {
+            return Ok(None);
+        };
+        let article_number = row
+            .get(ARTICLE_NUMBER_FIELD)
+            .and_then(|number| number.as_str())
+            .ok_or_else(|| WWSVCError::MissingField(ARTICLE_NUMBER_FIELD.to_string()))?
+            .to_string();
+        let ean = row.clone();
+
+        let response = self
+            .request(
+                None,
+                "ARTIKEL.GET",
+                1,
+                Parameters::new().param("ARTNR", &article_number),
+                None,
+            )
+            .await?;
+        let article = response
+            .list_as::<T>("ARTIKELLISTE", "ARTIKEL")?
+            .into_iter()
+            .next();
+
+        Ok(article.map(|article| EanMatch {
+            article_number,
+            ean,
+            article,
+        }))
+    }
+}
@@ -1,3 +1,20 @@
+use crate::responses::ComResult;
+
+/// Coarse category of a [`WWSVCError`], useful for handling errors uniformly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The server could not be reached or the connection failed.
+    Network,
+    /// The client is missing credentials or is not authenticated.
+    Auth,
+    /// The server responded with something that doesn't follow the WEBSERVICES protocol.
+    Protocol,
+    /// The server responded with an error.
+    Server,
+    /// The request could not be built from the provided input.
+    Client,
+}
+
 /// Error type for the wwsvc-rs crate.
 #[derive(Debug, thiserror::Error, miette::Diagnostic)]
 pub enum WWSVCError {
@@ -22,17 +39,197 @@ pub enum WWSVCError {
     InvalidHeader,
 
     /// The request to the server has failed.
-    #[error(transparent)]
+    #[error("The request to the server has failed.")]
     #[diagnostic(code(wwsvc_rs::error::WWSVCError::ReqwestError))]
     ReqwestError(#[from] reqwest::Error),
 
     /// An invalid header value has been provided.
-    #[error(transparent)]
+    #[error("An invalid header value has been provided.")]
     #[diagnostic(code(wwsvc_rs::error::WWSVCError::InvalidHeaderValue))]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
 
     /// Url parsing error.
-    #[error(transparent)]
+    #[error("The URL could not be parsed.")]
     #[diagnostic(code(wwsvc_rs::error::WWSVCError::UrlParseError))]
     UrlParseError(#[from] url::ParseError),
+
+    /// The response could not be deserialized.
+    #[error("The response could not be deserialized.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::DeserializationError))]
+    DeserializationError(#[from] serde_json::Error),
+
+    /// Binary content could not be decoded.
+    #[error("Binary content could not be decoded.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::Base64DecodeError))]
+    Base64DecodeError(#[from] base64::DecodeError),
+
+    /// The response doesn't contain an expected field.
+    #[error("The response does not contain the field `{0}`.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::MissingField))]
+    MissingField(String),
+
+    /// The server didn't return a cursor ID while a cursor was in use.
+    #[error("The server did not return a cursor ID, the cursor has been lost.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::CursorLost))]
+    CursorLost,
+
+    /// A field code doesn't have the form `TABLE_NUMBER_LENGTH`.
+    #[error("`{0}` is not a valid field code.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::InvalidFieldCode))]
+    InvalidFieldCode(String),
+
+    /// The local limit of concurrent registrations has been reached.
+    #[error("The limit of {limit} concurrent registrations for vendor `{vendor_hash}` and application `{app_hash}` has been reached.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::RegistrationLimitExceeded))]
+    RegistrationLimitExceeded {
+        /// Vendor hash of the application.
+        vendor_hash: String,
+        /// Application hash of the application.
+        app_hash: String,
+        /// The configured limit.
+        limit: usize,
+    },
+
+    /// A function other than a `.GET` function or one of the `requests::READ_ONLY_FUNCTIONS`
+    /// has been called on a read-only client.
+    #[error("The client is read-only, `{0}` is not a reading function.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::ReadOnly))]
+    ReadOnly(String),
+
+    /// The function isn't permitted by the `FunctionPolicy` of the client.
+    #[error("The function `{0}` is not permitted by the function policy of the client.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::FunctionNotPermitted))]
+    FunctionNotPermitted(String),
+
+    /// The serialized request body exceeds the `BodySizeLimit` of the client.
+    #[error("The request body of `{function}` has {size} bytes, exceeding the limit of {limit} bytes. Consider splitting the parameter `{parameter}` into chunks.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::BodyTooLarge))]
+    BodyTooLarge {
+        /// The name of the function.
+        function: String,
+        /// The size of the body in bytes.
+        size: usize,
+        /// The configured limit in bytes.
+        limit: usize,
+        /// The name of the largest parameter.
+        parameter: String,
+    },
+
+    /// The credentials have been registered for another application.
+    #[error("The credentials have been registered with the {field} `{found}`, but the client uses `{expected}`.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::CredentialsMismatch))]
+    CredentialsMismatch {
+        /// The value which differs, e.g. `vendor hash`.
+        field: String,
+        /// The value of the client.
+        expected: String,
+        /// The value of the credentials.
+        found: String,
+    },
+
+    /// A sort field of a cursored request isn't contained in its `FELDER`.
+    #[error("The sort field `{0}` is not contained in FELDER.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::SortFieldNotSelected))]
+    SortFieldNotSelected(String),
+
+    /// An operation didn't complete within the given time.
+    #[error("The operation did not complete within {0:?}.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::Timeout))]
+    Timeout(std::time::Duration),
+
+    /// The server has rejected a function call in the COMRESULT of its response.
+    #[error("`{function}` failed with status {} ({}): {}", com_result.status, com_result.code, com_result.info)]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::FunctionFailed))]
+    FunctionFailed {
+        /// The name of the function.
+        function: String,
+        /// The COMRESULT of the response.
+        com_result: Box<ComResult>,
+    },
+
+    /// The server has responded with something else than JSON, e.g. the maintenance page of a load
+    /// balancer.
+    #[error("The server responded with status {status} and content type `{content_type}` instead of JSON: {snippet}")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::UnexpectedContentType))]
+    UnexpectedContentType {
+        /// The HTTP status of the response.
+        status: u16,
+        /// The content type of the response, empty if none was declared.
+        content_type: String,
+        /// The beginning of the response body.
+        snippet: String,
+    },
+
+    /// A record has been rejected by its `Validate` implementation before being sent.
+    #[error("The field `{field}` is invalid: {reason}.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::InvalidField))]
+    InvalidField {
+        /// The name of the field.
+        field: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+
+    /// A patch passed to `WebwareClient::put_patch()` isn't a JSON object.
+    #[error("A patch has to be a JSON object, but is {0}.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::InvalidPatch))]
+    InvalidPatch(String),
+
+    /// The HTTP client couldn't be built, e.g. because the TLS backend couldn't be initialized.
+    #[error("The HTTP client couldn't be built: {0}")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::HttpClientError))]
+    HttpClientError(std::sync::Arc<reqwest::Error>),
+
+    /// No random request ID could be generated.
+    #[error("No random request ID could be generated: {0}")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::RequestIdError))]
+    RequestIdError(getrandom::Error),
+
+    /// Reading or writing a local file has failed.
+    #[error("Reading or writing a local file has failed.")]
+    #[diagnostic(code(wwsvc_rs::error::WWSVCError::IoError))]
+    IoError(#[from] std::io::Error),
+}
+
+impl WWSVCError {
+    /// Returns the category of the error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            WWSVCError::NotAuthenticated
+            | WWSVCError::MissingCredentials
+            | WWSVCError::CredentialsMismatch { .. } => ErrorCategory::Auth,
+            WWSVCError::HeaderValueToStrError
+            | WWSVCError::MissingField(_)
+            | WWSVCError::CursorLost
+            | WWSVCError::DeserializationError(_)
+            | WWSVCError::Base64DecodeError(_) => ErrorCategory::Protocol,
+            WWSVCError::InvalidHeader
+            | WWSVCError::InvalidHeaderValue(_)
+            | WWSVCError::UrlParseError(_)
+            | WWSVCError::InvalidFieldCode(_)
+            | WWSVCError::RegistrationLimitExceeded { .. }
+            | WWSVCError::ReadOnly(_)
+            | WWSVCError::FunctionNotPermitted(_)
+            | WWSVCError::BodyTooLarge { .. }
+            | WWSVCError::SortFieldNotSelected(_)
+            | WWSVCError::InvalidField { .. }
+            | WWSVCError::InvalidPatch(_)
+            | WWSVCError::HttpClientError(_)
+            | WWSVCError::RequestIdError(_)
+            | WWSVCError::IoError(_) => ErrorCategory::Client,
+            WWSVCError::Timeout(_) => ErrorCategory::Network,
+            WWSVCError::UnexpectedContentType { .. } => ErrorCategory::Server,
+            WWSVCError::FunctionFailed { com_result, .. } => match com_result.status {
+                400..=499 => ErrorCategory::Client,
+                _ => ErrorCategory::Server,
+            },
+            WWSVCError::ReqwestError(e) => match e.status() {
+                Some(status) if status.is_server_error() => ErrorCategory::Server,
+                Some(status) if status.is_client_error() => ErrorCategory::Client,
+                _ if e.is_decode() => ErrorCategory::Protocol,
+                _ if e.is_builder() => ErrorCategory::Client,
+                _ => ErrorCategory::Network,
+            },
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::responses::ComResult;
+use crate::Parameters;
+
+/// Receiving end of a mutation subscription, created by `WebwareClient::subscribe_mutations()`.
+pub type MutationReceiver = UnboundedReceiver<MutationEvent>;
+
+/// Event which is emitted after a mutating WEBSERVICES function has succeeded.
+///
+/// A function is considered mutating if it ends in one of the `MUTATING_SUFFIXES` (see
+/// [`is_mutation`]).
+#[derive(Debug, Clone)]
+pub struct MutationEvent {
+    /// The full function name, e.g. `ARTIKEL.PUT`.
+    pub function: String,
+    /// The revision of the function.
+    pub version: u32,
+    /// The parameters which were sent with the request, identifying the affected record.
+    pub parameters: Parameters,
+    /// The COMRESULT returned by the server.
+    pub com_result: ComResult,
+}
+
+/// Suffixes of the functions which mutate data on the server, e.g. `ARTIKEL.INSERT`.
+pub const MUTATING_SUFFIXES: &[&str] =
+    &[".PUT", ".INSERT", ".DELETE", ".UPDATE", ".CREATE", ".SET"];
+
+/// Returns whether the function with the given name mutates data on the server, i.e. whether it
+/// ends in one of the `MUTATING_SUFFIXES`.
+///
+/// All other functions, e.g. `ARTIKEL.GET` or `SERVICEPASS.INFO`, are considered reading.
+pub fn is_mutation(function: &str) -> bool {
+    let function = function.to_uppercase();
+    MUTATING_SUFFIXES
+        .iter()
+        .any(|suffix| function.ends_with(suffix))
+}
+
+/// Subscribers for mutation events, shared between clones of a client.
+#[derive(Clone, Default)]
+pub(crate) struct MutationHooks {
+    subscribers: Arc<Mutex<Vec<UnboundedSender<MutationEvent>>>>,
+}
+
+impl MutationHooks {
+    /// Registers a new subscriber and returns its receiving end.
+    pub(crate) fn subscribe(&self) -> MutationReceiver {
+        let (sender, receiver) = mpsc::unbounded();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(sender);
+        receiver
+    }
+
+    /// Returns whether at least one subscriber is registered.
+    pub(crate) fn has_subscribers(&self) -> bool {
+        !self
+            .subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Sends the event to all subscribers, dropping the ones whose receiver has been closed.
+    pub(crate) fn emit(&self, event: MutationEvent) {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|subscriber| subscriber.unbounded_send(event.clone()).is_ok());
+    }
+}
@@ -0,0 +1,121 @@
+use bytes::Bytes;
+use futures::Stream;
+
+use crate::client::states::OpenCursor;
+use crate::cursor::Cursor;
+use crate::{Parameters, WWClientResult, WebwareClient};
+
+/// State of a running JSON export.
+struct JsonExport {
+    client: WebwareClient<OpenCursor>,
+    cursor: Cursor,
+    method: Option<reqwest::Method>,
+    function: String,
+    version: u32,
+    parameters: Parameters,
+    started: bool,
+    wrote_item: bool,
+    finished: bool,
+}
+
+impl JsonExport {
+    /// Returns the next chunk of the JSON array. Sets `finished` once the closing bracket or an
+    /// error has been returned.
+    async fn next_chunk(&mut self) -> WWClientResult<Bytes> {
+        if !self.started {
+            self.started = true;
+            return Ok(Bytes::from_static(b"["));
+        }
+
+        loop {
+            let mut pages = self
+                .client
+                .cursored_request::<serde_json::Value, serde_json::Value>(
+                    self.method.clone(),
+                    &self.function,
+                    self.version,
+                    self.parameters.clone(),
+                )
+                .with_cursor(self.cursor.clone());
+            let page = pages.next().await;
+            self.cursor = pages.into_cursor();
+            let items = match page {
+                None => {
+                    self.finished = true;
+                    return Ok(Bytes::from_static(b"]"));
+                }
+                Some(Err(e)) => {
+                    self.finished = true;
+                    return Err(e);
+                }
+                Some(Ok(items)) if items.is_empty() => continue,
+                Some(Ok(items)) => items,
+            };
+
+            let mut chunk = Vec::new();
+            for item in items {
+                if self.wrote_item {
+                    chunk.push(b',');
+                }
+                serde_json::to_writer(&mut chunk, &item)?;
+                self.wrote_item = true;
+            }
+            return Ok(Bytes::from(chunk));
+        }
+    }
+}
+
+impl WebwareClient<OpenCursor> {
+    /// Exports all rows of a function as a JSON array, streamed page by page as they arrive.
+    ///
+    /// The stream can be used as the body of a streaming HTTP response, e.g. with axum's
+    /// `Body::from_stream()` or actix-web's `HttpResponseBuilder::streaming()`, so proxies don't
+    /// have to buffer the whole export. If a page fails, the error is returned and the stream
+    /// ends without closing the array.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,no_run
+    /// use wwsvc_rs::futures::StreamExt;
+    /// use wwsvc_rs::{Parameters, WebwareClient};
+    ///
+    /// # async fn example(client: WebwareClient<wwsvc_rs::Registered>) {
+    /// let export = client.create_cursor(500).into_json_stream(
+    ///     None,
+    ///     "ARTIKEL.GET",
+    ///     1,
+    ///     Parameters::new().param("FELDER", "ART_1_25"),
+    /// );
+    /// let mut export = std::pin::pin!(export);
+    /// while let Some(chunk) = export.next().await {
+    ///     print!("{}", String::from_utf8_lossy(&chunk.unwrap()));
+    /// }
+    /// # }
+    /// ```
+    pub fn into_json_stream(
+        self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+    ) -> impl Stream<Item = WWClientResult<Bytes>> + Send + 'static {
+        let export = JsonExport {
+            cursor: self.request_cursor(),
+            client: self,
+            method: method.into(),
+            function: function.to_string(),
+            version,
+            parameters,
+            started: false,
+            wrote_item: false,
+            finished: false,
+        };
+
+        futures::stream::unfold(Some(export), |export| async move {
+            let mut export = export?;
+            let chunk = export.next_chunk().await;
+            let next = (!export.finished).then_some(export);
+            Some((chunk, next))
+        })
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::states::Ready;
+use crate::{Parameters, WWClientResult, WWResponse, WWSVCError, WebwareClient};
+
+/// Code of a field, e.g. `ART_1_25`, consisting of the table prefix, the field number and the
+/// length of the field.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FieldCode {
+    /// The table prefix, e.g. `ART`.
+    pub table: String,
+    /// The number of the field within the table.
+    pub number: u32,
+    /// The maximum length of the field.
+    pub length: u32,
+}
+
+impl FromStr for FieldCode {
+    type Err = WWSVCError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let invalid = || WWSVCError::InvalidFieldCode(code.to_string());
+        let mut parts = code.rsplitn(3, '_');
+        let length = parts.next().and_then(|part| part.parse().ok());
+        let number = parts.next().and_then(|part| part.parse().ok());
+        let table = parts.next().filter(|table| !table.is_empty());
+
+        match (table, number, length) {
+            (Some(table), Some(number), Some(length)) => Ok(FieldCode {
+                table: table.to_string(),
+                number,
+                length,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl Display for FieldCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}_{}", self.table, self.number, self.length)
+    }
+}
+
+/// Description of a single field, as returned by field list functions.
+///
+/// The member names differ between functions and installations, so the commonly used ones are
+/// accepted as aliases.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldInfo {
+    /// The code of the field, e.g. `ART_1_25`.
+    #[serde(rename = "FELDNAME", alias = "FELD", alias = "NAME")]
+    pub code: String,
+    /// The description of the field.
+    #[serde(
+        rename = "BESCHREIBUNG",
+        alias = "BEZEICHNUNG",
+        alias = "TEXT",
+        default
+    )]
+    pub description: String,
+    /// The type of the field, as reported by the server.
+    #[serde(rename = "TYP", alias = "FELDTYP", default)]
+    pub field_type: Option<String>,
+}
+
+impl FieldInfo {
+    /// Parses the code of the field.
+    pub fn parsed_code(&self) -> WWClientResult<FieldCode> {
+        self.code.parse()
+    }
+}
+
+/// List of the fields of one or more tables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldList {
+    fields: Vec<FieldInfo>,
+}
+
+impl FieldList {
+    /// Creates a field list from the given fields.
+    pub fn new(fields: Vec<FieldInfo>) -> Self {
+        Self { fields }
+    }
+
+    /// Reads the field list from the first list of a response.
+    pub fn from_response(response: &serde_json::Value) -> WWClientResult<Self> {
+        let fields = response
+            .items()
+            .iter()
+            .map(|item| Ok(FieldInfo::deserialize(item)?))
+            .collect::<WWClientResult<Vec<_>>>()?;
+        Ok(Self { fields })
+    }
+
+    /// Returns the field with the given code.
+    pub fn get(&self, code: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|field| field.code == code)
+    }
+
+    /// Returns whether a field with the given code exists.
+    pub fn contains(&self, code: &str) -> bool {
+        self.get(code).is_some()
+    }
+
+    /// Returns the fields of the table with the given prefix, e.g. `ART`.
+    pub fn for_table<'a>(&'a self, table: &'a str) -> impl Iterator<Item = &'a FieldInfo> + 'a {
+        self.fields.iter().filter(move |field| {
+            field
+                .parsed_code()
+                .is_ok_and(|code| code.table.eq_ignore_ascii_case(table))
+        })
+    }
+
+    /// Returns all fields.
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    /// Returns the amount of fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Requests a field list with the given function and reads it into a `FieldList`.
+    ///
+    /// Which function lists the fields depends on the installation, so it has to be provided.
+    pub async fn fetch_fields(
+        &mut self,
+        function: &str,
+        version: u32,
+        parameters: Parameters,
+    ) -> WWClientResult<FieldList> {
+        let response = self
+            .request(None, function, version, parameters, None)
+            .await?;
+        FieldList::from_response(&response)
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::Display;
+
+use reqwest::header::{HeaderValue, InvalidHeaderValue};
+use serde::{Deserialize, Serialize};
+
+pub use crate::requests::ExecuteMode;
+
+/// Name of the header selecting the result type of a response, see `ResultType`.
+pub const ACCEPT_RESULT_TYPE: &str = "WWSVC-ACCEPT-RESULT-TYPE";
+/// Name of the header selecting the mode a function call is executed in, see `ExecuteMode`.
+pub const EXECUTE_MODE: &str = "WWSVC-EXECUTE-MODE";
+
+/// Format in which the WEBSERVICES return the result of a function call, sent in the
+/// `WWSVC-ACCEPT-RESULT-TYPE` header.
+///
+/// ## Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use wwsvc_rs::collection;
+/// use wwsvc_rs::headers::{ResultType, ACCEPT_RESULT_TYPE};
+///
+/// let headers: HashMap<&str, &str> = collection! {
+///     ACCEPT_RESULT_TYPE => ResultType::Bin.as_str(),
+/// };
+/// assert_eq!(headers["WWSVC-ACCEPT-RESULT-TYPE"], "BIN");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ResultType {
+    /// The result is returned as JSON.
+    #[default]
+    Json,
+    /// The result is returned as binary content, e.g. a document.
+    Bin,
+    /// The result is returned as XML.
+    Xml,
+    /// Any other result type, in upper case.
+    Other(String),
+}
+
+impl ResultType {
+    /// Returns the name of the result type as sent to the server, e.g. `JSON`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResultType::Json => "JSON",
+            ResultType::Bin => "BIN",
+            ResultType::Xml => "XML",
+            ResultType::Other(result_type) => result_type,
+        }
+    }
+
+    /// Returns the result type as a header value.
+    pub fn header_value(&self) -> Result<HeaderValue, InvalidHeaderValue> {
+        HeaderValue::from_str(self.as_str())
+    }
+}
+
+impl Display for ResultType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for ResultType {
+    fn from(result_type: &str) -> Self {
+        match result_type.trim().to_uppercase().as_str() {
+            "JSON" => ResultType::Json,
+            "BIN" => ResultType::Bin,
+            "XML" => ResultType::Xml,
+            other => ResultType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ResultType {
+    fn from(result_type: String) -> Self {
+        ResultType::from(result_type.as_str())
+    }
+}
+
+impl From<ResultType> for String {
+    fn from(result_type: ResultType) -> Self {
+        result_type.as_str().to_string()
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt::Display;
+
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::client::states::Ready;
+use crate::fields::FieldCode;
+use crate::responses::ComResult;
+use crate::{Parameters, WWClientResult, WWResponse, WWValueExt, WebwareClient};
+
+/// Individual database (IDB) table, e.g. `IDBID0026`.
+///
+/// Derives the function names and the response container of the table from its ID, so IDB
+/// tables can be consumed without a response type per table.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use wwsvc_rs::{idb::IdbTable, Parameters, WebwareClient};
+///
+/// #[derive(Debug, serde::Deserialize, Clone)]
+/// pub struct Tracking {
+///     #[serde(rename = "IDB_1_20")]
+///     pub tracking_number: String,
+/// }
+///
+/// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+/// let table = IdbTable::new(26);
+/// assert_eq!(table.function("GET"), "IDBID0026.GET");
+///
+/// let response = table.get::<Tracking>(&mut client, Parameters::new()).await.unwrap();
+/// println!("{:#?}", response.items);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdbTable {
+    name: String,
+    version: u32,
+}
+
+impl IdbTable {
+    /// Creates the table with the given numeric ID, e.g. `26` for `IDBID0026`.
+    pub fn new(id: u32) -> IdbTable {
+        IdbTable {
+            name: format!("IDBID{:04}", id),
+            version: 1,
+        }
+    }
+
+    /// Sets the version of the functions of the table. Defaults to 1.
+    pub fn with_version(mut self, version: u32) -> IdbTable {
+        self.version = version;
+        self
+    }
+
+    /// Returns the name of the table, e.g. `IDBID0026`. This is also the name of the list in
+    /// the response container.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the version of the functions of the table.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the name of the function `operation` of the table, e.g. `IDBID0026.GET`.
+    pub fn function(&self, operation: &str) -> String {
+        format!("{}.{}", self.name, operation.to_uppercase())
+    }
+
+    /// Returns the prefix of the field codes of the table, as in `IDB_1_20`.
+    pub fn field_prefix(&self) -> &'static str {
+        "IDB"
+    }
+
+    /// Returns the code of the field with the given number and length, e.g. `IDB_1_20`.
+    pub fn field(&self, number: u32, length: u32) -> FieldCode {
+        FieldCode {
+            table: self.field_prefix().to_string(),
+            number,
+            length,
+        }
+    }
+
+    /// Starts a query for the rows of the table.
+    pub fn query(&self) -> IdbQuery {
+        IdbQuery {
+            table: self.clone(),
+            fields: Vec::new(),
+            parameters: Parameters::new(),
+        }
+    }
+
+    /// Returns the name of the response container, e.g. `IDBID0026LISTE`.
+    pub fn container(&self) -> String {
+        format!("{}LISTE", self.name)
+    }
+
+    /// Requests the rows of the table matching `parameters`.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        client: &mut WebwareClient<impl Ready>,
+        parameters: Parameters,
+    ) -> WWClientResult<IdbGetResponse<T>> {
+        client
+            .request_generic(None, &self.function("GET"), self.version, parameters, None)
+            .await
+    }
+
+    /// Calls the function `operation` of the table and returns the untyped response, e.g. for
+    /// `INSERT`, `PUT` or `DELETE`.
+    pub async fn call(
+        &self,
+        client: &mut WebwareClient<impl Ready>,
+        operation: &str,
+        parameters: Parameters,
+    ) -> WWClientResult<serde_json::Value> {
+        client
+            .request(
+                None,
+                &self.function(operation),
+                self.version,
+                parameters,
+                None,
+            )
+            .await
+    }
+}
+
+impl Display for IdbTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// Query for the rows of an IDB table, created by `IdbTable::query()`.
+///
+/// ## Example
+///
+/// ```rust
+/// use wwsvc_rs::{idb::IdbTable, idb_fields};
+///
+/// idb_fields! {
+///     /// Fields of the tracking table.
+///     pub mod tracking = 26 {
+///         TRACKING_NUMBER = 1, 20;
+///         CARRIER = 2, 40;
+///     }
+/// }
+///
+/// let parameters = tracking::table()
+///     .query()
+///     .select(&[tracking::TRACKING_NUMBER, tracking::CARRIER])
+///     .filter(tracking::CARRIER, "DHL")
+///     .into_parameters();
+/// assert_eq!(parameters.get("FELDER"), Some("IDB_1_20,IDB_2_40"));
+/// assert_eq!(parameters.get("IDB_2_40"), Some("DHL"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdbQuery {
+    table: IdbTable,
+    fields: Vec<String>,
+    parameters: Parameters,
+}
+
+impl IdbQuery {
+    /// Adds fields to the `FELDER` parameter, i.e. the fields which are returned.
+    pub fn select(mut self, fields: &[&str]) -> IdbQuery {
+        for field in fields {
+            if !self.fields.iter().any(|existing| existing == field) {
+                self.fields.push(field.to_string());
+            }
+        }
+        self
+    }
+
+    /// Only returns rows where `field` has the given value.
+    pub fn filter(mut self, field: &str, value: impl ToString) -> IdbQuery {
+        self.parameters.insert(field, value);
+        self
+    }
+
+    /// Sets any other parameter of the GET function.
+    pub fn param(mut self, name: &str, content: impl ToString) -> IdbQuery {
+        self.parameters.insert(name, content);
+        self
+    }
+
+    /// Returns the parameters of the query.
+    pub fn into_parameters(self) -> Parameters {
+        let mut parameters = self.parameters;
+        if !self.fields.is_empty() {
+            parameters.insert("FELDER", self.fields.join(","));
+        }
+        parameters
+    }
+
+    /// Sends the query and returns the matching rows.
+    pub async fn send<T: DeserializeOwned>(
+        self,
+        client: &mut WebwareClient<impl Ready>,
+    ) -> WWClientResult<IdbGetResponse<T>> {
+        let table = self.table.clone();
+        table.get(client, self.into_parameters()).await
+    }
+}
+
+/// Response of a GET function of any IDB table.
+///
+/// The items are read from the first list of the response, so the type doesn't depend on the
+/// table and can be used with e.g. `WebwareClient::cursored_request()`. Since the table isn't
+/// known, the items are serialized into a `LISTE.ITEMS` list, which is read back the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdbGetResponse<T> {
+    /// The COMRESULT of the request. Contains information about the status of the request.
+    pub com_result: ComResult,
+    /// The rows of the table.
+    pub items: Vec<T>,
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for IdbGetResponse<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let com_result = WWValueExt::com_result(&value).map_err(serde::de::Error::custom)?;
+        let items = value
+            .into_items()
+            .into_iter()
+            .map(T::deserialize)
+            .collect::<Result<_, _>>()
+            .map_err(serde::de::Error::custom)?;
+        Ok(IdbGetResponse { com_result, items })
+    }
+}
+
+impl<T: Serialize> Serialize for IdbGetResponse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct List<'a, T> {
+            #[serde(rename = "ITEMS")]
+            items: &'a [T],
+        }
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("COMRESULT", &self.com_result)?;
+        map.serialize_entry("LISTE", &List { items: &self.items })?;
+        map.end()
+    }
+}
+
+impl<T> WWResponse for IdbGetResponse<T> {
+    type Item = T;
+
+    fn com_result(&self) -> Option<ComResult> {
+        Some(self.com_result.clone())
+    }
+
+    fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
@@ -12,7 +12,8 @@
 //! Here is an example using the `derive` feature, which is the preferred way
 //! of using this crate.
 //!
-//! ```rust,no_run
+#![cfg_attr(feature = "derive", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "derive"), doc = "```rust,ignore")]
 //! use wwsvc_rs::{WebwareClient, Unregistered, WWSVCGetData, collection};
 //!
 //! #[derive(WWSVCGetData, Debug, Clone, serde::Deserialize)]
@@ -46,7 +47,7 @@
 //!
 //! ```rust,no_run
 //! use reqwest::Method;
-//! use wwsvc_rs::{collection, WebwareClient, WWSVCGetData, generate_get_response};
+//! use wwsvc_rs::{collection, WebwareClient, generate_get_response};
 //!
 //! #[derive(Debug, serde::Deserialize, Clone)]
 //! pub struct ArticleData {
@@ -85,45 +86,134 @@ extern crate encoding_rs;
 extern crate httpdate;
 extern crate md5;
 extern crate reqwest;
+extern crate self as wwsvc_rs;
 extern crate serde;
-#[macro_use]
 extern crate serde_json;
-extern crate self as wwsvc_rs;
 
+/// Module containing grouping and aggregates computed by the server.
+pub mod aggregation;
 /// Module containing the app hash, which is needed for each request.
 pub mod app_hash;
+/// Module containing the tickets of function calls executed asynchronously.
+pub mod asynchron;
+/// Module containing the backoff policy for retries.
+pub mod backoff;
+/// Module containing helpers for binary content.
+pub mod binary;
+/// Module containing the compatibility profiles for WEBWARE versions.
+pub mod compat;
+/// Module containing the compression of request bodies.
+pub mod compression;
+/// Module containing the protocol conformance checks.
+#[cfg(feature = "conformance")]
+#[cfg_attr(docsrs, doc(cfg(feature = "conformance")))]
+pub mod conformance;
 /// Module containing the pagination cursor.
 pub mod cursor;
+/// Module containing the diagnostics of recent requests.
+pub mod diagnostics;
+/// Module containing binary documents like article images.
+pub mod documents;
+/// Module containing downloads of responses to files.
+pub mod download;
+/// Module containing the lookup of articles by their EAN.
+pub mod ean;
 /// Module containing the error type.
 pub mod error;
+/// Module containing mutation events.
+pub mod events;
+/// Module containing the streaming JSON export.
+pub mod export;
+/// Module containing field codes and field lists.
+pub mod fields;
+/// Module containing the names and typed values of protocol headers.
+pub mod headers;
+/// Module containing generic access to individual database (IDB) tables.
+pub mod idb;
+/// Module containing the runtime warnings about common misconfigurations.
+pub mod lints;
+mod logging;
 /// Module containing the macros.
 pub mod macros;
+/// Module containing curated models for commonly used entities.
+#[cfg(feature = "models")]
+#[cfg_attr(docsrs, doc(cfg(feature = "models")))]
+pub mod models;
+/// Module containing the request parameters.
+pub mod parameters;
+/// Module containing utilities for testing code built on this crate.
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod test_util;
 /// Module containing trais.
 pub mod traits;
+/// Module containing the worker pool for queued requests.
+pub mod workers;
+/// Module containing updates which only send changed fields.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub mod writes;
 
 mod credentials;
+/// Module containing the normalization of strings in responses.
+pub mod normalization;
+/// Module containing information about the service pass.
+pub mod pass_info;
+/// Module containing the pool of clients of several tenants.
+pub mod pool;
+/// Module containing requests prepared without credentials.
+pub mod prepared;
+/// Module containing the process-wide registry of active registrations.
+pub mod registry;
+/// Module containing the request ID generation strategies.
+pub mod request_id;
+/// Module containing the log of sent requests.
+pub mod request_log;
+/// Module containing the request body types.
+pub mod requests;
 /// Module containing common response types.
 pub mod responses;
+/// Module containing the table of known function revisions.
+pub mod revisions;
+/// Module containing the description of entities as JSON schema and OpenAPI.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub mod schema;
+/// Module containing concurrent exports split into shards.
+pub mod sharding;
+#[cfg(feature = "opentelemetry")]
+mod trace_context;
+/// Module containing the validation of records before writing.
+pub mod validation;
 
 pub use app_hash::AppHash;
-pub use cursor::Cursor;
+pub use backoff::BackoffPolicy;
+pub use compat::Compat;
+pub use cursor::{
+    Cursor, CursorSnapshot, CursorState, CursorStats, CursoredResponse, Page, PaginationStrategy,
+};
 pub use futures;
+pub use parameters::Parameters;
 pub use reqwest::Method;
 pub use serde_json::Value;
+pub use sharding::ShardedExport;
+pub use traits::{WWResponse, WWValueExt};
 
 #[cfg(feature = "derive")]
 pub use async_trait::async_trait;
 #[cfg(feature = "derive")]
-pub use traits::WWSVCGetData;
+pub use traits::{WWSVCDeleteData, WWSVCGetData, WWSVCInsertData, WWSVCPutData, WWSVCRecord};
 #[cfg(feature = "derive")]
-pub use wwsvc_rs_derive::WWSVCGetData;
+pub use wwsvc_rs_derive::{
+    WWSVCDeleteData, WWSVCGetData, WWSVCInsertData, WWSVCPutData, WWSVCRecord,
+};
 
 /// Module containing the client.
 pub mod client;
 pub use client::states::*;
-pub use client::WebwareClient;
-pub use credentials::Credentials;
-pub use error::WWSVCError;
+pub use client::{IntoWebwareUrl, WebwareClient};
+pub use credentials::{Credentials, CredentialsProvider, EnvCredentials, FileCredentials};
+pub use error::{ErrorCategory, WWSVCError};
 pub use reqwest::Response;
 
 /// Result type for the wwsvc-rs crate.
@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+use crate::logging;
+use crate::Parameters;
+
+/// Default of `result_max_lines`, at which responses of unfiltered `.GET` functions are cut off
+/// silently.
+pub const DEFAULT_RESULT_MAX_LINES: u32 = 1000;
+
+/// Parameters which don't restrict the returned rows.
+const NON_FILTERING_PARAMETERS: &[&str] = &["FELDER", "SORTIERUNG"];
+
+/// A common misconfiguration, which is detected at runtime and reported as a warning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lint {
+    /// A `.GET` function is called without filter and without cursor, so the response is cut off
+    /// after `max_lines` rows without any indication.
+    UnpaginatedGet {
+        /// The name of the function.
+        function: String,
+        /// The maximum amount of rows in the response.
+        max_lines: u32,
+    },
+    /// Invalid TLS certificates are accepted in a release build.
+    InsecureTls,
+    /// A cursor was created, but no request was sent with it.
+    UnusedCursor,
+}
+
+impl Lint {
+    /// Returns the lint for calling `function` with `parameters` and `max_lines` rows per
+    /// response without a cursor, if the response is likely to be truncated.
+    pub fn unpaginated_get(
+        function: &str,
+        parameters: &Parameters,
+        max_lines: u32,
+    ) -> Option<Self> {
+        let is_get = function
+            .rsplit_once('.')
+            .is_some_and(|(_, method)| method.eq_ignore_ascii_case("GET"));
+        let filtered = parameters.iter().any(|parameter| {
+            !NON_FILTERING_PARAMETERS
+                .iter()
+                .any(|name| parameter.name.eq_ignore_ascii_case(name))
+        });
+        (is_get && !filtered && max_lines == DEFAULT_RESULT_MAX_LINES).then(|| {
+            Lint::UnpaginatedGet {
+                function: function.to_string(),
+                max_lines,
+            }
+        })
+    }
+
+    /// Returns the lint for accepting invalid certificates, if this is a release build.
+    pub fn insecure_tls(allow_insecure: bool) -> Option<Self> {
+        (allow_insecure && !cfg!(debug_assertions)).then_some(Lint::InsecureTls)
+    }
+}
+
+impl Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Lint::UnpaginatedGet {
+                function,
+                max_lines,
+            } => write!(
+                f,
+                "{} is called without filter and without cursor, results beyond {} rows are dropped; use a cursor or set result_max_lines",
+                function, max_lines
+            ),
+            Lint::InsecureTls => f.write_str(
+                "invalid TLS certificates are accepted in a release build; disable allow_insecure",
+            ),
+            Lint::UnusedCursor => {
+                f.write_str("a cursor was created, but no request was sent with it")
+            }
+        }
+    }
+}
+
+/// Emits a warning for the lint, if any.
+pub(crate) fn warn(lint: Option<Lint>) {
+    if let Some(lint) = lint {
+        logging::warn_event!(lint = %lint, "possible misconfiguration");
+    }
+}
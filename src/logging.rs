@@ -0,0 +1,47 @@
+//! Events emitted through `tracing` with the `tracing` feature, and discarded without it.
+//!
+//! The macros accept the subset of the `tracing` syntax used in this crate: fields given as
+//! `name`, `name = value` or `name = %value`, followed by the message.
+
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($level:ident, $($args:tt)*) => {
+        tracing::$level!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! event {
+    (@use $field:ident) => {
+        let _ = &$field;
+    };
+    (@use $field:ident $value:expr) => {
+        let _ = &$value;
+    };
+    ($level:ident, $($field:ident $(= $(%)? $value:expr)?,)* $message:literal) => {{
+        // references the fields without evaluating them
+        let _ = || {
+            $($crate::logging::event!(@use $field $($value)?);)*
+        };
+    }};
+}
+
+macro_rules! debug_event {
+    ($($args:tt)*) => {
+        $crate::logging::event!(debug, $($args)*)
+    };
+}
+
+macro_rules! info_event {
+    ($($args:tt)*) => {
+        $crate::logging::event!(info, $($args)*)
+    };
+}
+
+macro_rules! warn_event {
+    ($($args:tt)*) => {
+        $crate::logging::event!(warn, $($args)*)
+    };
+}
+
+pub(crate) use {debug_event, event, info_event, warn_event};
@@ -1,5 +1,9 @@
 /// Generates a response struct with a container struct.
 ///
+/// Prefixing the arguments with `strict` generates structs which reject unknown members, e.g. a
+/// container which has been renamed by an update of WEBWARE. Intended for contract tests, since
+/// responses usually contain members which aren't needed.
+///
 /// ## Example
 ///
 /// Generating a response struct for an IDB GET request:
@@ -8,28 +12,107 @@
 /// use wwsvc_rs::generate_get_response;
 ///
 /// generate_get_response!(TrackingResponse, "IDBID0026LISTE", TrackingListe, "IDBID0026");
+/// generate_get_response!(strict StrictTrackingResponse, "IDBID0026LISTE", StrictTrackingListe, "IDBID0026");
 /// ```
 #[macro_export]
 macro_rules! generate_get_response {
     ($name:ident, $container_name:literal, $container_type:ident, $list_name:literal) => {
+        $crate::generate_get_response!(@generate [] $name, $container_name, $container_type, $list_name);
+    };
+    (strict $name:ident, $container_name:literal, $container_type:ident, $list_name:literal) => {
+        $crate::generate_get_response!(
+            @generate [#[serde(deny_unknown_fields)]] $name, $container_name, $container_type, $list_name
+        );
+    };
+    (@generate [$($attribute:tt)*] $name:ident, $container_name:literal, $container_type:ident, $list_name:literal) => {
         /// Generic response struct for a WWSVC GET request.
-        #[derive(serde::Deserialize, Clone)]
+        #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+        $($attribute)*
         pub struct $name<T> {
             /// The COMRESULT of the request. Contains information about the status of the request.
             #[serde(rename = "COMRESULT")]
             pub com_result: $crate::responses::ComResult,
             /// The container struct for the list of items.
-            #[serde(rename = $container_name)]
+            ///
+            /// Some server versions omit the container if no rows match, in which case it is empty.
+            #[serde(rename = $container_name, default = "Default::default")]
             pub container: $container_type<T>,
         }
 
         /// Container struct for the list of items.
-        #[derive(serde::Deserialize, Clone)]
+        #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+        $($attribute)*
         pub struct $container_type<T> {
             /// The list of items.
             #[serde(rename = $list_name)]
             pub list: Option<Vec<T>>,
         }
+
+        impl<T> Default for $container_type<T> {
+            fn default() -> Self {
+                Self { list: None }
+            }
+        }
+
+        impl<T> $crate::traits::WWResponse for $name<T> {
+            type Item = T;
+
+            fn com_result(&self) -> Option<$crate::responses::ComResult> {
+                Some(self.com_result.clone())
+            }
+
+            fn items(&self) -> &[T] {
+                self.container.list.as_deref().unwrap_or(&[])
+            }
+
+            fn into_items(self) -> Vec<T> {
+                self.container.list.unwrap_or_default()
+            }
+        }
+    };
+}
+
+/// Generates a module with the field codes of an IDB table as constants and a `table()` function
+/// returning its `IdbTable`.
+///
+/// Each field is given as `NAME = number, length;` and becomes a constant like `"IDB_1_20"`.
+///
+/// ## Example
+///
+/// ```
+/// use wwsvc_rs::idb_fields;
+///
+/// idb_fields! {
+///     /// Fields of the tracking table.
+///     pub mod tracking = 26 {
+///         TRACKING_NUMBER = 1, 20;
+///         CARRIER = 2, 40;
+///     }
+/// }
+///
+/// assert_eq!(tracking::TRACKING_NUMBER, "IDB_1_20");
+/// assert_eq!(tracking::table().function("GET"), "IDBID0026.GET");
+/// ```
+#[macro_export]
+macro_rules! idb_fields {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $module:ident = $id:literal {
+            $($(#[$field_meta:meta])* $field:ident = $number:literal, $length:literal;)*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $module {
+            /// Returns the IDB table of the fields.
+            pub fn table() -> $crate::idb::IdbTable {
+                $crate::idb::IdbTable::new($id)
+            }
+
+            $(
+                $(#[$field_meta])*
+                pub const $field: &str = concat!("IDB_", stringify!($number), "_", stringify!($length));
+            )*
+        }
     };
 }
 
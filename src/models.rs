@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::WWClientResult;
+
+/// Field access shared by all models.
+///
+/// Only fields whose codes are stable across WEBWARE versions are mapped to named struct
+/// fields. All other fields returned by the server are kept in `fields` and can be read with
+/// [`Model::field`].
+pub trait Model {
+    /// Returns all fields which aren't mapped to a named struct field.
+    fn fields(&self) -> &BTreeMap<String, serde_json::Value>;
+
+    /// Deserializes the field with the given code (e.g. `ART_1_25`) into `T`.
+    ///
+    /// Returns `Ok(None)` if the field hasn't been returned by the server.
+    fn field<T: DeserializeOwned>(&self, code: &str) -> WWClientResult<Option<T>> {
+        self.fields()
+            .get(code)
+            .map(|value| Ok(T::deserialize(value)?))
+            .transpose()
+    }
+}
+
+macro_rules! impl_model {
+    ($name:ident) => {
+        impl Model for $name {
+            fn fields(&self) -> &BTreeMap<String, serde_json::Value> {
+                &self.fields
+            }
+        }
+    };
+}
+
+/// An article, as returned by `ARTIKEL.GET`.
+///
+/// Use with [`ArtikelGetResponse`](crate::responses::ArtikelGetResponse).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Artikel {
+    /// The article number.
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    /// All other fields of the article.
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+impl_model!(Artikel);
+
+/// An address, as returned by `ADRESSE.GET`.
+///
+/// Use with [`AdresseGetResponse`](crate::responses::AdresseGetResponse).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Adresse {
+    /// All fields of the address.
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+impl_model!(Adresse);
+
+/// A document (e.g. an order or invoice), as returned by `BELEG.GET`.
+///
+/// Use with [`BelegGetResponse`](crate::responses::BelegGetResponse).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Beleg {
+    /// All fields of the document.
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+impl_model!(Beleg);
+
+/// A warehouse, as returned by `LAGER.GET`.
+///
+/// Use with [`LagerGetResponse`](crate::responses::LagerGetResponse).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Lager {
+    /// All fields of the warehouse.
+    #[serde(flatten)]
+    pub fields: BTreeMap<String, serde_json::Value>,
+}
+impl_model!(Lager);
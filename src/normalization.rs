@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use bytes::Bytes;
+use encoding_rs::WINDOWS_1252;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+/// Normalization of the strings in response bodies, applied before they are deserialized.
+///
+/// Some gateway configurations decode the UTF-8 responses of the WEBSERVICES as WINDOWS-1252,
+/// so e.g. `Müller` arrives as `MÃ¼ller`, and some installations return decomposed umlauts.
+/// Both are disabled by default.
+///
+/// ## Example
+///
+/// ```
+/// use wwsvc_rs::normalization::TextNormalization;
+///
+/// let normalization = TextNormalization::all();
+/// assert_eq!(normalization.apply("MÃ¼ller, StraÃŸe"), "Müller, Straße");
+/// assert_eq!(normalization.apply("Mu\u{308}ller"), "Müller");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TextNormalization {
+    /// Composes characters to Unicode normalization form C, e.g. `u` followed by a combining
+    /// diaeresis to `ü`.
+    pub nfc: bool,
+    /// Replaces UTF-8 sequences which have been decoded as WINDOWS-1252, e.g. `Ã¤` by `ä`.
+    pub repair_mojibake: bool,
+}
+
+impl TextNormalization {
+    /// Leaves the strings unchanged. This is the default.
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Repairs mojibake and composes characters to NFC.
+    pub fn all() -> Self {
+        Self {
+            nfc: true,
+            repair_mojibake: true,
+        }
+    }
+
+    /// Returns whether any normalization is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.nfc || self.repair_mojibake
+    }
+
+    /// Normalizes a string. Mojibake is repaired before composing characters.
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        if self.repair_mojibake {
+            if let Cow::Owned(repaired) = repair_mojibake(&text) {
+                text = Cow::Owned(repaired);
+            }
+        }
+        if self.nfc && is_nfc_quick(text.chars()) != IsNormalized::Yes {
+            text = Cow::Owned(text.nfc().collect());
+        }
+        text
+    }
+
+    /// Normalizes a response body. Bodies which aren't valid UTF-8 are returned unchanged.
+    pub(crate) fn apply_to_body(&self, body: Bytes) -> Bytes {
+        if !self.is_enabled() {
+            return body;
+        }
+        match std::str::from_utf8(&body).map(|text| self.apply(text)) {
+            Ok(Cow::Owned(text)) => Bytes::from(text),
+            _ => body,
+        }
+    }
+}
+
+/// Replaces sequences of characters which are the WINDOWS-1252 decoding of a multi-byte UTF-8
+/// character by that character.
+fn repair_mojibake(text: &str) -> Cow<'_, str> {
+    // lead bytes of multi-byte UTF-8 sequences, decoded as WINDOWS-1252
+    let is_lead = |c: char| ('\u{c2}'..='\u{f4}').contains(&c);
+    if !text.chars().any(is_lead) {
+        return Cow::Borrowed(text);
+    }
+
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut repaired = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if is_lead(c) {
+            let length = match c as u32 {
+                0xc2..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            if let Some(decoded) = chars.get(index..index + length).and_then(decode_sequence) {
+                repaired.push(decoded);
+                index += length;
+                continue;
+            }
+        }
+        repaired.push(c);
+        index += 1;
+    }
+
+    if repaired == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(repaired)
+    }
+}
+
+/// Encodes the characters as WINDOWS-1252 and decodes the bytes as a single UTF-8 character.
+fn decode_sequence(chars: &[char]) -> Option<char> {
+    let sequence = chars.iter().collect::<String>();
+    let (bytes, _, unmappable) = WINDOWS_1252.encode(&sequence);
+    if unmappable || bytes.len() != chars.len() {
+        return None;
+    }
+    let mut decoded = std::str::from_utf8(&bytes).ok()?.chars();
+    match (decoded.next(), decoded.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
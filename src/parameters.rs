@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::binary;
+
+/// A single parameter of a WEBSERVICES function call, serialized as `PNAME`/`PCONTENT`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parameter {
+    /// The name of the parameter (`PNAME`).
+    pub name: String,
+    /// The content of the parameter (`PCONTENT`).
+    pub content: String,
+    /// Whether the content is base64 encoded binary data. The flag is known to the client only,
+    /// where it keeps the content out of `Display` output; the server receives the plain base64
+    /// string in `PCONTENT`.
+    pub binary: bool,
+}
+
+/// Number of characters up to which contents are displayed in full by the `Display` impls of
+/// parameters.
+const DISPLAY_CONTENT_LENGTH: usize = 64;
+
+/// Writes a parameter as `NAME=content`, shortening long contents and omitting binary ones.
+pub(crate) fn fmt_parameter(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    content: &str,
+    binary: bool,
+) -> std::fmt::Result {
+    if binary {
+        return write!(f, "{}=<{} bytes base64>", name, content.len());
+    }
+    match content.char_indices().nth(DISPLAY_CONTENT_LENGTH) {
+        Some((end, _)) => write!(f, "{}={}… ({} bytes)", name, &content[..end], content.len()),
+        None => write!(f, "{}={}", name, content),
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_parameter(f, &self.name, &self.content, self.binary)
+    }
+}
+
+/// Decides which content is kept by `Parameters::merge()` if both sides contain a parameter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MergePrecedence {
+    /// Existing parameters are kept, only new ones are added.
+    KeepExisting,
+    /// Existing parameters are replaced by the merged ones.
+    #[default]
+    Overwrite,
+}
+
+/// Parameters of a WEBSERVICES function call.
+///
+/// Parameters keep their insertion order. Setting a parameter that already exists replaces its
+/// content in place.
+///
+/// Clones share the parameters until one of them is changed, so a parameter set with long
+/// contents, e.g. a `FELDER` list, can be reused for many requests without copying it.
+///
+/// ## Example
+///
+/// ```
+/// use wwsvc_rs::Parameters;
+///
+/// let parameters = Parameters::new()
+///     .param("ARTNR", "Artikel19Prozent")
+///     .param("FELDER", "ART_1_25");
+/// assert_eq!(parameters.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parameters {
+    // shared between clones until one of them is changed, so reusing parameters with long
+    // contents like `FELDER` for every page of a cursor doesn't copy them
+    params: Arc<Vec<Parameter>>,
+}
+
+impl Parameters {
+    /// Creates an empty set of parameters.
+    pub fn new() -> Parameters {
+        Parameters::default()
+    }
+
+    /// Adds a parameter and returns the parameters for chaining.
+    pub fn param(mut self, name: &str, content: impl ToString) -> Parameters {
+        self.insert(name, content);
+        self
+    }
+
+    /// Adds a parameter containing binary data, which is sent base64 encoded.
+    ///
+    /// Nothing marks the parameter as binary in the request, so the function has to expect base64
+    /// in this parameter. The parameter is only flagged as binary on the client, see
+    /// `Parameter::binary`.
+    pub fn param_bytes(mut self, name: &str, content: &[u8]) -> Parameters {
+        self.insert_bytes(name, content);
+        self
+    }
+
+    /// Creates parameters from the members of a struct or map, named by their serde names.
+    ///
+    /// Strings are sent unchanged, numbers and booleans formatted, `null` members are omitted and
+    /// nested arrays and objects are sent as JSON. The parameters are sorted by name.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use wwsvc_rs::Parameters;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Article {
+    ///     #[serde(rename = "ARTNR")]
+    ///     number: String,
+    ///     #[serde(rename = "ART_1_80")]
+    ///     stock: u32,
+    ///     #[serde(rename = "ART_2_10")]
+    ///     comment: Option<String>,
+    /// }
+    ///
+    /// let parameters = Parameters::from_serialize(&Article {
+    ///     number: "Artikel19Prozent".to_string(),
+    ///     stock: 3,
+    ///     comment: None,
+    /// })
+    /// .unwrap();
+    /// assert_eq!(parameters.to_string(), "ARTNR=Artikel19Prozent, ART_1_80=3");
+    /// ```
+    pub fn from_serialize<T: serde::Serialize + ?Sized>(
+        value: &T,
+    ) -> Result<Parameters, serde_json::Error> {
+        let serde_json::Value::Object(members) = serde_json::to_value(value)? else {
+            return Err(serde::ser::Error::custom(
+                "parameters can only be created from structs and maps",
+            ));
+        };
+        let mut parameters = Parameters::new();
+        for (name, value) in members {
+            match value {
+                serde_json::Value::Null => {}
+                serde_json::Value::String(content) => parameters.insert(&name, content),
+                other => parameters.insert(&name, other),
+            }
+        }
+        Ok(parameters)
+    }
+
+    /// Splits `keys` into chunks of at most `chunk_size` keys and returns a copy of these
+    /// parameters per chunk, with the comma separated keys of the chunk in the parameter `name`.
+    ///
+    /// The WEBSERVICES reject overly long conditions, so long key lists have to be requested in
+    /// several calls, e.g. with `WebwareClient::request_chunks()`. Returns no parameters if
+    /// `keys` is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use wwsvc_rs::Parameters;
+    ///
+    /// let chunks = Parameters::new()
+    ///     .param("FELDER", "ART_1_25")
+    ///     .param_in_chunked("ARTNR", ["A1", "A2", "A3"], 2);
+    /// assert_eq!(chunks.len(), 2);
+    /// assert_eq!(chunks[0].get("ARTNR"), Some("A1,A2"));
+    /// assert_eq!(chunks[1].get("ARTNR"), Some("A3"));
+    /// assert_eq!(chunks[1].get("FELDER"), Some("ART_1_25"));
+    /// ```
+    pub fn param_in_chunked<K: ToString>(
+        &self,
+        name: &str,
+        keys: impl IntoIterator<Item = K>,
+        chunk_size: usize,
+    ) -> Vec<Parameters> {
+        let keys = keys
+            .into_iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>();
+        keys.chunks(chunk_size.max(1))
+            .map(|chunk| self.clone().param(name, chunk.join(",")))
+            .collect()
+    }
+
+    /// Sets a parameter, replacing the content of an existing parameter with the same name.
+    pub fn insert(&mut self, name: &str, content: impl ToString) {
+        self.set(name, content.to_string(), false);
+    }
+
+    /// Sets a parameter containing binary data, which is sent base64 encoded, see `param_bytes()`.
+    pub fn insert_bytes(&mut self, name: &str, content: &[u8]) {
+        self.set(name, binary::encode(content), true);
+    }
+
+    /// Returns whether the parameter with the given name has been added as binary data. This is
+    /// known to the client only.
+    pub fn is_binary(&self, name: &str) -> bool {
+        self.params.iter().any(|p| p.name == name && p.binary)
+    }
+
+    /// Returns the amount of parameters.
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns whether there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Returns an iterator over the parameters in insertion order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Parameter> {
+        self.params.iter()
+    }
+
+    /// Returns the content of the parameter with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.content.as_str())
+    }
+
+    /// Returns whether a parameter with the given name exists.
+    pub fn contains(&self, name: &str) -> bool {
+        self.params.iter().any(|p| p.name == name)
+    }
+
+    /// Removes the parameter with the given name and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<Parameter> {
+        let index = self.params.iter().position(|p| p.name == name)?;
+        Some(Arc::make_mut(&mut self.params).remove(index))
+    }
+
+    /// Merges the parameters of `other` into these parameters. New parameters are appended,
+    /// `precedence` decides about parameters contained in both.
+    pub fn merge(&mut self, other: &Parameters, precedence: MergePrecedence) {
+        for param in other.iter() {
+            if precedence == MergePrecedence::KeepExisting && self.contains(&param.name) {
+                continue;
+            }
+            self.set(&param.name, param.content.clone(), param.binary);
+        }
+    }
+
+    fn set(&mut self, name: &str, content: String, binary: bool) {
+        let params = Arc::make_mut(&mut self.params);
+        match params.iter_mut().find(|p| p.name == name) {
+            Some(existing) => {
+                existing.content = content;
+                existing.binary = binary;
+            }
+            None => params.push(Parameter {
+                name: name.to_string(),
+                content,
+                binary,
+            }),
+        }
+    }
+}
+
+/// Displays the parameters as `NAME=content`, separated by commas. Long contents are shortened
+/// and binary contents omitted.
+impl Display for Parameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, parameter) in self.params.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            Display::fmt(parameter, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for Parameters {
+    fn from(params: [(&str, &str); N]) -> Self {
+        params
+            .into_iter()
+            .fold(Parameters::new(), |acc, (name, content)| {
+                acc.param(name, content)
+            })
+    }
+}
+
+impl From<HashMap<&str, &str>> for Parameters {
+    fn from(params: HashMap<&str, &str>) -> Self {
+        params
+            .into_iter()
+            .fold(Parameters::new(), |acc, (name, content)| {
+                acc.param(name, content)
+            })
+    }
+}
+
+impl From<HashMap<String, String>> for Parameters {
+    fn from(params: HashMap<String, String>) -> Self {
+        params
+            .into_iter()
+            .fold(Parameters::new(), |acc, (name, content)| {
+                acc.param(&name, content)
+            })
+    }
+}
+
+impl IntoIterator for Parameters {
+    type Item = Parameter;
+    type IntoIter = std::vec::IntoIter<Parameter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Arc::try_unwrap(self.params)
+            .unwrap_or_else(|shared| shared.as_ref().clone())
+            .into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Parameters {
+    type Item = &'a Parameter;
+    type IntoIter = std::slice::Iter<'a, Parameter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Parameter> for Parameters {
+    fn from_iter<I: IntoIterator<Item = Parameter>>(iter: I) -> Self {
+        let mut parameters = Parameters::new();
+        for param in iter {
+            parameters.set(&param.name, param.content, param.binary);
+        }
+        parameters
+    }
+}
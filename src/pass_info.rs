@@ -0,0 +1,103 @@
+use serde_json::Value;
+
+use crate::client::states::Ready;
+use crate::responses::ComResult;
+use crate::{Parameters, WWClientResult, WWValueExt, WebwareClient};
+
+/// Name of the function returning information about the current service pass.
+///
+/// Installations exposing it under another name can use `service_pass_info_with()`.
+pub const SERVICE_PASS_INFO_FUNCTION: &str = "SERVICEPASS.INFO";
+
+const EXPIRES_KEYS: &[&str] = &["GUELTIGBIS", "GUELTIG_BIS", "ABLAUFDATUM", "EXPIRES"];
+const USER_KEYS: &[&str] = &["BENUTZER", "USER", "USERNAME"];
+const MODULES_KEYS: &[&str] = &["MODULE", "MODULES", "LIZENZEN"];
+
+/// Information about the current service pass, e.g. for monitoring when it expires.
+///
+/// The members of the response differ between WEBWARE versions, so the commonly used names are
+/// accepted. The whole response is kept in `raw`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServicePassStatus {
+    /// The COMRESULT of the request.
+    pub com_result: ComResult,
+    /// The expiry of the service pass, as returned by the server.
+    pub expires_at: Option<String>,
+    /// The user the service pass is assigned to.
+    pub user: Option<String>,
+    /// The modules the service pass is allowed to use.
+    pub modules: Vec<String>,
+    /// The raw response.
+    pub raw: Value,
+}
+
+impl ServicePassStatus {
+    /// Reads the service pass information from a response.
+    pub fn from_response(response: Value) -> WWClientResult<Self> {
+        let com_result = WWValueExt::com_result(&response)?;
+        let info = response
+            .as_object()
+            .into_iter()
+            .flat_map(|object| object.iter())
+            .filter(|(key, _)| key.as_str() != "COMRESULT")
+            .find_map(|(_, value)| value.as_object())
+            .or_else(|| response.as_object());
+
+        let lookup = |keys: &[&str]| {
+            info.and_then(|info| {
+                keys.iter()
+                    .find_map(|key| info.get(*key))
+                    .filter(|value| !value.is_null())
+                    .cloned()
+            })
+        };
+        let as_string = |value: Value| match value {
+            Value::String(value) => value,
+            value => value.to_string(),
+        };
+
+        let modules = match lookup(MODULES_KEYS) {
+            Some(Value::Array(modules)) => modules.into_iter().map(as_string).collect(),
+            Some(Value::String(modules)) => modules
+                .split(',')
+                .map(str::trim)
+                .filter(|module| !module.is_empty())
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            com_result,
+            expires_at: lookup(EXPIRES_KEYS).map(as_string),
+            user: lookup(USER_KEYS).map(as_string),
+            modules,
+            raw: response,
+        })
+    }
+
+    /// Returns whether the server accepted the service pass.
+    pub fn is_valid(&self) -> bool {
+        (200..300).contains(&self.com_result.status)
+    }
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Requests information about the current service pass using `SERVICE_PASS_INFO_FUNCTION`.
+    pub async fn service_pass_info(&mut self) -> WWClientResult<ServicePassStatus> {
+        self.service_pass_info_with(SERVICE_PASS_INFO_FUNCTION, 1)
+            .await
+    }
+
+    /// Requests information about the current service pass using the given function.
+    pub async fn service_pass_info_with(
+        &mut self,
+        function: &str,
+        version: u32,
+    ) -> WWClientResult<ServicePassStatus> {
+        let response = self
+            .request(None, function, version, Parameters::new(), None)
+            .await?;
+        ServicePassStatus::from_response(response)
+    }
+}
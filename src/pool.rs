@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::client::states::{Registered, Unregistered};
+use crate::{logging, WWClientResult, WWSVCError, WebwareClient};
+
+/// Registered clients of several tenants, e.g. one per WEBWARE instance of a multi-tenant
+/// service.
+///
+/// Clients are returned as clones, so a pool can be shared between tasks, e.g. in an `Arc`.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use wwsvc_rs::{pool::ClientPool, WebwareClient};
+///
+/// # async fn example(client: WebwareClient<wwsvc_rs::Unregistered>) {
+/// let pool = ClientPool::new();
+/// pool.register("tenant-a", client).await.unwrap();
+///
+/// let mut client = pool.get("tenant-a").unwrap();
+/// // ...
+///
+/// // e.g. in a SIGTERM handler
+/// let report = pool.shutdown(Duration::from_secs(5)).await;
+/// for (tenant, error) in &report.failed {
+///     eprintln!("could not deregister {}: {}", tenant, error);
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ClientPool {
+    clients: Mutex<HashMap<String, WebwareClient<Registered>>>,
+}
+
+/// Result of `ClientPool::shutdown()`.
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    /// The tenants whose clients have been deregistered.
+    pub deregistered: Vec<String>,
+    /// The tenants whose clients could not be deregistered, with the reason.
+    pub failed: Vec<(String, WWSVCError)>,
+}
+
+impl ShutdownReport {
+    /// Returns whether all clients have been deregistered.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl ClientPool {
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the registered client of a tenant and returns the client it replaces, if any.
+    pub fn insert(
+        &self,
+        tenant: &str,
+        client: WebwareClient<Registered>,
+    ) -> Option<WebwareClient<Registered>> {
+        self.lock().insert(tenant.to_string(), client)
+    }
+
+    /// Registers the client of a tenant and adds it to the pool.
+    ///
+    /// A client the tenant had before is deregistered. Only an error registering the new client
+    /// is returned; if deregistering the previous one fails, the new client stays in the pool and
+    /// the failure is logged.
+    pub async fn register(
+        &self,
+        tenant: &str,
+        client: WebwareClient<Unregistered>,
+    ) -> WWClientResult<()> {
+        let client = client.register().await?;
+        if let Some(previous) = self.insert(tenant, client) {
+            if let Err(e) = previous.deregister().await {
+                logging::warn_event!(
+                    tenant = %tenant,
+                    error = %e,
+                    "deregistering the previous client failed"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a clone of the client of a tenant.
+    pub fn get(&self, tenant: &str) -> Option<WebwareClient<Registered>> {
+        self.lock().get(tenant).cloned()
+    }
+
+    /// Removes the client of a tenant from the pool, without deregistering it.
+    pub fn remove(&self, tenant: &str) -> Option<WebwareClient<Registered>> {
+        self.lock().remove(tenant)
+    }
+
+    /// Returns the tenants in the pool, sorted by name.
+    pub fn tenants(&self) -> Vec<String> {
+        let mut tenants = self.lock().keys().cloned().collect::<Vec<_>>();
+        tenants.sort();
+        tenants
+    }
+
+    /// Returns the number of clients in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes all clients from the pool and deregisters them concurrently.
+    ///
+    /// Each client has `timeout` to deregister, so the shutdown takes at most about `timeout`.
+    /// Clients which can't reach the server or time out are reported as failed; their service
+    /// passes are invalidated by the server after a while.
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownReport {
+        let clients = self.lock().drain().collect::<Vec<_>>();
+        let results =
+            futures::future::join_all(clients.into_iter().map(|(tenant, mut client)| async move {
+                let result = tokio::time::timeout(timeout, client.send_deregister())
+                    .await
+                    .map_err(|_| WWSVCError::Timeout(timeout))
+                    .and_then(|result| Ok(result??));
+                (tenant, result)
+            }))
+            .await;
+
+        let mut report = ShutdownReport::default();
+        for (tenant, result) in results {
+            match result {
+                Ok(()) => report.deregistered.push(tenant),
+                Err(e) => report.failed.push((tenant, e)),
+            }
+        }
+        report.deregistered.sort();
+        report.failed.sort_by(|a, b| a.0.cmp(&b.0));
+        report
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, WebwareClient<Registered>>> {
+        self.clients
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
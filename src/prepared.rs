@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, HashMap};
+
+use reqwest::header::HeaderMap;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::client::states::Ready;
+use crate::parameters::MergePrecedence;
+use crate::requests::{ExecJsonRequest, ServiceFunction, ServicePassInfo};
+use crate::{Parameters, WWClientResult, WWSVCError, WebwareClient};
+
+/// Function call which has been prepared without credentials, to be signed and executed later,
+/// possibly by another service holding the credentials.
+///
+/// Prepared requests can be serialized to be passed between services.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use wwsvc_rs::{Parameters, WebwareClient};
+///
+/// # async fn example(client: WebwareClient, mut registered: WebwareClient<wwsvc_rs::Registered>) {
+/// let prepared = client.prepare("ARTIKEL.GET", 1, Parameters::new().param("ARTNR", "Artikel19Prozent"));
+/// println!("{}", serde_json::to_string(&prepared.unsigned_body()).unwrap());
+///
+/// // in the service holding the credentials
+/// let response = registered.execute_prepared(None, &prepared).await.unwrap();
+/// # }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreparedRequest {
+    /// The function call.
+    pub function: ServiceFunction,
+}
+
+impl PreparedRequest {
+    /// Returns the parameters of the function call.
+    pub fn parameters(&self) -> Parameters {
+        self.function
+            .parameters
+            .iter()
+            .fold(Parameters::new(), |acc, parameter| {
+                acc.param(&parameter.name, &parameter.content)
+            })
+    }
+
+    /// Returns the EXECJSON body with empty authentication, for inspection.
+    pub fn unsigned_body(&self) -> ExecJsonRequest {
+        ExecJsonRequest::new(self.function.clone(), ServicePassInfo::default())
+    }
+}
+
+/// Query with a fixed function, version and base parameters, which is built once and executed
+/// many times with varying key parameters, e.g. one article number per call.
+///
+/// Clones share the base parameters, so a query can be stored e.g. in a `static` or the state of
+/// a service without copying a long `FELDER` list per call.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use wwsvc_rs::prepared::PreparedQuery;
+/// use wwsvc_rs::{Parameters, WebwareClient};
+///
+/// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+/// let query = PreparedQuery::new(
+///     "ARTIKEL.GET",
+///     1,
+///     Parameters::new().param("FELDER", "ART_1_25,ART_2_80"),
+/// );
+/// for number in ["A1", "A2"] {
+///     let article: wwsvc_rs::Value = query
+///         .execute(&mut client, Parameters::new().param("ARTNR", number))
+///         .await
+///         .unwrap();
+///     println!("{}", article);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedQuery {
+    method: Option<reqwest::Method>,
+    function: String,
+    version: u32,
+    parameters: Parameters,
+}
+
+impl PreparedQuery {
+    /// Creates a query of the given function with the given base parameters.
+    pub fn new(function: &str, version: u32, parameters: Parameters) -> Self {
+        Self {
+            method: None,
+            function: function.to_string(),
+            version,
+            parameters,
+        }
+    }
+
+    /// Sets the HTTP method. Defaults to the `MethodPolicy` of the client.
+    pub fn method(mut self, method: reqwest::Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Returns the name of the function.
+    pub fn function(&self) -> &str {
+        &self.function
+    }
+
+    /// Returns the version of the function.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the base parameters combined with `keys`, which take precedence.
+    pub fn parameters_with(&self, keys: &Parameters) -> Parameters {
+        if keys.is_empty() {
+            return self.parameters.clone();
+        }
+        let mut parameters = self.parameters.clone();
+        parameters.merge(keys, MergePrecedence::Overwrite);
+        parameters
+    }
+
+    /// Executes the query with the given key parameters and deserializes the response to `T`.
+    pub async fn execute<T: DeserializeOwned>(
+        &self,
+        client: &mut WebwareClient<impl Ready>,
+        keys: Parameters,
+    ) -> WWClientResult<T> {
+        client
+            .request_generic(
+                self.method.clone(),
+                &self.function,
+                self.version,
+                self.parameters_with(&keys),
+                None,
+            )
+            .await
+    }
+}
+
+/// Request as it would be sent by the client, returned by `WebwareClient::preview_request()`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PreparedRequestInfo {
+    /// The target URL.
+    pub url: String,
+    /// The HTTP method.
+    pub method: String,
+    /// The headers, by lower case name.
+    pub headers: BTreeMap<String, String>,
+    /// The EXECJSON body.
+    pub body: ExecJsonRequest,
+}
+
+impl<State> WebwareClient<State> {
+    /// Prepares a function call without sending it. Doesn't require credentials.
+    pub fn prepare(&self, function: &str, version: u32, parameters: Parameters) -> PreparedRequest {
+        PreparedRequest {
+            function: ServiceFunction::new(function, version).parameters(&parameters),
+        }
+    }
+}
+
+impl<State: Ready> WebwareClient<State> {
+    /// Signs a prepared request with the credentials of this client, allocating a request ID.
+    ///
+    /// Returns the target URL, the headers and the body, which have to be sent as they are.
+    pub fn sign(
+        &mut self,
+        prepared: &PreparedRequest,
+    ) -> WWClientResult<(Url, HeaderMap, ExecJsonRequest)> {
+        self.prepare_request(
+            &prepared.function.function_name,
+            prepared.function.revision,
+            &prepared.parameters(),
+            None,
+        )
+    }
+
+    /// Returns the request the client would send for a function call, without sending it.
+    ///
+    /// The request is signed with the request ID the next request would get, but the client
+    /// isn't changed, i.e. the request ID isn't used up and an endpoint override stays in place.
+    /// The headers and body contain the credentials, so they shouldn't be logged as they are.
+    ///
+    /// If `method` is `None`, the method is chosen by the `MethodPolicy` of the client.
+    pub fn preview_request(
+        &self,
+        method: impl Into<Option<reqwest::Method>>,
+        function: &str,
+        version: u32,
+        parameters: &Parameters,
+        additional_headers: Option<HashMap<&str, &str>>,
+    ) -> WWClientResult<PreparedRequestInfo>
+    where
+        State: Clone,
+    {
+        let method = method
+            .into()
+            .unwrap_or_else(|| self.method_policy().method_for(function));
+        let (url, headers, body) =
+            self.clone()
+                .prepare_request(function, version, parameters, additional_headers)?;
+        let headers = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = value
+                    .to_str()
+                    .map_err(|_| WWSVCError::HeaderValueToStrError)?;
+                Ok((name.as_str().to_string(), value.to_string()))
+            })
+            .collect::<WWClientResult<_>>()?;
+
+        Ok(PreparedRequestInfo {
+            url: url.to_string(),
+            method: method.to_string(),
+            headers,
+            body,
+        })
+    }
+
+    /// Signs and sends a prepared request and returns the response.
+    ///
+    /// If `method` is `None`, the method is chosen by the `MethodPolicy` of the client.
+    pub async fn execute_prepared(
+        &mut self,
+        method: impl Into<Option<reqwest::Method>>,
+        prepared: &PreparedRequest,
+    ) -> WWClientResult<Response> {
+        self.request_as_response(
+            method,
+            &prepared.function.function_name,
+            prepared.function.revision,
+            prepared.parameters(),
+            None,
+        )
+        .await
+    }
+}
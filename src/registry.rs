@@ -0,0 +1,146 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::{WWClientResult, WWSVCError};
+
+/// Active service passes and the local limit of one vendor/application hash pair.
+#[derive(Default)]
+struct Registrations {
+    service_passes: HashSet<String>,
+    pending: usize,
+    limit: Option<usize>,
+}
+
+impl Registrations {
+    fn active(&self) -> usize {
+        self.service_passes.len() + self.pending
+    }
+}
+
+type Key = (String, String);
+
+fn registry() -> MutexGuard<'static, HashMap<Key, Registrations>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<Key, Registrations>>> = OnceLock::new();
+    REGISTRY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn key(vendor_hash: &str, app_hash: &str) -> Key {
+    (vendor_hash.to_string(), app_hash.to_string())
+}
+
+/// Limits the number of service passes the clients of this process may hold at the same time
+/// for the given vendor and application hash. `None` removes the limit, which is the default.
+///
+/// WEBWARE licenses limit the number of concurrent service passes, so a local limit prevents
+/// e.g. a test suite from exhausting them by registering without deregistering. Once the limit
+/// is reached, `WebwareClient::register()` returns `WWSVCError::RegistrationLimitExceeded`
+/// without sending a `REGISTER` request.
+///
+/// ## Example
+///
+/// ```rust
+/// use wwsvc_rs::registry::{active_registrations, set_registration_limit};
+///
+/// set_registration_limit("vendor", "app", Some(2));
+/// assert_eq!(active_registrations("vendor", "app"), 0);
+/// ```
+pub fn set_registration_limit(vendor_hash: &str, app_hash: &str, limit: Option<usize>) {
+    registry()
+        .entry(key(vendor_hash, app_hash))
+        .or_default()
+        .limit = limit;
+}
+
+/// Returns the number of service passes the clients of this process currently hold for the
+/// given vendor and application hash, including registrations in progress.
+///
+/// Service passes are counted from a successful `REGISTER` request until `deregister()`, so
+/// clients dropped without deregistering keep counting.
+pub fn active_registrations(vendor_hash: &str, app_hash: &str) -> usize {
+    registry()
+        .get(&key(vendor_hash, app_hash))
+        .map_or(0, Registrations::active)
+}
+
+/// Slot for a registration in progress, released when dropped unless it has been completed.
+pub(crate) struct Reservation {
+    key: Key,
+    completed: bool,
+}
+
+impl Reservation {
+    /// Reserves a slot for a new service pass, if the limit allows it.
+    pub(crate) fn new(vendor_hash: &str, app_hash: &str) -> WWClientResult<Reservation> {
+        let key = key(vendor_hash, app_hash);
+        let mut registry = registry();
+        let registrations = registry.entry(key.clone()).or_default();
+        if let Some(limit) = registrations.limit {
+            if registrations.active() >= limit {
+                return Err(WWSVCError::RegistrationLimitExceeded {
+                    vendor_hash: key.0,
+                    app_hash: key.1,
+                    limit,
+                });
+            }
+        }
+
+        registrations.pending += 1;
+        Ok(Reservation {
+            key,
+            completed: false,
+        })
+    }
+
+    /// Records the service pass returned by the `REGISTER` request.
+    pub(crate) fn complete(mut self, service_pass: &str) {
+        let mut registry = registry();
+        let registrations = registry.entry(self.key.clone()).or_default();
+        registrations.pending -= 1;
+        registrations
+            .service_passes
+            .insert(service_pass.to_string());
+        self.completed = true;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.completed {
+            if let Some(registrations) = registry().get_mut(&self.key) {
+                registrations.pending -= 1;
+            }
+        }
+    }
+}
+
+/// Service pass being deregistered, released when dropped, even if the `DEREGISTER` request
+/// fails or is cancelled.
+pub(crate) struct Release {
+    key: Key,
+    service_pass: String,
+}
+
+impl Release {
+    pub(crate) fn new(vendor_hash: &str, app_hash: &str, service_pass: &str) -> Release {
+        Release {
+            key: key(vendor_hash, app_hash),
+            service_pass: service_pass.to_string(),
+        }
+    }
+}
+
+impl Drop for Release {
+    fn drop(&mut self) {
+        release(&self.key.0, &self.key.1, &self.service_pass);
+    }
+}
+
+/// Removes a deregistered service pass.
+pub(crate) fn release(vendor_hash: &str, app_hash: &str, service_pass: &str) {
+    if let Some(registrations) = registry().get_mut(&key(vendor_hash, app_hash)) {
+        registrations.service_passes.remove(service_pass);
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt::{Debug, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::WWSVCError, WWClientResult};
+
+/// ID of a request, sent in the `WWSVC-REQID` header and as `REQUESTID` in the request body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum RequestId {
+    /// Sequential numeric ID, as used by default.
+    Number(u32),
+    /// Textual ID, e.g. a GUID.
+    Text(String),
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        RequestId::Number(0)
+    }
+}
+
+impl Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestId::Number(id) => write!(f, "{}", id),
+            RequestId::Text(id) => f.write_str(id),
+        }
+    }
+}
+
+impl From<u32> for RequestId {
+    fn from(id: u32) -> Self {
+        RequestId::Number(id)
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self {
+        RequestId::Text(id)
+    }
+}
+
+/// Strategy for generating the IDs of requests.
+///
+/// The client counts its requests; `generate()` is called once per request with the number of
+/// the request, starting at 1.
+pub trait RequestIdGenerator: Debug + Send + Sync {
+    /// Returns the ID of the request with the given number, or an error if no ID could be generated.
+    fn generate(&self, sequence: u32) -> WWClientResult<RequestId>;
+}
+
+/// Uses the number of the request as its ID. This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SequentialIds;
+
+impl RequestIdGenerator for SequentialIds {
+    fn generate(&self, sequence: u32) -> WWClientResult<RequestId> {
+        Ok(RequestId::Number(sequence))
+    }
+}
+
+/// Uses a random GUID as the ID of each request, for installations which expect GUID-style
+/// request IDs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidIds;
+
+impl RequestIdGenerator for UuidIds {
+    fn generate(&self, _sequence: u32) -> WWClientResult<RequestId> {
+        let mut bytes = [0u8; 16];
+        getrandom::getrandom(&mut bytes).map_err(WWSVCError::RequestIdError)?;
+        // version 4, variant RFC 4122
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex = bytes
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<String>();
+        Ok(RequestId::Text(format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )))
+    }
+}
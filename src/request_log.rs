@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use crate::request_id::RequestId;
+use crate::WWClientResult;
+
+/// Record of an EXECJSON request, as passed to a `RequestLogSink`.
+///
+/// The request ID and timestamp are the ones sent to the server, so entries can be matched with
+/// the request log of WEBWARE.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RequestLogEntry {
+    /// The ID of the request.
+    pub request_id: RequestId,
+    /// The full function name, e.g. `ARTIKEL.GET`.
+    pub function: String,
+    /// The timestamp the request has been signed with.
+    pub timestamp: String,
+    /// The HTTP status of the response, or `None` if no response has been received.
+    pub status: Option<u16>,
+}
+
+/// Destination of the request log of a client, set with `request_log()` on the builder.
+///
+/// `record()` is called after every EXECJSON request and shouldn't block, so sinks usually
+/// buffer the entries until `flush()` is called.
+pub trait RequestLogSink: std::fmt::Debug + Send + Sync {
+    /// Records a request.
+    fn record(&self, entry: RequestLogEntry);
+
+    /// Persists the recorded entries.
+    fn flush(&self) -> BoxFuture<'_, WWClientResult<()>>;
+}
+
+/// Appends the request log to a file, one JSON object per line.
+///
+/// Entries are kept in memory until `flush()` is called.
+#[derive(Debug)]
+pub struct FileRequestLog {
+    path: PathBuf,
+    pending: Mutex<Vec<RequestLogEntry>>,
+}
+
+impl FileRequestLog {
+    /// Appends the log to the file at `path`, which is created if it doesn't exist.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the entries which haven't been flushed yet.
+    pub fn pending(&self) -> Vec<RequestLogEntry> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl RequestLogSink for FileRequestLog {
+    fn record(&self, entry: RequestLogEntry) {
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(entry);
+    }
+
+    fn flush(&self) -> BoxFuture<'_, WWClientResult<()>> {
+        Box::pin(async move {
+            let entries =
+                std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+            if entries.is_empty() {
+                return Ok(());
+            }
+
+            let mut lines = Vec::new();
+            for entry in &entries {
+                serde_json::to_writer(&mut lines, entry)?;
+                lines.push(b'\n');
+            }
+            let written = async {
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .await?;
+                file.write_all(&lines).await?;
+                file.flush().await
+            }
+            .await;
+            if let Err(e) = written {
+                // keep the entries for the next attempt
+                let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+                pending.splice(0..0, entries);
+                return Err(e.into());
+            }
+            Ok(())
+        })
+    }
+}
+
+impl<T: RequestLogSink + ?Sized> RequestLogSink for std::sync::Arc<T> {
+    fn record(&self, entry: RequestLogEntry) {
+        (**self).record(entry)
+    }
+
+    fn flush(&self) -> BoxFuture<'_, WWClientResult<()>> {
+        (**self).flush()
+    }
+}
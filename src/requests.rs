@@ -0,0 +1,611 @@
+use std::fmt::Display;
+
+use reqwest::header::{HeaderValue, InvalidHeaderValue};
+use serde::{Deserialize, Serialize};
+
+use crate::events::is_mutation;
+use crate::logging;
+use crate::parameters::fmt_parameter;
+use crate::request_id::RequestId;
+use crate::{Parameters, WWClientResult, WWSVCError};
+
+/// Order in which the members of an EXECJSON request body are serialized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum BodyOrder {
+    /// Members are serialized in the order of the protocol documentation, e.g. `FUNCTIONNAME`,
+    /// `PARAMETER`, `REVISION`.
+    #[default]
+    Protocol,
+    /// Members are sorted alphabetically, like earlier versions of this crate did.
+    Alphabetical,
+}
+
+/// Serializes `value` with the members of all objects sorted by name.
+///
+/// The members are sorted explicitly, as `serde_json::Map` keeps the insertion order if the
+/// `preserve_order` feature of `serde_json` is enabled anywhere in the dependency graph.
+fn alphabetical_vec(value: &impl Serialize) -> WWClientResult<Vec<u8>> {
+    fn sort(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(members) => {
+                let mut members = members.into_iter().collect::<Vec<_>>();
+                members.sort_by(|(a, _), (b, _)| a.cmp(b));
+                serde_json::Value::Object(
+                    members
+                        .into_iter()
+                        .map(|(name, value)| (name, sort(value)))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(values) => {
+                serde_json::Value::Array(values.into_iter().map(sort).collect())
+            }
+            other => other,
+        }
+    }
+
+    Ok(serde_json::to_vec(&sort(serde_json::to_value(value)?))?)
+}
+
+/// HTTP methods used for EXECJSON requests which don't specify a method explicitly.
+///
+/// The WEBSERVICES accept EXECJSON requests with several HTTP methods. Some gateways block `PUT`,
+/// in which case e.g. `POST` can be used for all requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodPolicy {
+    /// Method for reading functions, e.g. functions ending in `.GET`.
+    pub get: reqwest::Method,
+    /// Method for functions which mutate data, see `events::is_mutation()`.
+    pub mutate: reqwest::Method,
+}
+
+impl Default for MethodPolicy {
+    fn default() -> Self {
+        Self {
+            get: reqwest::Method::PUT,
+            mutate: reqwest::Method::PUT,
+        }
+    }
+}
+
+impl MethodPolicy {
+    /// Uses `method` for all requests.
+    pub fn all(method: reqwest::Method) -> Self {
+        Self {
+            get: method.clone(),
+            mutate: method,
+        }
+    }
+
+    /// Returns the method for the function with the given name.
+    pub fn method_for(&self, function: &str) -> reqwest::Method {
+        if is_mutation(function) {
+            self.mutate.clone()
+        } else {
+            self.get.clone()
+        }
+    }
+}
+
+/// Functions which don't end in `.GET` but only read data, and which a read-only client may call
+/// nevertheless.
+pub const READ_ONLY_FUNCTIONS: &[&str] = &[crate::pass_info::SERVICE_PASS_INFO_FUNCTION];
+
+/// Returns whether a read-only client may call the function with the given name, i.e. whether it
+/// ends in `.GET` or is one of the `READ_ONLY_FUNCTIONS`.
+///
+/// This is deliberately stricter than `events::is_mutation()`: a read-only client refuses every
+/// function it doesn't know to be reading, e.g. `BELEG.BUCHEN`.
+pub fn is_read_only_function(function: &str) -> bool {
+    let function = function.to_uppercase();
+    function.ends_with(".GET") || READ_ONLY_FUNCTIONS.contains(&function.as_str())
+}
+
+/// Limits of the size of serialized EXECJSON request bodies, checked before a request is sent.
+///
+/// Some WEBWARE installations reject bodies larger than 1 MB, so by default a warning is logged
+/// above that size. Bodies larger than `max` aren't sent at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodySizeLimit {
+    /// Size in bytes above which a warning is logged.
+    pub warn: Option<usize>,
+    /// Size in bytes above which the request is refused with `WWSVCError::BodyTooLarge`.
+    pub max: Option<usize>,
+}
+
+impl Default for BodySizeLimit {
+    fn default() -> Self {
+        Self {
+            warn: Some(1024 * 1024),
+            max: None,
+        }
+    }
+}
+
+impl BodySizeLimit {
+    /// Neither warns nor refuses any body.
+    pub fn unlimited() -> Self {
+        Self {
+            warn: None,
+            max: None,
+        }
+    }
+
+    /// Refuses bodies larger than `max` bytes and warns above the same size.
+    pub fn max(max: usize) -> Self {
+        Self {
+            warn: Some(max),
+            max: Some(max),
+        }
+    }
+
+    /// Checks the size of the serialized `body`.
+    ///
+    /// The largest parameter is named in the warning and the error, since long parameter lists
+    /// are the usual cause and can be split into chunks.
+    pub fn check(&self, body: &ExecJsonRequest, size: usize) -> WWClientResult<()> {
+        self.check_size(&body.function, size)
+    }
+
+    /// Checks the size of the serialized batch `body`, naming the function with the largest
+    /// parameter.
+    pub fn check_batch(&self, body: &ExecJsonBatchRequest, size: usize) -> WWClientResult<()> {
+        match body.largest_function() {
+            Some(function) => self.check_size(function, size),
+            None => Ok(()),
+        }
+    }
+
+    fn check_size(&self, function: &ServiceFunction, size: usize) -> WWClientResult<()> {
+        let largest_parameter = || {
+            function
+                .parameters
+                .iter()
+                .max_by_key(|parameter| parameter.content.len())
+                .map(|parameter| parameter.name.clone())
+                .unwrap_or_default()
+        };
+
+        if let Some(max) = self.max.filter(|max| size > *max) {
+            return Err(WWSVCError::BodyTooLarge {
+                function: function.function_name.clone(),
+                size,
+                limit: max,
+                parameter: largest_parameter(),
+            });
+        }
+        if let Some(warn) = self.warn.filter(|warn| size > *warn) {
+            logging::warn_event!(
+                function = %function.function_name,
+                size,
+                limit = warn,
+                parameter = %largest_parameter(),
+                "request body exceeds the size limit, consider splitting the largest parameter into chunks"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Function names a client may call, checked before a request is signed.
+///
+/// Patterns are matched case-insensitively and may contain `*` as a wildcard, e.g. `ARTIKEL.*` or
+/// `*.GET`. A function is permitted if it matches no `deny` pattern and, unless `allow` is empty,
+/// at least one `allow` pattern.
+///
+/// ## Example
+///
+/// ```
+/// use wwsvc_rs::requests::FunctionPolicy;
+///
+/// let policy = FunctionPolicy::default()
+///     .allow("ARTIKEL.*")
+///     .allow("*.GET")
+///     .deny("ARTIKEL.DELETE");
+/// assert!(policy.permits("ARTIKEL.PUT"));
+/// assert!(policy.permits("beleg.get"));
+/// assert!(!policy.permits("ARTIKEL.DELETE"));
+/// assert!(!policy.permits("BELEG.INSERT"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionPolicy {
+    /// Patterns of the permitted functions. If empty, all functions are permitted.
+    pub allow: Vec<String>,
+    /// Patterns of the refused functions. Takes precedence over `allow`.
+    pub deny: Vec<String>,
+}
+
+impl FunctionPolicy {
+    /// Adds a pattern of permitted functions.
+    pub fn allow(mut self, pattern: &str) -> Self {
+        self.allow.push(pattern.to_string());
+        self
+    }
+
+    /// Adds a pattern of refused functions.
+    pub fn deny(mut self, pattern: &str) -> Self {
+        self.deny.push(pattern.to_string());
+        self
+    }
+
+    /// Returns whether the function with the given name may be called.
+    pub fn permits(&self, function: &str) -> bool {
+        let function = function.to_uppercase();
+        let matches = |pattern: &String| wildcard_match(&pattern.to_uppercase(), &function);
+        !self.deny.iter().any(matches) && (self.allow.is_empty() || self.allow.iter().any(matches))
+    }
+}
+
+/// Matches `text` against `pattern`, where `*` matches any sequence of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Mode in which the WEBSERVICES execute a function call, sent in the `WWSVC-EXECUTE-MODE` header
+/// and the `EXECUTE_MODE` member of the request body, depending on the `Compat` profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ExecuteMode {
+    /// The response contains the result of the function call.
+    #[default]
+    Synchron,
+    /// The response contains a ticket, see `WebwareClient::request_async()`.
+    Asynchron,
+    /// The function call is queued and executed with other calls.
+    Batch,
+    /// Any other mode, in upper case.
+    Other(String),
+}
+
+impl ExecuteMode {
+    /// Returns the name of the mode as sent to the server, e.g. `SYNCHRON`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExecuteMode::Synchron => "SYNCHRON",
+            ExecuteMode::Asynchron => "ASYNCHRON",
+            ExecuteMode::Batch => "BATCH",
+            ExecuteMode::Other(mode) => mode,
+        }
+    }
+
+    /// Returns the mode as a header value.
+    pub fn header_value(&self) -> Result<HeaderValue, InvalidHeaderValue> {
+        HeaderValue::from_str(self.as_str())
+    }
+}
+
+impl Display for ExecuteMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for ExecuteMode {
+    fn from(mode: &str) -> Self {
+        match mode.trim().to_uppercase().as_str() {
+            "SYNCHRON" => ExecuteMode::Synchron,
+            "ASYNCHRON" => ExecuteMode::Asynchron,
+            "BATCH" => ExecuteMode::Batch,
+            other => ExecuteMode::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ExecuteMode {
+    fn from(mode: String) -> Self {
+        ExecuteMode::from(mode.as_str())
+    }
+}
+
+impl From<ExecuteMode> for String {
+    fn from(mode: ExecuteMode) -> Self {
+        mode.as_str().to_string()
+    }
+}
+
+/// Body of an EXECJSON request.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecJsonRequest {
+    /// The function to execute.
+    #[serde(rename = "WWSVC_FUNCTION")]
+    pub function: ServiceFunction,
+    /// The authentication of the request.
+    #[serde(rename = "WWSVC_PASSINFO")]
+    pub pass_info: ServicePassInfo,
+}
+
+/// Function call of an EXECJSON request.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceFunction {
+    /// The full function name, e.g. `ARTIKEL.GET`.
+    #[serde(rename = "FUNCTIONNAME")]
+    pub function_name: String,
+    /// The parameters of the function.
+    #[serde(rename = "PARAMETER")]
+    pub parameters: Vec<ServiceFunctionParameter>,
+    /// The revision of the function.
+    #[serde(rename = "REVISION")]
+    pub revision: u32,
+}
+
+/// Body of an EXECJSON request executing several functions in one transaction, sent by
+/// `WebwareClient::send_batch()`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecJsonBatchRequest {
+    /// The functions to execute, in order.
+    #[serde(rename = "WWSVC_FUNCTION")]
+    pub functions: Vec<ServiceFunction>,
+    /// The authentication of the request.
+    #[serde(rename = "WWSVC_PASSINFO")]
+    pub pass_info: ServicePassInfo,
+    /// The transaction mode. `ATOMIC` rolls back all functions if one of them fails.
+    #[serde(rename = "WWSVC_TRANSACTION")]
+    pub transaction: String,
+}
+
+/// Function calls which are executed in one EXECJSON request and one transaction, to avoid a
+/// round trip per call, e.g. for bulk inserts.
+///
+/// Only some revisions of the WEBSERVICES support batches. If one of the functions fails, none of
+/// them takes effect.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use wwsvc_rs::requests::{BatchRequest, ServiceFunction};
+///
+/// let batch = BatchRequest::new()
+///     .function(ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", "A1"))
+///     .function(ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", "A2"));
+/// let response = client.send_batch(&batch).await?;
+/// for com_result in response.com_results() {
+///     println!("{}", com_result.status);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchRequest {
+    functions: Vec<ServiceFunction>,
+}
+
+impl BatchRequest {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a function call and returns the batch for chaining.
+    pub fn function(mut self, function: ServiceFunction) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    /// Adds a function call.
+    pub fn push(&mut self, function: ServiceFunction) {
+        self.functions.push(function);
+    }
+
+    /// Returns the function calls in order.
+    pub fn functions(&self) -> &[ServiceFunction] {
+        &self.functions
+    }
+
+    /// Returns the number of function calls.
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Returns whether the batch doesn't contain any function call.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+}
+
+impl FromIterator<ServiceFunction> for BatchRequest {
+    fn from_iter<I: IntoIterator<Item = ServiceFunction>>(iter: I) -> Self {
+        Self {
+            functions: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl ExecJsonBatchRequest {
+    /// Creates an atomic batch request body.
+    pub fn new(functions: Vec<ServiceFunction>, pass_info: ServicePassInfo) -> Self {
+        Self {
+            functions,
+            pass_info,
+            transaction: "ATOMIC".to_string(),
+        }
+    }
+
+    /// Serializes the request body with the members in the given order.
+    pub fn to_vec(&self, order: BodyOrder) -> WWClientResult<Vec<u8>> {
+        Ok(match order {
+            BodyOrder::Protocol => serde_json::to_vec(self)?,
+            BodyOrder::Alphabetical => alphabetical_vec(self)?,
+        })
+    }
+
+    /// Returns the function call containing the largest parameter, which is named if the body
+    /// exceeds the `BodySizeLimit`.
+    pub fn largest_function(&self) -> Option<&ServiceFunction> {
+        self.functions.iter().max_by_key(|function| {
+            function
+                .parameters
+                .iter()
+                .map(|parameter| parameter.content.len())
+                .max()
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Parameter of a function call.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceFunctionParameter {
+    /// The name of the parameter.
+    #[serde(rename = "PNAME")]
+    pub name: String,
+    /// The content of the parameter.
+    #[serde(rename = "PCONTENT")]
+    pub content: String,
+}
+
+/// Authentication of an EXECJSON request.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServicePassInfo {
+    /// The service pass of the client.
+    #[serde(rename = "SERVICEPASS")]
+    pub service_pass: String,
+    /// The hash of the request.
+    #[serde(rename = "APPHASH")]
+    pub app_hash: String,
+    /// The timestamp the hash has been created with.
+    #[serde(rename = "TIMESTAMP")]
+    pub timestamp: String,
+    /// The ID of the request.
+    #[serde(rename = "REQUESTID")]
+    pub request_id: RequestId,
+    /// The execute mode, e.g. `SYNCHRON`.
+    #[serde(rename = "EXECUTE_MODE", skip_serializing_if = "Option::is_none")]
+    pub execute_mode: Option<ExecuteMode>,
+}
+
+impl ExecJsonRequest {
+    /// Creates a request body for the given function call and authentication.
+    pub fn new(function: ServiceFunction, pass_info: ServicePassInfo) -> Self {
+        Self {
+            function,
+            pass_info,
+        }
+    }
+
+    /// Serializes the request body with the members in the given order.
+    pub fn to_vec(&self, order: BodyOrder) -> WWClientResult<Vec<u8>> {
+        Ok(match order {
+            BodyOrder::Protocol => serde_json::to_vec(self)?,
+            BodyOrder::Alphabetical => alphabetical_vec(self)?,
+        })
+    }
+
+    /// Returns the size in bytes of the serialized request body.
+    pub fn size(&self, order: BodyOrder) -> WWClientResult<usize> {
+        Ok(self.to_vec(order)?.len())
+    }
+}
+
+impl ServiceFunction {
+    /// Creates a call of the function with the given name and revision, without parameters.
+    pub fn new(function_name: &str, revision: u32) -> Self {
+        Self {
+            function_name: function_name.to_string(),
+            parameters: Vec::new(),
+            revision,
+        }
+    }
+
+    /// Adds a parameter and returns the function call for chaining.
+    pub fn parameter(mut self, name: &str, content: impl ToString) -> Self {
+        self.parameters
+            .push(ServiceFunctionParameter::new(name, content));
+        self
+    }
+
+    /// Replaces the parameters with the given ones.
+    pub fn parameters(mut self, parameters: &Parameters) -> Self {
+        self.parameters = parameters.into();
+        self
+    }
+}
+
+impl ServiceFunctionParameter {
+    /// Creates a parameter.
+    pub fn new(name: &str, content: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+impl ServicePassInfo {
+    /// Creates the authentication of a request, without an execute mode.
+    pub fn new(
+        service_pass: &str,
+        app_hash: &str,
+        timestamp: &str,
+        request_id: impl Into<RequestId>,
+    ) -> Self {
+        Self {
+            service_pass: service_pass.to_string(),
+            app_hash: app_hash.to_string(),
+            timestamp: timestamp.to_string(),
+            request_id: request_id.into(),
+            execute_mode: None,
+        }
+    }
+
+    /// Sets the execute mode and returns the authentication for chaining.
+    pub fn execute_mode(mut self, execute_mode: impl Into<ExecuteMode>) -> Self {
+        self.execute_mode = Some(execute_mode.into());
+        self
+    }
+}
+
+/// Displays the function call and the request ID. The service pass and the app hash are redacted.
+///
+/// ## Example
+///
+/// ```
+/// use wwsvc_rs::requests::{ExecJsonRequest, ServiceFunction, ServicePassInfo};
+///
+/// let request = ExecJsonRequest::new(
+///     ServiceFunction::new("ARTIKEL.GET", 1).parameter("ARTNR", "A1"),
+///     ServicePassInfo::new("service-pass", "app-hash", "timestamp", 3),
+/// );
+/// assert_eq!(
+///     request.to_string(),
+///     "ARTIKEL.GET (revision 1, request 3, service pass <redacted>): ARTNR=A1"
+/// );
+/// ```
+impl Display for ExecJsonRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (revision {}, request {}, service pass <redacted>)",
+            self.function.function_name, self.function.revision, self.pass_info.request_id
+        )?;
+        for (index, parameter) in self.function.parameters.iter().enumerate() {
+            f.write_str(if index == 0 { ": " } else { ", " })?;
+            fmt_parameter(f, &parameter.name, &parameter.content, false)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Parameters> for Vec<ServiceFunctionParameter> {
+    fn from(parameters: &Parameters) -> Self {
+        parameters
+            .iter()
+            .map(|parameter| ServiceFunctionParameter {
+                name: parameter.name.clone(),
+                content: parameter.content.clone(),
+            })
+            .collect()
+    }
+}
@@ -1,12 +1,42 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
 
-use crate::generate_get_response;
+/// Numbers or strings, as some server versions return numeric fields as strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(u64),
+    String(String),
+}
+
+fn deserialize_lenient_u32<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(number) => u32::try_from(number).map_err(serde::de::Error::custom),
+        NumberOrString::String(string) => string.trim().parse().map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_lenient_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        Option::<NumberOrString>::deserialize(deserializer)?.map(|value| match value {
+            NumberOrString::Number(number) => number.to_string(),
+            NumberOrString::String(string) => string,
+        }),
+    )
+}
 
 /// COMRESULT of a request. Contains information about the status of the request.
-#[derive(Deserialize, Debug, Clone)]
+///
+/// `STATUS` and `ERRNO` are accepted both as numbers and as strings.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct ComResult {
     /// The HTTP status code of the request.
-    #[serde(rename = "STATUS")]
+    #[serde(rename = "STATUS", deserialize_with = "deserialize_lenient_u32")]
     pub status: u32,
     /// The HTTP status message of the request.
     #[serde(rename = "CODE")]
@@ -15,18 +45,30 @@ pub struct ComResult {
     #[serde(rename = "INFO")]
     pub info: String,
     /// Additional information about the request.
-    #[serde(rename = "INFO2")]
+    #[serde(rename = "INFO2", skip_serializing_if = "Option::is_none")]
     pub info2: Option<String>,
     /// Additional information about the request.
-    #[serde(rename = "INFO3")]
+    #[serde(rename = "INFO3", skip_serializing_if = "Option::is_none")]
     pub info3: Option<String>,
     /// Error number of the request.
-    #[serde(rename = "ERRNO")]
+    #[serde(
+        rename = "ERRNO",
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_lenient_string"
+    )]
     pub errno: Option<String>,
 }
 
+impl ComResult {
+    /// Returns whether the status is a success status (2xx).
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
 /// Response of a REGISTER request.
-#[derive(Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct RegisterResponse {
     /// The COMRESULT of the request. Contains information about the status of the request.
     #[serde(rename = "COMRESULT")]
@@ -36,8 +78,56 @@ pub struct RegisterResponse {
     pub service_pass: ServicePass,
 }
 
+/// Response of an `.INSERT` function, containing the identifying fields of the created record.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InsertResponse<T> {
+    /// The COMRESULT of the request. Contains information about the status of the request.
+    #[serde(rename = "COMRESULT")]
+    pub com_result: ComResult,
+    /// The created record, as returned by the server.
+    #[serde(rename = "RECORD")]
+    pub record: T,
+}
+
+/// Response of a `BatchRequest`, containing a result per function call.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BatchResponse {
+    /// The COMRESULT of the whole batch.
+    #[serde(rename = "COMRESULT")]
+    pub com_result: ComResult,
+    /// The results of the function calls, in the order of the batch.
+    #[serde(rename = "RESULTS", default)]
+    pub results: Vec<BatchResult>,
+}
+
+impl BatchResponse {
+    /// Returns the COMRESULTs of the function calls, in the order of the batch.
+    pub fn com_results(&self) -> impl Iterator<Item = &ComResult> {
+        self.results.iter().map(|result| &result.com_result)
+    }
+
+    /// Returns whether the batch and every function call have succeeded.
+    pub fn is_success(&self) -> bool {
+        self.com_result.is_success() && self.com_results().all(ComResult::is_success)
+    }
+}
+
+/// Result of a function call of a `BatchRequest`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BatchResult {
+    /// The function which has been called.
+    #[serde(rename = "FUNCTIONNAME", default)]
+    pub function: String,
+    /// The COMRESULT of the function call.
+    #[serde(rename = "COMRESULT")]
+    pub com_result: ComResult,
+    /// The remaining members of the result, e.g. the created record.
+    #[serde(flatten)]
+    pub data: serde_json::Map<String, serde_json::Value>,
+}
+
 /// Service pass of a REGISTER request.
-#[derive(Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct ServicePass {
     /// The service pass.
     #[serde(rename = "PASSID")]
@@ -47,77 +137,87 @@ pub struct ServicePass {
     pub app_id: String,
 }
 
-generate_get_response!(ArtikelGetResponse, "ARTIKELLISTE", ArtikelListe, "ARTIKEL");
-generate_get_response!(AdresseGetResponse, "ADRESSLISTE", AdresseListe, "ADRESSE");
-generate_get_response!(BelegGetResponse, "BELEGLISTE", BelegListe, "BELEG");
-generate_get_response!(
-    BelPosGetResponse,
-    "POSITIONSLISTE",
-    PositionListe,
-    "POSITION"
-);
-generate_get_response!(ProjektGetResponse, "PROJEKTLISTE", ProjektListe, "PROJEKT");
-generate_get_response!(
-    SeriennummerGetResponse,
-    "SERIENNUMMERNLISTE",
-    SeriennummerListe,
-    "SERIENNUMMER"
-);
-generate_get_response!(ChargeGetResponse, "CHARGENLISTE", ChargeListe, "CHARGE");
-generate_get_response!(
-    AdressArtikelGetResponse,
-    "ADRESSARTIKELLISTE",
-    AdressArtikelListe,
-    "ADRESSARTIKEL"
-);
-generate_get_response!(
-    LieferadresseGetResponse,
-    "LIEFERADRESSLISTE",
-    LieferadresseListe,
-    "LIEFERADRESSE"
-);
-generate_get_response!(
-    AnsprechpartnerGetResponse,
-    "ANSPRECHPARTNERLISTE",
-    AnsprechpartnerListe,
-    "ANSPRECHPARTNER"
-);
-generate_get_response!(
-    VertreterGetResponse,
-    "VERTRETERLISTE",
-    VertreterListe,
-    "VERTRETER"
-);
-generate_get_response!(TermineGetResponse, "TERMINLISTE", TerminListe, "TERMIN");
-generate_get_response!(
-    GespraechGetResponse,
-    "GESPRAECHELISTE",
-    GespraechListe,
-    "GESPRAECH"
-);
-generate_get_response!(
-    WiedervorlageGetResponse,
-    "WIEDERVORLAGELISTE",
-    WiedervorlageListe,
-    "WIEDERVORLAGE"
-);
-generate_get_response!(
-    WarengruppeGetResponse,
-    "WARENGRUPPENLISTE",
-    WarengruppeListe,
-    "WARENGRUPPE"
-);
-generate_get_response!(LagerGetResponse, "LAGERLISTE", LagerListe, "LAGER");
-generate_get_response!(
-    MPKatalogGetResponse,
-    "KATALOGLISTE",
-    MPKatalogListe,
-    "KATALOG"
-);
-generate_get_response!(
-    MPKategorieGetResponse,
-    "KATEGORIENLISTE",
-    MPKategorieListe,
-    "KATEGORIE"
-);
-generate_get_response!(EANCodeGetResponse, "EANCODELISTE", EANCodeListe, "EANCODE");
+#[cfg(feature = "presets")]
+#[cfg_attr(docsrs, doc(cfg(feature = "presets")))]
+pub use presets::*;
+
+/// Response types of commonly used GET functions.
+#[cfg(feature = "presets")]
+mod presets {
+    use crate::generate_get_response;
+
+    generate_get_response!(ArtikelGetResponse, "ARTIKELLISTE", ArtikelListe, "ARTIKEL");
+    generate_get_response!(AdresseGetResponse, "ADRESSLISTE", AdresseListe, "ADRESSE");
+    generate_get_response!(BelegGetResponse, "BELEGLISTE", BelegListe, "BELEG");
+    generate_get_response!(
+        BelPosGetResponse,
+        "POSITIONSLISTE",
+        PositionListe,
+        "POSITION"
+    );
+    generate_get_response!(ProjektGetResponse, "PROJEKTLISTE", ProjektListe, "PROJEKT");
+    generate_get_response!(
+        SeriennummerGetResponse,
+        "SERIENNUMMERNLISTE",
+        SeriennummerListe,
+        "SERIENNUMMER"
+    );
+    generate_get_response!(ChargeGetResponse, "CHARGENLISTE", ChargeListe, "CHARGE");
+    generate_get_response!(
+        AdressArtikelGetResponse,
+        "ADRESSARTIKELLISTE",
+        AdressArtikelListe,
+        "ADRESSARTIKEL"
+    );
+    generate_get_response!(
+        LieferadresseGetResponse,
+        "LIEFERADRESSLISTE",
+        LieferadresseListe,
+        "LIEFERADRESSE"
+    );
+    generate_get_response!(
+        AnsprechpartnerGetResponse,
+        "ANSPRECHPARTNERLISTE",
+        AnsprechpartnerListe,
+        "ANSPRECHPARTNER"
+    );
+    generate_get_response!(
+        VertreterGetResponse,
+        "VERTRETERLISTE",
+        VertreterListe,
+        "VERTRETER"
+    );
+    generate_get_response!(TermineGetResponse, "TERMINLISTE", TerminListe, "TERMIN");
+    generate_get_response!(
+        GespraechGetResponse,
+        "GESPRAECHELISTE",
+        GespraechListe,
+        "GESPRAECH"
+    );
+    generate_get_response!(
+        WiedervorlageGetResponse,
+        "WIEDERVORLAGELISTE",
+        WiedervorlageListe,
+        "WIEDERVORLAGE"
+    );
+    generate_get_response!(
+        WarengruppeGetResponse,
+        "WARENGRUPPENLISTE",
+        WarengruppeListe,
+        "WARENGRUPPE"
+    );
+    generate_get_response!(LagerGetResponse, "LAGERLISTE", LagerListe, "LAGER");
+    generate_get_response!(
+        MPKatalogGetResponse,
+        "KATALOGLISTE",
+        MPKatalogListe,
+        "KATALOG"
+    );
+    generate_get_response!(
+        MPKategorieGetResponse,
+        "KATEGORIENLISTE",
+        MPKategorieListe,
+        "KATEGORIE"
+    );
+    generate_get_response!(EANCodeGetResponse, "EANCODELISTE", EANCodeListe, "EANCODE");
+}
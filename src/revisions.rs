@@ -0,0 +1,81 @@
+use crate::logging;
+use crate::Parameters;
+
+/// A known revision of a WEBSERVICES function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionRevision {
+    /// The full function name, e.g. `ARTIKEL.GET`.
+    pub function: &'static str,
+    /// The revision number.
+    pub revision: u32,
+    /// Parameters which are supported starting with this revision.
+    pub parameters_added: &'static [&'static str],
+    /// Description of breaking differences to the previous revision.
+    pub changes: &'static str,
+}
+
+/// Table of known function revisions.
+///
+/// Only differences which have been observed on real instances are recorded here, so the table
+/// is extended as further differences become known.
+const KNOWN_REVISIONS: &[FunctionRevision] = &[FunctionRevision {
+    function: "ARTIKEL.GET",
+    revision: 1,
+    parameters_added: &["ARTNR", "FELDER"],
+    changes: "Initial revision.",
+}];
+
+/// Access to the table of known function revisions.
+pub struct FunctionRevisions;
+
+impl FunctionRevisions {
+    /// Returns the known revisions of the given function, ordered by revision.
+    pub fn for_function(function: &str) -> Vec<&'static FunctionRevision> {
+        let mut revisions = KNOWN_REVISIONS
+            .iter()
+            .filter(|revision| revision.function.eq_ignore_ascii_case(function))
+            .collect::<Vec<_>>();
+        revisions.sort_by_key(|revision| revision.revision);
+        revisions
+    }
+
+    /// Returns the known revisions of `ARTIKEL.GET`.
+    pub fn artikel_get() -> Vec<&'static FunctionRevision> {
+        Self::for_function("ARTIKEL.GET")
+    }
+
+    /// Returns the parameters which are known to be supported only by a revision newer than
+    /// `revision`, together with the revision that introduced them.
+    pub fn unsupported_parameters<'a>(
+        function: &str,
+        revision: u32,
+        parameters: &'a Parameters,
+    ) -> Vec<(&'a str, u32)> {
+        let revisions = Self::for_function(function);
+        parameters
+            .iter()
+            .filter_map(|parameter| {
+                revisions
+                    .iter()
+                    .find(|known| known.parameters_added.contains(&parameter.name.as_str()))
+                    .filter(|known| known.revision > revision)
+                    .map(|known| (parameter.name.as_str(), known.revision))
+            })
+            .collect()
+    }
+}
+
+/// Emits a warning for each parameter which the requested revision is known to lack.
+pub(crate) fn warn_unsupported_parameters(function: &str, revision: u32, parameters: &Parameters) {
+    for (parameter, introduced) in
+        FunctionRevisions::unsupported_parameters(function, revision, parameters)
+    {
+        logging::warn_event!(
+            function,
+            revision,
+            parameter,
+            introduced,
+            "parameter is not supported by the requested function revision"
+        );
+    }
+}
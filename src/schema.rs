@@ -0,0 +1,156 @@
+use serde_json::{json, Map, Value};
+
+use crate::traits::WWSVCGetData;
+
+/// JSON schema type of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaType {
+    /// A string, also used for types whose representation isn't known.
+    String,
+    /// An integer.
+    Integer,
+    /// A floating point number.
+    Number,
+    /// A boolean.
+    Boolean,
+    /// A list.
+    Array,
+    /// A map.
+    Object,
+}
+
+impl SchemaType {
+    /// Returns the name of the type in JSON schema.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaType::String => "string",
+            SchemaType::Integer => "integer",
+            SchemaType::Number => "number",
+            SchemaType::Boolean => "boolean",
+            SchemaType::Array => "array",
+            SchemaType::Object => "object",
+        }
+    }
+}
+
+/// Description of a field of an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDescription {
+    /// The name of the field in the WEBSERVICES, e.g. `ART_1_25`.
+    pub name: String,
+    /// The name of the field in Rust.
+    pub field: String,
+    /// The type of the field.
+    pub schema_type: SchemaType,
+    /// Whether the field may be missing or `null`.
+    pub nullable: bool,
+}
+
+/// Description of an entity read with `WWSVCGetData`, as returned by
+/// `WWSVCGetData::description()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityDescription {
+    /// The name of the Rust type.
+    pub name: String,
+    /// The function the entity is read with, e.g. `ARTIKEL.GET`.
+    pub function: String,
+    /// The fields of the entity.
+    pub fields: Vec<FieldDescription>,
+}
+
+impl EntityDescription {
+    /// Returns the JSON schema of the entity.
+    pub fn to_json_schema(&self) -> Value {
+        let properties = self
+            .fields
+            .iter()
+            .map(|field| {
+                let mut property = json!({
+                    "type": field.schema_type.as_str(),
+                    "title": field.field,
+                });
+                if field.nullable {
+                    property["nullable"] = Value::Bool(true);
+                }
+                (field.name.clone(), property)
+            })
+            .collect::<Map<_, _>>();
+        let required = self
+            .fields
+            .iter()
+            .filter(|field| !field.nullable)
+            .map(|field| Value::String(field.name.clone()))
+            .collect::<Vec<_>>();
+        json!({
+            "type": "object",
+            "description": format!("Read with {}", self.function),
+            "x-wwsvc-function": self.function,
+            "properties": properties,
+            "required": required,
+        })
+    }
+}
+
+/// Registry of the entities a service exposes, from which an OpenAPI document is generated for
+/// API gateways and documentation portals.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use wwsvc_rs::schema::EntityRegistry;
+///
+/// let document = EntityRegistry::new()
+///     .register::<ArticleData>()
+///     .register::<StockData>()
+///     .to_openapi("Article service", "1.0.0");
+/// println!("{}", serde_json::to_string_pretty(&document)?);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EntityRegistry {
+    entities: Vec<EntityDescription>,
+}
+
+impl EntityRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an entity. Registering an entity twice replaces the previous description.
+    pub fn register<T: WWSVCGetData>(mut self) -> Self {
+        self.insert(T::description());
+        self
+    }
+
+    /// Adds the description of an entity, replacing one with the same name.
+    pub fn insert(&mut self, description: EntityDescription) {
+        match self
+            .entities
+            .iter_mut()
+            .find(|entity| entity.name == description.name)
+        {
+            Some(existing) => *existing = description,
+            None => self.entities.push(description),
+        }
+    }
+
+    /// Returns the registered entities in registration order.
+    pub fn entities(&self) -> &[EntityDescription] {
+        &self.entities
+    }
+
+    /// Returns an OpenAPI 3.0 document containing the schemas of all registered entities.
+    pub fn to_openapi(&self, title: &str, version: &str) -> Value {
+        let schemas = self
+            .entities
+            .iter()
+            .map(|entity| (entity.name.clone(), entity.to_json_schema()))
+            .collect::<Map<_, _>>();
+        json!({
+            "openapi": "3.0.3",
+            "info": {"title": title, "version": version},
+            "paths": {},
+            "components": {"schemas": schemas},
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::de::DeserializeOwned;
+
+use crate::client::states::{OpenCursor, Registered};
+use crate::cursor::Cursor;
+use crate::parameters::MergePrecedence;
+use crate::{Parameters, WWClientResult, WWResponse, WebwareClient};
+
+/// Export of a function which is split into several shards, e.g. key ranges, which are
+/// requested concurrently with one cursor each.
+///
+/// Each shard runs on its own clone of the client. The rows of all shards are merged into a
+/// single stream in the order they arrive, so at most one page per shard is held in memory.
+///
+/// How a key range is expressed depends on the function; each shard is given as the parameters
+/// which are set in addition to the parameters of the export.
+///
+/// ## Example
+///
+#[cfg_attr(feature = "presets", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "presets"), doc = "```rust,ignore")]
+/// use futures::StreamExt;
+/// use wwsvc_rs::{responses::ArtikelGetResponse, Parameters, ShardedExport, WebwareClient};
+///
+/// #[derive(Debug, serde::Deserialize, Clone)]
+/// pub struct ArticleData {
+///     #[serde(rename = "ART_1_25")]
+///     pub article_number: String,
+/// }
+///
+/// # async fn example(client: WebwareClient<wwsvc_rs::Registered>) {
+/// let mut rows = ShardedExport::new("ARTIKEL.GET", 1, Parameters::new().param("FELDER", "ART_1_25"))
+///     // the parameters selecting a key range depend on the function
+///     .shard(Parameters::new().param("ARTNR", "A*"))
+///     .shard(Parameters::new().param("ARTNR", "B*"))
+///     .run::<ArticleData, ArtikelGetResponse<ArticleData>>(&client);
+/// while let Some(row) = rows.next().await {
+///     println!("{:#?}", row.unwrap());
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShardedExport {
+    method: Option<reqwest::Method>,
+    function: String,
+    version: u32,
+    parameters: Parameters,
+    shards: Vec<Parameters>,
+    page_size: u32,
+}
+
+impl ShardedExport {
+    /// Creates an export of the given function without any shards.
+    pub fn new(function: &str, version: u32, parameters: Parameters) -> Self {
+        Self {
+            method: None,
+            function: function.to_string(),
+            version,
+            parameters,
+            shards: Vec::new(),
+            page_size: 500,
+        }
+    }
+
+    /// Sets the HTTP method. Defaults to the `MethodPolicy` of the client.
+    pub fn method(mut self, method: reqwest::Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Sets the page size of each cursor. Defaults to 500.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Adds a shard, given as the parameters which select its key range.
+    pub fn shard(mut self, parameters: Parameters) -> Self {
+        self.shards.push(parameters);
+        self
+    }
+
+    /// Returns the amount of shards.
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Starts the export and returns the merged rows of all shards.
+    ///
+    /// If a shard fails, its error is yielded and the shard stops, while the others continue. An
+    /// export without shards runs as a single cursor.
+    pub fn run<T, R>(
+        self,
+        client: &WebwareClient<Registered>,
+    ) -> BoxStream<'static, WWClientResult<T>>
+    where
+        T: Send + 'static,
+        R: DeserializeOwned + WWResponse<Item = T> + 'static,
+    {
+        let shards = if self.shards.is_empty() {
+            vec![Parameters::new()]
+        } else {
+            self.shards
+        };
+
+        let streams = shards.into_iter().map(|shard| {
+            let mut parameters = self.parameters.clone();
+            parameters.merge(&shard, MergePrecedence::Overwrite);
+            let state = ShardState {
+                client: client.clone().create_cursor(self.page_size),
+                cursor: Cursor::new(self.page_size),
+                method: self.method.clone(),
+                function: self.function.clone(),
+                version: self.version,
+                parameters,
+                finished: false,
+            };
+            stream::unfold(state, |mut state| async move {
+                let page = state.next_page::<T, R>().await?;
+                Some((page, state))
+            })
+            .flat_map(|page| match page {
+                Ok(rows) => stream::iter(rows.into_iter().map(Ok).collect::<Vec<_>>()),
+                Err(e) => stream::iter(vec![Err(e)]),
+            })
+            .boxed()
+        });
+
+        stream::select_all(streams).boxed()
+    }
+}
+
+struct ShardState {
+    client: WebwareClient<OpenCursor>,
+    cursor: Cursor,
+    method: Option<reqwest::Method>,
+    function: String,
+    version: u32,
+    parameters: Parameters,
+    finished: bool,
+}
+
+impl ShardState {
+    async fn next_page<T, R>(&mut self) -> Option<WWClientResult<Vec<T>>>
+    where
+        R: DeserializeOwned + WWResponse<Item = T>,
+    {
+        if self.finished {
+            return None;
+        }
+
+        let mut pages = self
+            .client
+            .cursored_request::<T, R>(
+                self.method.clone(),
+                &self.function,
+                self.version,
+                self.parameters.clone(),
+            )
+            .with_cursor(self.cursor.clone());
+        let page = pages.next().await;
+        self.cursor = pages.into_cursor();
+        if !matches!(page, Some(Ok(_))) {
+            self.finished = true;
+        }
+        page
+    }
+}
@@ -0,0 +1,276 @@
+use std::cell::RefCell;
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::HeaderValue;
+use reqwest::Response;
+
+use crate::{WWClientResult, WWSVCError};
+
+/// Anonymized sample response of the WEBSERVICES.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    /// Name of the fixture, e.g. `artikel_get`.
+    pub name: &'static str,
+    /// The function which produced the response, e.g. `ARTIKEL.GET`.
+    pub function: &'static str,
+    /// The response body.
+    pub json: &'static str,
+}
+
+impl Fixture {
+    /// Parses the response body into a `serde_json::Value`.
+    pub fn value(&self) -> serde_json::Value {
+        serde_json::from_str(self.json).expect("fixtures are valid JSON")
+    }
+}
+
+const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "artikel_get",
+        function: "ARTIKEL.GET",
+        json: include_str!("../fixtures/artikel_get.json"),
+    },
+    Fixture {
+        name: "artikel_get_empty",
+        function: "ARTIKEL.GET",
+        json: include_str!("../fixtures/artikel_get_empty.json"),
+    },
+    Fixture {
+        name: "adresse_get",
+        function: "ADRESSE.GET",
+        json: include_str!("../fixtures/adresse_get.json"),
+    },
+    Fixture {
+        name: "beleg_get",
+        function: "BELEG.GET",
+        json: include_str!("../fixtures/beleg_get.json"),
+    },
+    Fixture {
+        name: "error_not_found",
+        function: "UNBEKANNT.GET",
+        json: include_str!("../fixtures/error_not_found.json"),
+    },
+];
+
+/// Returns all bundled response fixtures.
+///
+/// The fixtures are the contract the bundled response types are tested against, so downstream
+/// crates can use them to test their own types.
+pub fn fixtures() -> &'static [Fixture] {
+    FIXTURES
+}
+
+/// Returns the bundled response fixture with the given name.
+pub fn fixture(name: &str) -> Option<&'static Fixture> {
+    FIXTURES.iter().find(|fixture| fixture.name == name)
+}
+
+/// Timestamp and salt used for every `AppHash` created on the current thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedAppHash {
+    /// The timestamp, sent in the `WWSVC-TS` header.
+    pub date: SystemTime,
+    /// The salt replacing the application secret in the hash, so snapshots don't depend on real
+    /// credentials. If `None`, the application secret is used.
+    pub salt: Option<String>,
+}
+
+thread_local! {
+    static FIXED_APP_HASH: RefCell<Option<FixedAppHash>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous `AppHash` behaviour of the current thread when dropped.
+#[derive(Debug)]
+#[must_use = "the AppHash is only fixed until the guard is dropped"]
+pub struct FixedAppHashGuard {
+    previous: Option<FixedAppHash>,
+}
+
+impl Drop for FixedAppHashGuard {
+    fn drop(&mut self) {
+        FIXED_APP_HASH.with(|fixed| *fixed.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Fixes the timestamp and salt of every `AppHash` created on the current thread until the
+/// returned guard is dropped, so signed requests are byte-identical across runs.
+///
+/// The override is thread-local, so requests have to be signed on the thread which called this,
+/// e.g. within a `#[tokio::test]` using the default current-thread runtime.
+///
+/// ## Example
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+/// use wwsvc_rs::{test_util::fix_app_hash, AppHash};
+///
+/// let _guard = fix_app_hash(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000), Some("salt"));
+/// let first = AppHash::for_request(1, "secret");
+/// let second = AppHash::for_request(1, "other secret");
+/// assert_eq!(first.hash, second.hash);
+/// assert_eq!(first.date_formatted, "Tue, 14 Nov 2023 22:13:20 GMT");
+/// ```
+pub fn fix_app_hash(date: SystemTime, salt: Option<&str>) -> FixedAppHashGuard {
+    let fixed = FixedAppHash {
+        date,
+        salt: salt.map(str::to_string),
+    };
+    let previous = FIXED_APP_HASH.with(|current| current.borrow_mut().replace(fixed));
+    FixedAppHashGuard { previous }
+}
+
+pub(crate) fn fixed_app_hash() -> Option<FixedAppHash> {
+    FIXED_APP_HASH.with(|fixed| fixed.borrow().clone())
+}
+
+/// How the `WWSVC-CURSOR` header of a response is corrupted by `FaultInjection::corrupt_cursor()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorFault {
+    /// The header is removed, as if the server had lost the cursor.
+    Missing,
+    /// The header is replaced by a cursor ID the server doesn't know.
+    Invalid,
+}
+
+/// Cursor ID set by `CursorFault::Invalid`.
+pub const INVALID_CURSOR_ID: &str = "FAULT-INJECTED";
+
+/// Faults injected into the EXECJSON requests sent on the current thread, to test how code built
+/// on this crate copes with an unreliable network or server.
+///
+/// Requests are counted from the call to `inject_faults()`, starting at 1, so e.g. `drop_every(3)`
+/// drops the third, sixth, ... request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaultInjection {
+    /// Every `n`th request isn't sent and fails with `WWSVCError::Timeout`, as if the server
+    /// hadn't answered.
+    pub drop_every: Option<u32>,
+    /// Latency added before each request is sent.
+    pub latency: Option<Duration>,
+    /// Every `n`th response has its `WWSVC-CURSOR` header corrupted.
+    pub corrupt_cursor_every: Option<(u32, CursorFault)>,
+}
+
+impl FaultInjection {
+    /// Creates a configuration without faults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops every `n`th request.
+    pub fn drop_every(mut self, n: u32) -> Self {
+        self.drop_every = Some(n);
+        self
+    }
+
+    /// Adds `latency` before each request is sent.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Corrupts the `WWSVC-CURSOR` header of every `n`th response.
+    pub fn corrupt_cursor(mut self, n: u32, fault: CursorFault) -> Self {
+        self.corrupt_cursor_every = Some((n, fault));
+        self
+    }
+}
+
+/// Returns whether the request with the given number is affected by a fault injected every `n`th
+/// request.
+fn affects(every: Option<u32>, request: u32) -> bool {
+    every.is_some_and(|n| n > 0 && request % n == 0)
+}
+
+thread_local! {
+    static FAULTS: RefCell<Option<(FaultInjection, u32)>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous fault injection of the current thread when dropped.
+#[derive(Debug)]
+#[must_use = "faults are only injected until the guard is dropped"]
+pub struct FaultInjectionGuard {
+    previous: Option<(FaultInjection, u32)>,
+}
+
+impl Drop for FaultInjectionGuard {
+    fn drop(&mut self) {
+        FAULTS.with(|faults| *faults.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Injects the given faults into every EXECJSON request sent on the current thread until the
+/// returned guard is dropped, e.g. to verify that retries and pagination survive dropped
+/// requests, slow responses and lost cursors.
+///
+/// Faults apply to the requests of all clients, against a mock server as well as a real
+/// instance. Like `fix_app_hash()`, the injection is thread-local, so requests have to be sent on
+/// the thread which called this.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use wwsvc_rs::test_util::{inject_faults, FaultInjection};
+/// use wwsvc_rs::{Parameters, WWSVCError, WebwareClient};
+///
+/// # async fn example(mut client: WebwareClient<wwsvc_rs::Registered>) {
+/// let _guard = inject_faults(FaultInjection::new().drop_every(2));
+/// assert!(client.request(None, "ARTIKEL.GET", 1, Parameters::new(), None).await.is_ok());
+/// assert!(matches!(
+///     client.request(None, "ARTIKEL.GET", 1, Parameters::new(), None).await,
+///     Err(WWSVCError::Timeout(_))
+/// ));
+/// # }
+/// ```
+pub fn inject_faults(faults: FaultInjection) -> FaultInjectionGuard {
+    let previous = FAULTS.with(|current| current.borrow_mut().replace((faults, 0)));
+    FaultInjectionGuard { previous }
+}
+
+/// Applies the faults of the current thread to a request which is about to be sent, and returns
+/// the number of the request if faults are injected.
+pub(crate) async fn before_request(
+    timeout: Option<Duration>,
+) -> WWClientResult<Option<(FaultInjection, u32)>> {
+    let current = FAULTS.with(|faults| {
+        faults.borrow_mut().as_mut().map(|(faults, count)| {
+            *count += 1;
+            (faults.clone(), *count)
+        })
+    });
+    let Some((faults, request)) = current else {
+        return Ok(None);
+    };
+    if let Some(latency) = faults.latency {
+        tokio::time::sleep(latency).await;
+    }
+    if affects(faults.drop_every, request) {
+        return Err(WWSVCError::Timeout(timeout.unwrap_or_default()));
+    }
+    Ok(Some((faults, request)))
+}
+
+/// Applies the faults returned by `before_request()` to the response of the request.
+pub(crate) fn after_response(
+    faults: Option<(FaultInjection, u32)>,
+    mut response: Response,
+) -> Response {
+    let Some((faults, request)) = faults else {
+        return response;
+    };
+    if let Some((every, fault)) = faults.corrupt_cursor_every {
+        if affects(Some(every), request) && response.headers().contains_key("WWSVC-CURSOR") {
+            match fault {
+                CursorFault::Missing => {
+                    response.headers_mut().remove("WWSVC-CURSOR");
+                }
+                CursorFault::Invalid => {
+                    response
+                        .headers_mut()
+                        .insert("WWSVC-CURSOR", HeaderValue::from_static(INVALID_CURSOR_ID));
+                }
+            }
+        }
+    }
+    response
+}
@@ -0,0 +1,32 @@
+use opentelemetry::trace::TraceContextExt;
+use reqwest::header::{HeaderMap, HeaderValue};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Injects the W3C `traceparent` and `tracestate` headers of the current span into `headers`.
+///
+/// Nothing is injected if the current span isn't linked to a valid OpenTelemetry span context.
+pub(crate) fn inject(headers: &mut HeaderMap) {
+    let context = tracing::Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    if !span_context.is_valid() {
+        return;
+    }
+
+    let traceparent = format!(
+        "00-{}-{}-{:02x}",
+        span_context.trace_id(),
+        span_context.span_id(),
+        span_context.trace_flags().to_u8()
+    );
+    if let Ok(value) = HeaderValue::from_str(&traceparent) {
+        headers.insert("traceparent", value);
+    }
+
+    let tracestate = span_context.trace_state().header();
+    if !tracestate.is_empty() {
+        if let Ok(value) = HeaderValue::from_str(&tracestate) {
+            headers.insert("tracestate", value);
+        }
+    }
+}
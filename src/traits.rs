@@ -1,6 +1,210 @@
-use std::collections::HashMap;
+use serde::de::DeserializeOwned;
 
-use crate::{Ready, WWClientResult};
+use crate::error::WWSVCError;
+use crate::responses::ComResult;
+use crate::WWClientResult;
+#[cfg(feature = "derive")]
+use crate::{Parameters, Ready};
+
+/// Common interface of all responses to WEBSERVICES functions returning a list of items.
+///
+/// Implemented by the responses generated by `generate_get_response!` and the `WWSVCGetData`
+/// derive macro, as well as by `serde_json::Value`, so generic utilities can operate on any of
+/// them.
+pub trait WWResponse {
+    /// The type of the items in the list.
+    type Item;
+
+    /// Returns the COMRESULT of the response, if present.
+    fn com_result(&self) -> Option<ComResult>;
+
+    /// Returns the items of the response. Returns an empty slice if the list is missing.
+    fn items(&self) -> &[Self::Item];
+
+    /// Consumes the response and returns its items. Returns an empty `Vec` if the list is missing.
+    fn into_items(self) -> Vec<Self::Item>;
+
+    /// Returns the raw JSON of the response, if it has been kept.
+    fn raw(&self) -> Option<&serde_json::Value> {
+        None
+    }
+}
+
+impl WWResponse for serde_json::Value {
+    type Item = serde_json::Value;
+
+    fn com_result(&self) -> Option<ComResult> {
+        self.get("COMRESULT")
+            .and_then(|com_result| serde_json::from_value(com_result.clone()).ok())
+    }
+
+    /// Returns the items of the first list found in the response, i.e. the array inside the
+    /// first container object (e.g. `ARTIKELLISTE.ARTIKEL`).
+    fn items(&self) -> &[serde_json::Value] {
+        self.as_object()
+            .into_iter()
+            .flat_map(|object| object.iter())
+            .filter(|(key, _)| key.as_str() != "COMRESULT")
+            .filter_map(|(_, container)| container.as_object())
+            .flat_map(|container| container.values())
+            .find_map(|list| list.as_array())
+            .map(|list| list.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn into_items(mut self) -> Vec<serde_json::Value> {
+        self.as_object_mut()
+            .into_iter()
+            .flat_map(|object| object.iter_mut())
+            .filter(|(key, _)| key.as_str() != "COMRESULT")
+            .filter_map(|(_, container)| container.as_object_mut())
+            .flat_map(|container| container.values_mut())
+            .find_map(|list| list.as_array_mut())
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    fn raw(&self) -> Option<&serde_json::Value> {
+        Some(self)
+    }
+}
+
+/// Fallible accessors for untyped responses, as returned by `WebwareClient::request()`.
+///
+/// ## Example
+///
+/// ```
+/// use serde_json::json;
+/// use wwsvc_rs::WWValueExt;
+///
+/// let response = json!({
+///     "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+///     "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent"}]}
+/// });
+///
+/// assert_eq!(WWValueExt::com_result(&response).unwrap().status, 200);
+/// assert_eq!(response.list("ARTIKELLISTE", "ARTIKEL").unwrap().len(), 1);
+/// assert_eq!(
+///     response.field("ARTIKELLISTE.ARTIKEL.0.ART_1_25").unwrap(),
+///     "Artikel19Prozent"
+/// );
+/// ```
+pub trait WWValueExt {
+    /// Returns the COMRESULT of the response.
+    fn com_result(&self) -> WWClientResult<ComResult>;
+
+    /// Returns the list `list` inside the container `container`.
+    ///
+    /// Returns an empty slice if the container exists, but the list doesn't, which is how the
+    /// WEBSERVICES report that no rows matched.
+    fn list(&self, container: &str, list: &str) -> WWClientResult<&[serde_json::Value]>;
+
+    /// Deserializes the list `list` inside the container `container` into a `Vec<T>`.
+    fn list_as<T: DeserializeOwned>(&self, container: &str, list: &str) -> WWClientResult<Vec<T>>;
+
+    /// Returns the value at the dot separated `path`. Array elements are addressed by their
+    /// index, e.g. `ARTIKELLISTE.ARTIKEL.0.ART_1_25`.
+    fn field(&self, path: &str) -> WWClientResult<&serde_json::Value>;
+
+    /// Deserializes the value at the dot separated `path` into `T`.
+    fn field_as<T: DeserializeOwned>(&self, path: &str) -> WWClientResult<T>;
+}
+
+impl WWValueExt for serde_json::Value {
+    fn com_result(&self) -> WWClientResult<ComResult> {
+        self.field_as("COMRESULT")
+    }
+
+    fn list(&self, container: &str, list: &str) -> WWClientResult<&[serde_json::Value]> {
+        let container_value = self.field(container)?;
+        match container_value.get(list) {
+            Some(serde_json::Value::Array(items)) => Ok(items.as_slice()),
+            Some(serde_json::Value::Null) | None => Ok(&[]),
+            Some(_) => Err(WWSVCError::MissingField(format!("{}.{}", container, list))),
+        }
+    }
+
+    fn list_as<T: DeserializeOwned>(&self, container: &str, list: &str) -> WWClientResult<Vec<T>> {
+        self.list(container, list)?
+            .iter()
+            .map(|item| Ok(T::deserialize(item)?))
+            .collect()
+    }
+
+    fn field(&self, path: &str) -> WWClientResult<&serde_json::Value> {
+        path.split('.')
+            .try_fold(self, |value, segment| match value {
+                serde_json::Value::Array(items) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| items.get(index)),
+                _ => value.get(segment),
+            })
+            .ok_or_else(|| WWSVCError::MissingField(path.to_string()))
+    }
+
+    fn field_as<T: DeserializeOwned>(&self, path: &str) -> WWClientResult<T> {
+        Ok(T::deserialize(self.field(path)?)?)
+    }
+}
+
+/// Decides how the `FIELDS` of a `WWSVCGetData` type are combined with a `FELDER` parameter
+/// provided by the caller.
+#[cfg(feature = "derive")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FieldsMerge {
+    /// The `FELDER` of the caller are sent unchanged. `FIELDS` are only sent if the caller didn't
+    /// provide `FELDER`.
+    #[default]
+    RespectCaller,
+    /// The fields of the caller and `FIELDS` are combined, without duplicates.
+    Union,
+    /// `FIELDS` replace the `FELDER` of the caller.
+    Overwrite,
+}
+
+#[cfg(feature = "derive")]
+impl FieldsMerge {
+    /// Sets the `FELDER` parameter according to the merge mode.
+    pub fn apply(&self, parameters: &mut Parameters, fields: &str) {
+        if fields.is_empty() {
+            return;
+        }
+
+        let merged = match (self, parameters.get("FELDER")) {
+            (FieldsMerge::RespectCaller, Some(_)) => return,
+            (FieldsMerge::Union, Some(existing)) => {
+                let mut merged = existing
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .collect::<Vec<_>>();
+                for field in fields.split(',') {
+                    if !merged.contains(&field) {
+                        merged.push(field);
+                    }
+                }
+                merged.join(",")
+            }
+            _ => fields.to_string(),
+        };
+        parameters.insert("FELDER", merged);
+    }
+}
+
+/// Returns the version of `function` to request, which is taken from the client if `auto` is set.
+#[cfg(feature = "derive")]
+pub(crate) fn function_version<State: Ready>(
+    client: &crate::client::WebwareClient<State>,
+    function: &str,
+    version: u32,
+    auto: bool,
+) -> u32 {
+    match auto {
+        true => client.function_version(function).unwrap_or(version),
+        false => version,
+    }
+}
 
 /// Trait for the WWSVCGetData derive macro.
 #[cfg(feature = "derive")]
@@ -10,10 +214,20 @@ pub trait WWSVCGetData {
     const FUNCTION: &'static str;
     /// The version of the function.
     const VERSION: u32 = 1;
+    /// Whether the version is taken from `WebwareClient::function_version()`, falling back to
+    /// `VERSION`.
+    const AUTO_VERSION: bool = false;
     /// The function method of the WWSVC request.
     const METHOD: reqwest::Method = reqwest::Method::PUT;
     /// The fields of the struct.
     const FIELDS: &'static str = "";
+    /// How `FIELDS` are combined with the `FELDER` provided by the caller.
+    const FIELDS_MERGE: FieldsMerge = FieldsMerge::RespectCaller;
+    /// The maximum amount of results per response, or the page size of `get_cursored()`.
+    /// Defaults to the settings of the client.
+    const MAX_LINES: Option<u32> = None;
+    /// The timeout of each request. Defaults to the timeout of the client.
+    const TIMEOUT: Option<std::time::Duration> = None;
 
     /// The response type of the WWSVC request.
     type Response: serde::de::DeserializeOwned;
@@ -21,20 +235,523 @@ pub trait WWSVCGetData {
     /// The container type of the WWSVC request.
     type Container: serde::de::DeserializeOwned;
 
+    /// Returns the description of this entity, e.g. for `schema::EntityRegistry`.
+    ///
+    /// By default, every field of `FIELDS` is described as a string. The derive macro describes
+    /// the fields by their Rust types.
+    fn description() -> crate::schema::EntityDescription {
+        let name = std::any::type_name::<Self>();
+        crate::schema::EntityDescription {
+            name: name.rsplit("::").next().unwrap_or(name).to_string(),
+            function: Self::FUNCTION.to_string(),
+            fields: Self::FIELDS
+                .split(',')
+                .filter(|field| !field.is_empty())
+                .map(|field| crate::schema::FieldDescription {
+                    name: field.to_string(),
+                    field: field.to_string(),
+                    schema_type: crate::schema::SchemaType::String,
+                    nullable: true,
+                })
+                .collect(),
+        }
+    }
+
     /// Requests this data from the server.
+    ///
+    /// `FIELDS` are sent as `FELDER`, combined with the `FELDER` of the caller according to
+    /// `FIELDS_MERGE`. If `FIELDS` is empty, only the `FELDER` of the caller are sent.
+    ///
+    /// `MAX_LINES` and `TIMEOUT` take precedence over the settings of the client.
     async fn get(
         client: &mut crate::client::WebwareClient<impl Ready + Send>,
-        mut parameters: HashMap<&str, &str>,
+        mut parameters: Parameters,
     ) -> WWClientResult<Self::Response> {
-        parameters.insert("FELDER", Self::FIELDS);
+        Self::FIELDS_MERGE.apply(&mut parameters, Self::FIELDS);
+        if let Some(timeout) = Self::TIMEOUT {
+            client.override_next_timeout(timeout);
+        }
+        let max_lines = Self::MAX_LINES.map(|max_lines| max_lines.to_string());
+        let headers = max_lines.as_deref().map(|max_lines| {
+            std::collections::HashMap::from([("WWSVC-ACCEPT-RESULT-MAX-LINES", max_lines)])
+        });
         client
             .request_generic(
                 Self::METHOD,
                 Self::FUNCTION,
-                Self::VERSION,
+                function_version(client, Self::FUNCTION, Self::VERSION, Self::AUTO_VERSION),
                 parameters,
-                None,
+                headers,
             )
             .await
     }
+
+    /// Requests this data page by page, using the cursor of the client.
+    ///
+    /// `FIELDS` are combined with the `FELDER` of the caller like in `get()`. `MAX_LINES` is used
+    /// as the page size instead of the `max_lines` of the cursor, `TIMEOUT` applies to each page.
+    /// Available for all types whose response implements `WWResponse`, which includes the
+    /// responses generated by the derive macro.
+    ///
+    /// ## Example
+    ///
+    /// ```rust,ignore
+    /// use wwsvc_rs::{Parameters, WWSVCGetData};
+    ///
+    /// let mut client = client.create_cursor(500);
+    /// let mut pages = ArticleData::get_cursored(&mut client, Parameters::new());
+    /// while let Some(page) = pages.next().await {
+    ///     for article in page? {
+    ///         println!("{}", article.article_number);
+    ///     }
+    /// }
+    /// ```
+    fn get_cursored(
+        client: &mut crate::client::WebwareClient<crate::OpenCursor>,
+        mut parameters: Parameters,
+    ) -> crate::cursor::CursoredResponse<'_, Self, Self::Response>
+    where
+        Self: Sized,
+        Self::Response: WWResponse<Item = Self>,
+    {
+        Self::FIELDS_MERGE.apply(&mut parameters, Self::FIELDS);
+        let version = function_version(client, Self::FUNCTION, Self::VERSION, Self::AUTO_VERSION);
+        let mut pages = client.cursored_request(Self::METHOD, Self::FUNCTION, version, parameters);
+        if let Some(max_lines) = Self::MAX_LINES {
+            pages = pages.with_page_size(max_lines);
+        }
+        if let Some(timeout) = Self::TIMEOUT {
+            pages = pages.with_timeout(timeout);
+        }
+        pages
+    }
+}
+
+/// Trait for the WWSVCPutData derive macro.
+///
+/// Sends the members of a record as parameters of a writing function like `ARTIKEL.PUT`.
+#[cfg(feature = "derive")]
+#[wwsvc_rs::async_trait]
+pub trait WWSVCPutData: serde::Serialize + Sync {
+    /// The function name of the WWSVC request.
+    const FUNCTION: &'static str;
+    /// The version of the function.
+    const VERSION: u32 = 1;
+    /// Whether the version is taken from `WebwareClient::function_version()`, falling back to
+    /// `VERSION`.
+    const AUTO_VERSION: bool = false;
+    /// The function method of the WWSVC request.
+    const METHOD: reqwest::Method = reqwest::Method::PUT;
+    /// Widths of the fields in WEBWARE, checked by `to_parameters()`.
+    const FIELD_WIDTHS: &'static [crate::validation::FieldWidth] = &[];
+
+    /// Returns the parameters of this record, see `Parameters::from_serialize()`, checked
+    /// against `FIELD_WIDTHS`.
+    fn to_parameters(&self) -> WWClientResult<Parameters> {
+        crate::validation::checked_parameters(self, Self::FIELD_WIDTHS)
+    }
+
+    /// Writes this record to the server and returns the COMRESULT of the request.
+    ///
+    /// `parameters` are sent in addition to the members of the record and take precedence over
+    /// them, e.g. to pass the key of the record to update. Returns `WWSVCError::FunctionFailed`
+    /// if the COMRESULT doesn't have a success status.
+    async fn put(
+        &self,
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        parameters: Parameters,
+    ) -> WWClientResult<ComResult> {
+        #[derive(serde::Deserialize)]
+        struct PutResponse {
+            #[serde(rename = "COMRESULT")]
+            com_result: ComResult,
+        }
+
+        let mut record = self.to_parameters()?;
+        record.merge(&parameters, crate::parameters::MergePrecedence::Overwrite);
+        let version = function_version(client, Self::FUNCTION, Self::VERSION, Self::AUTO_VERSION);
+        let response: PutResponse = client
+            .request_generic(Self::METHOD, Self::FUNCTION, version, record, None)
+            .await?;
+        if !response.com_result.is_success() {
+            return Err(WWSVCError::FunctionFailed {
+                function: Self::FUNCTION.to_string(),
+                com_result: Box::new(response.com_result),
+            });
+        }
+        Ok(response.com_result)
+    }
+}
+
+/// Trait for the WWSVCInsertData derive macro.
+///
+/// Sends the members of a record as parameters of a creating function like `ARTIKEL.INSERT` and
+/// returns the created record.
+#[cfg(feature = "derive")]
+#[wwsvc_rs::async_trait]
+pub trait WWSVCInsertData: serde::Serialize + Sync {
+    /// The function name of the WWSVC request.
+    const FUNCTION: &'static str;
+    /// The version of the function.
+    const VERSION: u32 = 1;
+    /// Whether the version is taken from `WebwareClient::function_version()`, falling back to
+    /// `VERSION`.
+    const AUTO_VERSION: bool = false;
+    /// The function method of the WWSVC request.
+    const METHOD: reqwest::Method = reqwest::Method::PUT;
+    /// The member of the response containing the created record.
+    const CONTAINER: &'static str;
+    /// Widths of the fields in WEBWARE, checked by `to_parameters()`.
+    const FIELD_WIDTHS: &'static [crate::validation::FieldWidth] = &[];
+
+    /// The identifying fields of the created record, as returned by the server.
+    type Created: DeserializeOwned;
+
+    /// Returns the parameters of this record, see `Parameters::from_serialize()`, checked
+    /// against `FIELD_WIDTHS`.
+    fn to_parameters(&self) -> WWClientResult<Parameters> {
+        crate::validation::checked_parameters(self, Self::FIELD_WIDTHS)
+    }
+
+    /// Creates this record on the server and returns the created record.
+    ///
+    /// `parameters` are sent in addition to the members of the record and take precedence over
+    /// them. If the server returns a list, the first record is returned. Returns
+    /// `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status.
+    async fn insert(
+        &self,
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        parameters: Parameters,
+    ) -> WWClientResult<crate::responses::InsertResponse<Self::Created>> {
+        let mut record = self.to_parameters()?;
+        record.merge(&parameters, crate::parameters::MergePrecedence::Overwrite);
+        let version = function_version(client, Self::FUNCTION, Self::VERSION, Self::AUTO_VERSION);
+        let mut response: serde_json::Value = client
+            .request_generic(Self::METHOD, Self::FUNCTION, version, record, None)
+            .await?;
+
+        let com_result: ComResult = serde_json::from_value(
+            response
+                .get_mut("COMRESULT")
+                .map(serde_json::Value::take)
+                .ok_or_else(|| WWSVCError::MissingField("COMRESULT".to_string()))?,
+        )?;
+        if !com_result.is_success() {
+            return Err(WWSVCError::FunctionFailed {
+                function: Self::FUNCTION.to_string(),
+                com_result: Box::new(com_result),
+            });
+        }
+        let created = match response
+            .get_mut(Self::CONTAINER)
+            .map(serde_json::Value::take)
+        {
+            Some(serde_json::Value::Array(records)) => records.into_iter().next(),
+            other => other,
+        }
+        .ok_or_else(|| WWSVCError::MissingField(Self::CONTAINER.to_string()))?;
+        Ok(crate::responses::InsertResponse {
+            com_result,
+            record: serde_json::from_value(created)?,
+        })
+    }
+}
+
+/// Trait for the WWSVCDeleteData derive macro.
+///
+/// Deletes records with a deleting function like `ARTIKEL.DELETE`.
+#[cfg(feature = "derive")]
+#[wwsvc_rs::async_trait]
+pub trait WWSVCDeleteData {
+    /// The function name of the WWSVC request.
+    const FUNCTION: &'static str;
+    /// The version of the function.
+    const VERSION: u32 = 1;
+    /// Whether the version is taken from `WebwareClient::function_version()`, falling back to
+    /// `VERSION`.
+    const AUTO_VERSION: bool = false;
+    /// The function method of the WWSVC request.
+    const METHOD: reqwest::Method = reqwest::Method::PUT;
+
+    /// Deletes the record identified by `key_parameters` and returns the COMRESULT of the request.
+    ///
+    /// Returns `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status, e.g.
+    /// if the record doesn't exist.
+    async fn delete(
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        key_parameters: Parameters,
+    ) -> WWClientResult<ComResult> {
+        #[derive(serde::Deserialize)]
+        struct DeleteResponse {
+            #[serde(rename = "COMRESULT")]
+            com_result: ComResult,
+        }
+
+        let version = function_version(client, Self::FUNCTION, Self::VERSION, Self::AUTO_VERSION);
+        let response: DeleteResponse = client
+            .request_generic(Self::METHOD, Self::FUNCTION, version, key_parameters, None)
+            .await?;
+        if !response.com_result.is_success() {
+            return Err(WWSVCError::FunctionFailed {
+                function: Self::FUNCTION.to_string(),
+                com_result: Box::new(response.com_result),
+            });
+        }
+        Ok(response.com_result)
+    }
+}
+
+/// Operation on the records of a function group, as performed by `WWSVCRecord`.
+#[cfg(feature = "derive")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordOperation {
+    /// Reads records, `.GET` by default.
+    Get,
+    /// Creates a record, `.INSERT` by default.
+    Insert,
+    /// Updates a record, `.PUT` by default.
+    Update,
+    /// Deletes a record, `.DELETE` by default.
+    Delete,
+}
+
+#[cfg(feature = "derive")]
+impl RecordOperation {
+    /// All operations.
+    pub const ALL: [RecordOperation; 4] = [
+        RecordOperation::Get,
+        RecordOperation::Insert,
+        RecordOperation::Update,
+        RecordOperation::Delete,
+    ];
+
+    /// Returns the function suffix of the operation used by the WEBSERVICES.
+    pub fn default_suffix(&self) -> &'static str {
+        match self {
+            RecordOperation::Get => "GET",
+            RecordOperation::Insert => "INSERT",
+            RecordOperation::Update => "PUT",
+            RecordOperation::Delete => "DELETE",
+        }
+    }
+}
+
+/// Result of `WWSVCRecord::upsert()`.
+#[cfg(feature = "derive")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Upserted {
+    /// No record matched the key, so it has been created.
+    Inserted(crate::responses::InsertResponse<serde_json::Value>),
+    /// A record matched the key and has been updated.
+    Updated(ComResult),
+}
+
+#[cfg(feature = "derive")]
+impl Upserted {
+    /// Returns the COMRESULT of the `.INSERT` or `.PUT` request.
+    pub fn com_result(&self) -> &ComResult {
+        match self {
+            Upserted::Inserted(response) => &response.com_result,
+            Upserted::Updated(com_result) => com_result,
+        }
+    }
+
+    /// Returns whether the record has been created.
+    pub fn is_inserted(&self) -> bool {
+        matches!(self, Upserted::Inserted(_))
+    }
+}
+
+/// Trait for the WWSVCRecord derive macro.
+///
+/// Combines reading, creating, updating and deleting the records of a function group like
+/// `ARTIKEL`. The function of each operation is the function group followed by the suffix of the
+/// operation, e.g. `ARTIKEL.PUT` for updates. Operations which aren't contained in `OPERATIONS`
+/// fail with `WWSVCError::FunctionNotPermitted`.
+#[cfg(feature = "derive")]
+#[wwsvc_rs::async_trait]
+pub trait WWSVCRecord: serde::Serialize + DeserializeOwned + Send + Sync {
+    /// The function group, e.g. `ARTIKEL`.
+    const FUNCTION: &'static str;
+    /// The version of the functions.
+    const VERSION: u32 = 1;
+    /// Whether the version is taken from `WebwareClient::function_version()`, falling back to
+    /// `VERSION`.
+    const AUTO_VERSION: bool = false;
+    /// The fields requested by `get()`. If empty, the default fields of the server are returned.
+    const FIELDS: &'static str = "";
+    /// The operations which may be performed.
+    const OPERATIONS: &'static [RecordOperation] = &RecordOperation::ALL;
+    /// Widths of the fields in WEBWARE, checked by `to_parameters()`.
+    const FIELD_WIDTHS: &'static [crate::validation::FieldWidth] = &[];
+
+    /// Returns the parameters of this record, see `Parameters::from_serialize()`, checked
+    /// against `FIELD_WIDTHS`.
+    fn to_parameters(&self) -> WWClientResult<Parameters> {
+        crate::validation::checked_parameters(self, Self::FIELD_WIDTHS)
+    }
+
+    /// Returns the function suffix of the given operation.
+    fn suffix(operation: RecordOperation) -> &'static str {
+        operation.default_suffix()
+    }
+
+    /// Returns the HTTP method of the given operation.
+    fn method(_operation: RecordOperation) -> reqwest::Method {
+        reqwest::Method::PUT
+    }
+
+    /// Returns the name of the member of `.GET` responses containing the list of records.
+    fn list_name() -> String {
+        format!("{}LISTE", Self::FUNCTION)
+    }
+
+    /// Returns the name of the list of records inside the list member, which is also the member
+    /// containing the created record in `.INSERT` responses.
+    fn container_name() -> String {
+        Self::FUNCTION.to_string()
+    }
+
+    /// Reads the records matching `parameters`. `FIELDS` are sent as `FELDER`, unless the caller
+    /// provides `FELDER`.
+    ///
+    /// Returns no records if the response doesn't contain the list member, which the server omits
+    /// when no rows matched. Returns `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a
+    /// success status, so a failed request isn't mistaken for an empty result.
+    async fn get(
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        mut parameters: Parameters,
+    ) -> WWClientResult<Vec<Self>> {
+        FieldsMerge::RespectCaller.apply(&mut parameters, Self::FIELDS);
+        let response = record_request::<Self>(client, RecordOperation::Get, parameters).await?;
+        checked_com_result::<Self>(&response, RecordOperation::Get)?;
+        if response.get(Self::list_name().as_str()).is_none() {
+            return Ok(Vec::new());
+        }
+        response.list_as(&Self::list_name(), &Self::container_name())
+    }
+
+    /// Creates this record and returns the created record as returned by the server.
+    ///
+    /// `parameters` are sent in addition to the members of the record and take precedence over
+    /// them. Returns `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status.
+    async fn insert(
+        &self,
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        parameters: Parameters,
+    ) -> WWClientResult<crate::responses::InsertResponse<serde_json::Value>> {
+        let mut record = self.to_parameters()?;
+        record.merge(&parameters, crate::parameters::MergePrecedence::Overwrite);
+        let mut response = record_request::<Self>(client, RecordOperation::Insert, record).await?;
+        let com_result = checked_com_result::<Self>(&response, RecordOperation::Insert)?;
+        let created = match response
+            .get_mut(Self::container_name().as_str())
+            .map(serde_json::Value::take)
+        {
+            Some(serde_json::Value::Array(records)) => records.into_iter().next(),
+            other => other,
+        }
+        .ok_or_else(|| WWSVCError::MissingField(Self::container_name()))?;
+        Ok(crate::responses::InsertResponse {
+            com_result,
+            record: created,
+        })
+    }
+
+    /// Updates this record and returns the COMRESULT of the request.
+    ///
+    /// `parameters` are sent in addition to the members of the record and take precedence over
+    /// them, e.g. to pass the key of the record.
+    async fn update(
+        &self,
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        parameters: Parameters,
+    ) -> WWClientResult<ComResult> {
+        let mut record = self.to_parameters()?;
+        record.merge(&parameters, crate::parameters::MergePrecedence::Overwrite);
+        let response = record_request::<Self>(client, RecordOperation::Update, record).await?;
+        checked_com_result::<Self>(&response, RecordOperation::Update)
+    }
+
+    /// Updates the record identified by `key_parameters` with `data` if it exists, otherwise
+    /// creates it.
+    ///
+    /// Whether the record exists is checked with `get()`. `key_parameters` are sent with the
+    /// `.INSERT` or `.PUT` request as well. Fails with `WWSVCError::FunctionNotPermitted` before
+    /// any request is sent if one of the three operations isn't permitted.
+    async fn upsert(
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        key_parameters: Parameters,
+        data: &Self,
+    ) -> WWClientResult<Upserted> {
+        for operation in [
+            RecordOperation::Get,
+            RecordOperation::Insert,
+            RecordOperation::Update,
+        ] {
+            record_function::<Self>(operation)?;
+        }
+        // rejects invalid records before the existence check
+        data.to_parameters()?;
+        if Self::get(client, key_parameters.clone()).await?.is_empty() {
+            Ok(Upserted::Inserted(
+                data.insert(client, key_parameters).await?,
+            ))
+        } else {
+            Ok(Upserted::Updated(
+                data.update(client, key_parameters).await?,
+            ))
+        }
+    }
+
+    /// Deletes the record identified by `key_parameters` and returns the COMRESULT of the
+    /// request.
+    async fn delete(
+        client: &mut crate::client::WebwareClient<impl Ready + Send>,
+        key_parameters: Parameters,
+    ) -> WWClientResult<ComResult> {
+        let response =
+            record_request::<Self>(client, RecordOperation::Delete, key_parameters).await?;
+        checked_com_result::<Self>(&response, RecordOperation::Delete)
+    }
+}
+
+/// Returns the function of `operation` on the records of `R`, if the operation is permitted.
+#[cfg(feature = "derive")]
+fn record_function<R: WWSVCRecord>(operation: RecordOperation) -> WWClientResult<String> {
+    let function = format!("{}.{}", R::FUNCTION, R::suffix(operation));
+    match R::OPERATIONS.contains(&operation) {
+        true => Ok(function),
+        false => Err(WWSVCError::FunctionNotPermitted(function)),
+    }
+}
+
+/// Performs `operation` on the records of `R`.
+#[cfg(feature = "derive")]
+async fn record_request<R: WWSVCRecord>(
+    client: &mut crate::client::WebwareClient<impl Ready + Send>,
+    operation: RecordOperation,
+    parameters: Parameters,
+) -> WWClientResult<serde_json::Value> {
+    let function = record_function::<R>(operation)?;
+    let version = function_version(client, &function, R::VERSION, R::AUTO_VERSION);
+    client
+        .request_generic(R::method(operation), &function, version, parameters, None)
+        .await
+}
+
+/// Returns the COMRESULT of a response, failing if it doesn't have a success status.
+#[cfg(feature = "derive")]
+fn checked_com_result<R: WWSVCRecord>(
+    response: &serde_json::Value,
+    operation: RecordOperation,
+) -> WWClientResult<ComResult> {
+    let com_result = WWValueExt::com_result(response)?;
+    if !com_result.is_success() {
+        return Err(WWSVCError::FunctionFailed {
+            function: format!("{}.{}", R::FUNCTION, R::suffix(operation)),
+            com_result: Box::new(com_result),
+        });
+    }
+    Ok(com_result)
 }
@@ -0,0 +1,106 @@
+use crate::{Parameters, WWClientResult, WWSVCError};
+
+/// Validation of a record before it is written to the server.
+///
+/// With `#[wwsvc(validate)]`, the `WWSVCPutData`, `WWSVCInsertData` and `WWSVCRecord` derives call
+/// `validate()` before the record is serialized, so invalid values are rejected locally instead of
+/// with a COMRESULT error of the server. Invalid values are usually reported with
+/// `WWSVCError::InvalidField`, e.g. by the helpers of this module.
+///
+/// ## Example
+///
+/// ```rust
+/// use wwsvc_rs::validation::{self, Validate};
+/// use wwsvc_rs::{WWClientResult, WWSVCError};
+///
+/// pub struct ArticleUpdate {
+///     pub article_number: String,
+///     pub description: String,
+/// }
+///
+/// impl Validate for ArticleUpdate {
+///     fn validate(&self) -> WWClientResult<()> {
+///         validation::required("ARTNR", &self.article_number)?;
+///         validation::max_chars("ART_1_10", &self.description, 40)
+///     }
+/// }
+///
+/// let update = ArticleUpdate {
+///     article_number: String::new(),
+///     description: "Schraube".to_string(),
+/// };
+/// assert!(matches!(update.validate(), Err(WWSVCError::InvalidField { .. })));
+/// ```
+pub trait Validate {
+    /// Returns an error if the record can't be written.
+    fn validate(&self) -> WWClientResult<()>;
+}
+
+/// Fails if `value` is empty or consists of whitespace only.
+pub fn required(field: &str, value: &str) -> WWClientResult<()> {
+    if value.trim().is_empty() {
+        return Err(WWSVCError::InvalidField {
+            field: field.to_string(),
+            reason: "must not be empty".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Fails if `value` has more than `max` characters, e.g. the width of the field in WEBWARE.
+pub fn max_chars(field: &str, value: &str, max: usize) -> WWClientResult<()> {
+    let chars = value.chars().count();
+    if chars > max {
+        return Err(WWSVCError::InvalidField {
+            field: field.to_string(),
+            reason: format!("has {} characters, at most {} are allowed", chars, max),
+        });
+    }
+    Ok(())
+}
+
+/// Width of a field in WEBWARE, declared with `#[wwsvc(max_len = 25)]` on a field of a write
+/// derive.
+///
+/// The widths are checked by `to_parameters()` of `WWSVCPutData`, `WWSVCInsertData` and
+/// `WWSVCRecord`. Values exceeding the width are rejected with `WWSVCError::InvalidField`, or
+/// truncated if the field is marked with `#[wwsvc(truncate)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldWidth {
+    /// The name of the parameter, e.g. `ART_1_25`.
+    pub name: &'static str,
+    /// The maximum number of characters.
+    pub max_len: usize,
+    /// Whether longer values are truncated instead of rejected.
+    pub truncate: bool,
+}
+
+impl FieldWidth {
+    /// Checks the parameter of this field in `parameters`, truncating it if allowed.
+    pub fn apply(&self, parameters: &mut Parameters) -> WWClientResult<()> {
+        let Some(value) = parameters.get(self.name) else {
+            return Ok(());
+        };
+        if value.chars().count() <= self.max_len {
+            return Ok(());
+        }
+        if !self.truncate {
+            return max_chars(self.name, value, self.max_len);
+        }
+        let truncated = value.chars().take(self.max_len).collect::<String>();
+        parameters.insert(self.name, truncated);
+        Ok(())
+    }
+}
+
+/// Serializes `record` to parameters and applies `widths` to them.
+pub fn checked_parameters<T: serde::Serialize + ?Sized>(
+    record: &T,
+    widths: &[FieldWidth],
+) -> WWClientResult<Parameters> {
+    let mut parameters = Parameters::from_serialize(record)?;
+    for width in widths {
+        width.apply(&mut parameters)?;
+    }
+    Ok(parameters)
+}
@@ -0,0 +1,51 @@
+use std::future::Future;
+
+use futures::{Stream, StreamExt};
+use tokio::task::JoinHandle;
+
+use crate::client::states::Registered;
+use crate::WebwareClient;
+
+/// Spawns a pool of workers which processes the jobs received from `rx` with `handler`, running
+/// at most `concurrency` handlers at the same time.
+///
+/// Each handler gets its own clone of `client`, so request IDs and cursors don't interfere. The
+/// pool shuts down once `rx` has ended, e.g. because all senders have been dropped, and the
+/// handlers in flight have finished. Aborting the returned handle cancels all handlers in flight.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use futures::channel::mpsc;
+/// use wwsvc_rs::{workers::spawn_workers, Parameters, WebwareClient};
+///
+/// # async fn example(client: WebwareClient<wwsvc_rs::Registered>) {
+/// let (tx, rx) = mpsc::unbounded::<String>();
+/// let pool = spawn_workers(client, rx, 4, |mut client, article_number| async move {
+///     let parameters = Parameters::new().param("ARTNR", &article_number);
+///     if let Err(e) = client.request(None, "ARTIKEL.GET", 1, parameters, None).await {
+///         eprintln!("{}: {}", article_number, e);
+///     }
+/// });
+///
+/// tx.unbounded_send("Artikel19Prozent".to_string()).unwrap();
+/// drop(tx);
+/// pool.await.unwrap();
+/// # }
+/// ```
+pub fn spawn_workers<T, F, Fut>(
+    client: WebwareClient<Registered>,
+    rx: impl Stream<Item = T> + Send + 'static,
+    concurrency: usize,
+    handler: F,
+) -> JoinHandle<()>
+where
+    T: Send + 'static,
+    F: Fn(WebwareClient<Registered>, T) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        rx.for_each_concurrent(concurrency.max(1), move |job| handler(client.clone(), job))
+            .await
+    })
+}
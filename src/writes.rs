@@ -0,0 +1,158 @@
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::client::WebwareClient;
+use crate::responses::ComResult;
+use crate::traits::{function_version, WWResponse, WWSVCGetData};
+use crate::{Parameters, Ready, WWClientResult, WWSVCError};
+
+/// Update of a record, which only sends the fields that have been changed since the record was
+/// loaded.
+///
+/// WEBWARE overwrites every field passed to a `.PUT` function, so sending a whole record which
+/// has been loaded a while ago would revert changes made in the meantime, e.g. to the stock.
+/// The builder keeps the fields as loaded and compares them to the modified record, named by
+/// their serde names. Fields which have been set to `None` are sent empty, to clear them.
+///
+/// ## Example
+///
+/// ```rust,ignore
+/// use wwsvc_rs::writes::UpdateBuilder;
+/// use wwsvc_rs::collection;
+///
+/// let update = UpdateBuilder::<ArticleData>::load(&mut client, collection! { "ARTNR" => "A1" })
+///     .await?
+///     .expect("article not found")
+///     .modify(|article| article.description = "Neue Bezeichnung".to_string());
+/// update.send(&mut client).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct UpdateBuilder<T> {
+    record: T,
+    original: Parameters,
+    key_parameters: Parameters,
+    method: Method,
+}
+
+impl<T> UpdateBuilder<T>
+where
+    T: WWSVCGetData + Serialize + Send + Sync,
+{
+    /// Loads the record identified by `key_parameters` with `WWSVCGetData::get()`. Returns
+    /// `None` if no record matches.
+    ///
+    /// `key_parameters` are sent with the update as well, to identify the record.
+    pub async fn load(
+        client: &mut WebwareClient<impl Ready + Send>,
+        key_parameters: Parameters,
+    ) -> WWClientResult<Option<Self>>
+    where
+        T::Response: WWResponse<Item = T>,
+    {
+        let response = T::get(client, key_parameters.clone()).await?;
+        match response.into_items().into_iter().next() {
+            Some(record) => Ok(Some(Self::from_record(record, key_parameters)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Creates an update of a record which has been loaded before. Changes are detected relative
+    /// to the record as passed.
+    pub fn from_record(record: T, key_parameters: Parameters) -> WWClientResult<Self> {
+        Ok(Self {
+            original: Parameters::from_serialize(&record)?,
+            record,
+            key_parameters,
+            method: T::METHOD,
+        })
+    }
+
+    /// Sets the HTTP method of the update. Defaults to the method of `WWSVCGetData`.
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Modifies the record and returns the builder for chaining.
+    pub fn modify(mut self, f: impl FnOnce(&mut T)) -> Self {
+        f(&mut self.record);
+        self
+    }
+
+    /// Returns the record.
+    pub fn record(&self) -> &T {
+        &self.record
+    }
+
+    /// Returns the record mutably.
+    pub fn record_mut(&mut self) -> &mut T {
+        &mut self.record
+    }
+
+    /// Returns the `.PUT` function of the record, e.g. `ARTIKEL.PUT` for `ARTIKEL.GET`.
+    pub fn function(&self) -> String {
+        let group = T::FUNCTION
+            .rsplit_once('.')
+            .map_or(T::FUNCTION, |(group, _)| group);
+        format!("{}.PUT", group)
+    }
+
+    /// Returns the fields which have been changed since the record was loaded.
+    pub fn changes(&self) -> WWClientResult<Parameters> {
+        let current = Parameters::from_serialize(&self.record)?;
+        let mut changes = current
+            .iter()
+            .filter(|field| self.original.get(&field.name) != Some(field.content.as_str()))
+            .cloned()
+            .collect::<Parameters>();
+        for field in self.original.iter() {
+            if !current.contains(&field.name) {
+                changes.insert(&field.name, "");
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Returns whether any field has been changed.
+    pub fn is_dirty(&self) -> WWClientResult<bool> {
+        Ok(!self.changes()?.is_empty())
+    }
+
+    /// Sends the changed fields together with the key parameters to the `.PUT` function and
+    /// returns the COMRESULT. Returns `None` without sending a request if nothing has changed.
+    ///
+    /// Returns `WWSVCError::FunctionFailed` if the COMRESULT doesn't have a success status.
+    pub async fn send(
+        self,
+        client: &mut WebwareClient<impl Ready + Send>,
+    ) -> WWClientResult<Option<ComResult>> {
+        #[derive(serde::Deserialize)]
+        struct PutResponse {
+            #[serde(rename = "COMRESULT")]
+            com_result: ComResult,
+        }
+
+        let mut parameters = self.changes()?;
+        if parameters.is_empty() {
+            return Ok(None);
+        }
+        // the keys identify the record, even if a key field has been changed
+        parameters.merge(
+            &self.key_parameters,
+            crate::parameters::MergePrecedence::Overwrite,
+        );
+
+        let function = self.function();
+        let version = function_version(client, &function, T::VERSION, T::AUTO_VERSION);
+        let response: PutResponse = client
+            .request_generic(self.method, &function, version, parameters, None)
+            .await?;
+        if !response.com_result.is_success() {
+            return Err(WWSVCError::FunctionFailed {
+                function,
+                com_result: Box::new(response.com_result),
+            });
+        }
+        Ok(Some(response.com_result))
+    }
+}
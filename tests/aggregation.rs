@@ -0,0 +1,93 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::aggregation::{AggregateFunction, Aggregation};
+use wwsvc_rs::{ErrorCategory, Parameters, WWSVCError};
+
+#[test]
+fn test_aggregation_parameters() {
+    let mut parameters = Parameters::new().param("FELDER", "ART_1_10");
+    Aggregation::new()
+        .group_by("ART_1_10")
+        .group_by("ART_1_3")
+        .sum("ART_1_80")
+        .avg("ART_3_15")
+        .apply(&mut parameters);
+    assert_eq!(parameters.get("GRUPPIERUNG"), Some("ART_1_10,ART_1_3"));
+    assert_eq!(
+        parameters.get("AGGREGATION"),
+        Some("SUMME(ART_1_80),DURCHSCHNITT(ART_3_15)")
+    );
+
+    let mut parameters = Parameters::new();
+    Aggregation::new().apply(&mut parameters);
+    assert!(parameters.is_empty());
+}
+
+#[tokio::test]
+async fn test_aggregate() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {"ARTIKEL": [
+            {"ART_1_10": "SCHRAUBEN", "SUMME_ART_1_80": 120.5, "ANZAHL_ART_1_25": "4"},
+            {"ART_1_10": "MUTTERN", "SUMME_ART_1_80": "7,25", "ANZAHL_ART_1_25": 2}
+        ]}
+    })));
+
+    let mut client = server.client().await;
+    let rows = client
+        .aggregate(
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            &Aggregation::new()
+                .group_by("ART_1_10")
+                .sum("ART_1_80")
+                .count("ART_1_25"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].group("ART_1_10").as_deref(), Some("SCHRAUBEN"));
+    assert_eq!(rows[0].sum("ART_1_80"), Some(120.5));
+    assert_eq!(rows[0].count("ART_1_25"), Some(4));
+    assert_eq!(rows[1].sum("ART_1_80"), Some(7.25));
+    assert_eq!(
+        rows[1].value(AggregateFunction::Count, "ART_1_25"),
+        Some(2.0)
+    );
+    assert_eq!(rows[1].value(AggregateFunction::Max, "ART_1_80"), None);
+
+    let request = server.requests()[0].json();
+    let parameters = &request["WWSVC_FUNCTION"]["PARAMETER"];
+    assert_eq!(parameters[0]["PNAME"], "GRUPPIERUNG");
+    assert_eq!(parameters[0]["PCONTENT"], "ART_1_10");
+    assert_eq!(parameters[1]["PNAME"], "AGGREGATION");
+    assert_eq!(
+        parameters[1]["PCONTENT"],
+        "SUMME(ART_1_80),ANZAHL(ART_1_25)"
+    );
+}
+
+#[tokio::test]
+async fn test_aggregate_unsupported() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 400, "CODE": "Bad Request", "INFO": "Unbekannter Parameter"}
+    })));
+
+    let mut client = server.client().await;
+    let error = client
+        .aggregate(
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            &Aggregation::new().sum("ART_1_80"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WWSVCError::FunctionFailed { .. }));
+    assert_eq!(error.category(), ErrorCategory::Client);
+}
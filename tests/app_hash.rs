@@ -0,0 +1,55 @@
+mod common;
+
+use std::time::{Duration, SystemTime};
+
+use common::MockServer;
+use wwsvc_rs::test_util::fix_app_hash;
+use wwsvc_rs::{AppHash, Parameters};
+
+#[test]
+fn test_fixed_app_hash() {
+    let date = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let expected = AppHash::at(1, "salt", date);
+
+    {
+        let _guard = fix_app_hash(date, Some("salt"));
+        let app_hash = AppHash::for_request(1, "secret");
+        assert_eq!(app_hash.hash, expected.hash);
+        assert_eq!(app_hash.date_formatted, "Tue, 14 Nov 2023 22:13:20 GMT");
+
+        let _guard = fix_app_hash(date, None);
+        assert_eq!(
+            AppHash::for_request(1, "secret").hash,
+            AppHash::at(1, "secret", date).hash
+        );
+    }
+
+    assert_ne!(
+        AppHash::for_request(1, "salt").date_formatted,
+        expected.date_formatted
+    );
+}
+
+#[tokio::test]
+async fn test_golden_request() {
+    let server = MockServer::start().await;
+    let _guard = fix_app_hash(SystemTime::UNIX_EPOCH, Some("salt"));
+
+    let mut signed = Vec::new();
+    for _ in 0..2 {
+        let client = server.client().await;
+        let prepared = client.prepare(
+            "ARTIKEL.GET",
+            1,
+            Parameters::new().param("ARTNR", "Artikel19Prozent"),
+        );
+        let (_, headers, body) = client.clone().sign(&prepared).unwrap();
+        signed.push((headers, serde_json::to_vec(&body).unwrap()));
+    }
+
+    assert_eq!(signed[0], signed[1]);
+    assert_eq!(
+        signed[0].0.get("WWSVC-TS").unwrap(),
+        "Thu, 01 Jan 1970 00:00:00 GMT"
+    );
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::asynchron::{AsyncResult, AsyncState};
+use wwsvc_rs::Parameters;
+
+#[test]
+fn test_async_state() {
+    assert_eq!(AsyncState::from("finished"), AsyncState::Done);
+    assert_eq!(AsyncState::from("ERROR"), AsyncState::Failed);
+    assert_eq!(
+        AsyncState::from("paused"),
+        AsyncState::Other("PAUSED".to_string())
+    );
+    assert!(AsyncState::Cancelled.is_terminal());
+    assert!(!AsyncState::Running.is_terminal());
+    assert!(!AsyncState::Other("PAUSED".to_string()).is_terminal());
+    assert!(AsyncState::Done.is_success());
+    assert!(!AsyncState::Failed.is_success());
+}
+
+#[tokio::test]
+async fn test_request_async() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    server.enqueue(MockResponse::json(json!({
+        "TICKET": {"ID": "T1", "STATUS": "QUEUED"}
+    })));
+    let ticket = client
+        .request_async(
+            None,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new().param("ARTNR", "Artikel19Prozent"),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ticket.id, "T1");
+    assert_eq!(ticket.state, AsyncState::Queued);
+    assert_eq!(ticket.to_string(), "T1 (QUEUED)");
+
+    server.enqueue(MockResponse::json(json!({
+        "TICKET": {"ID": "T1", "STATUS": "RUNNING"}
+    })));
+    server.enqueue(MockResponse::json(json!({
+        "TICKET": {"ID": "T1", "STATUS": "DONE"},
+        "RESULT": {"ARTIKELLISTE": {"ARTIKEL": [{"ARTNR": "Artikel19Prozent"}]}}
+    })));
+    let running: AsyncResult<serde_json::Value> = client.fetch_async_result(&ticket).await.unwrap();
+    assert!(!running.is_terminal());
+    assert!(running.result.is_none());
+    let done: AsyncResult<serde_json::Value> = client.fetch_async_result(&ticket).await.unwrap();
+    assert!(done.is_terminal());
+    assert_eq!(
+        done.result.unwrap()["ARTIKELLISTE"]["ARTIKEL"][0]["ARTNR"],
+        "Artikel19Prozent"
+    );
+
+    let requests = server.requests();
+    let request = &requests[requests.len() - 3];
+    assert_eq!(request.header("WWSVC-EXECUTE-MODE"), Some("ASYNCHRON"));
+    assert_eq!(
+        request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"],
+        "ASYNCHRON"
+    );
+    let poll = &requests[requests.len() - 1].json()["WWSVC_FUNCTION"];
+    assert_eq!(poll["FUNCTIONNAME"], "TICKET.GET");
+    assert_eq!(poll["PARAMETER"][0]["PCONTENT"], "T1");
+    // polling is synchronous
+    assert_eq!(
+        requests[requests.len() - 1].header("WWSVC-EXECUTE-MODE"),
+        Some("SYNCHRON")
+    );
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{MockResponse, MockServer};
+use wwsvc_rs::registry::set_registration_limit;
+use wwsvc_rs::{BackoffPolicy, ErrorCategory, WWSVCError, WebwareClient};
+
+#[test]
+fn test_backoff_delays() {
+    let policy = BackoffPolicy {
+        initial_delay: Duration::from_millis(100),
+        max_delay: Duration::from_secs(1),
+        multiplier: 2.0,
+        max_attempts: 10,
+    };
+
+    assert_eq!(policy.delay_for(0), Duration::from_millis(100));
+    assert_eq!(policy.delay_for(1), Duration::from_millis(200));
+    assert_eq!(policy.delay_for(3), Duration::from_millis(800));
+    assert_eq!(policy.delay_for(4), Duration::from_secs(1));
+    assert_eq!(policy.delay_for(u32::MAX), Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_register_with_backoff_gives_up() {
+    let client = WebwareClient::builder()
+        .webware_url("http://127.0.0.1:1")
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+
+    let policy = BackoffPolicy {
+        initial_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(5),
+        multiplier: 2.0,
+        max_attempts: 3,
+    };
+
+    let result = client.register_with_backoff(&policy).await;
+    assert_eq!(
+        result.err().map(|e| e.category()),
+        Some(ErrorCategory::Network)
+    );
+}
+
+#[tokio::test]
+async fn test_register_with_backoff_attempts() {
+    let server = MockServer::start().await;
+    for _ in 0..3 {
+        server.enqueue(MockResponse::text("text/plain", "unavailable").status(503));
+    }
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+
+    let policy = BackoffPolicy {
+        initial_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(5),
+        multiplier: 2.0,
+        max_attempts: 3,
+    };
+
+    assert!(client.register_with_backoff(&policy).await.is_err());
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests
+        .iter()
+        .all(|r| r.path.contains("/WWSERVICE/REGISTER/vendor/")));
+}
+
+#[tokio::test]
+async fn test_register_with_backoff_client_error() {
+    let server = MockServer::start().await;
+    set_registration_limit("backoff-vendor", "app", Some(0));
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("backoff-vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+
+    // a retry would wait for a minute
+    let policy = BackoffPolicy {
+        initial_delay: Duration::from_secs(60),
+        max_delay: Duration::from_secs(60),
+        multiplier: 2.0,
+        max_attempts: 3,
+    };
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.register_with_backoff(&policy),
+    )
+    .await
+    .expect("client errors must not be retried");
+    let error = result.err().unwrap();
+    assert!(matches!(
+        error,
+        WWSVCError::RegistrationLimitExceeded { .. }
+    ));
+    assert_eq!(error.category(), ErrorCategory::Client);
+    assert!(server.requests().is_empty());
+}
@@ -1,6 +1,13 @@
+mod common;
+
+use common::{default_response, MockServer};
 use futures::FutureExt;
 use reqwest::Method;
-use wwsvc_rs::{collection, generate_get_response};
+use wwsvc_rs::request_id::{RequestId, RequestIdGenerator, UuidIds};
+use wwsvc_rs::requests::{BodySizeLimit, FunctionPolicy, MethodPolicy};
+use wwsvc_rs::{
+    collection, generate_get_response, Credentials, Parameters, WWSVCError, WebwareClient,
+};
 
 #[derive(Debug, serde::Deserialize, Clone)]
 pub struct ArticleData {
@@ -52,3 +59,1040 @@ async fn test_articles() {
         std::env::var("TEST_ARTNR").unwrap().as_str()
     );
 }
+
+#[tokio::test]
+async fn test_request_id_continuity() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    let headers = client.headers_for(1, None).unwrap();
+    assert_eq!(headers.get("WWSVC-REQID").unwrap(), "1");
+    client.headers_for(2, None).unwrap();
+    assert_eq!(client.current_request_id(), 0);
+
+    for _ in 0..3 {
+        client
+            .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+            .await
+            .unwrap();
+    }
+    assert_eq!(client.current_request_id(), 3);
+
+    let requests = server.requests();
+    for (i, request) in requests.iter().enumerate() {
+        let expected = (i + 1).to_string();
+        assert_eq!(request.header("WWSVC-REQID"), Some(expected.as_str()));
+        assert_eq!(
+            request.json()["WWSVC_PASSINFO"]["REQUESTID"],
+            serde_json::json!(i + 1)
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_function_max_lines() {
+    let server = MockServer::start().await;
+    let mut client = wwsvc_rs::WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(wwsvc_rs::Credentials::new("service-pass", "app-id"))
+        .function_max_lines(collection! {
+            "artikel.info" => 1,
+        })
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    for function in ["ARTIKEL.INFO", "ARTIKEL.GET"] {
+        client
+            .request(Method::PUT, function, 1, Parameters::new(), None)
+            .await
+            .unwrap();
+    }
+    client
+        .request(
+            Method::PUT,
+            "ARTIKEL.INFO",
+            1,
+            Parameters::new(),
+            Some(collection! {
+                "WWSVC-ACCEPT-RESULT-MAX-LINES" => "5",
+            }),
+        )
+        .await
+        .unwrap();
+
+    let max_lines = server
+        .requests()
+        .iter()
+        .map(|request| {
+            request
+                .header("WWSVC-ACCEPT-RESULT-MAX-LINES")
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(max_lines, vec!["1", "1000", "5"]);
+}
+
+#[tokio::test]
+async fn test_rest_get() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    let response = client
+        .rest_get::<serde_json::Value>("/REST/ARTIKEL/", &[("ARTNR", "A 1")])
+        .await
+        .unwrap();
+    assert_eq!(response["COMRESULT"]["STATUS"], 200);
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "GET");
+    assert_eq!(request.path, "/WWSVC/REST/ARTIKEL/?ARTNR=A+1");
+    assert_eq!(request.header("WWSVC-SERVICEPASS"), Some("service-pass"));
+    assert_eq!(request.header("WWSVC-REQID"), Some("1"));
+    assert!(request.header("WWSVC-HASH").is_some());
+}
+
+#[tokio::test]
+async fn test_body_order() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    client
+        .request(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            collection! {
+                "ARTNR" => "A1",
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let body = String::from_utf8(server.requests()[0].body.clone()).unwrap();
+    let positions = [
+        "WWSVC_FUNCTION",
+        "FUNCTIONNAME",
+        "PARAMETER",
+        "PNAME",
+        "PCONTENT",
+        "REVISION",
+        "WWSVC_PASSINFO",
+        "SERVICEPASS",
+        "APPHASH",
+        "TIMESTAMP",
+        "REQUESTID",
+        "EXECUTE_MODE",
+    ]
+    .map(|key| body.find(&format!("\"{}\"", key)).unwrap());
+    assert!(
+        positions.windows(2).all(|pair| pair[0] < pair[1]),
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn test_method_policy() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .method_policy(MethodPolicy {
+            get: Method::POST,
+            mutate: Method::PATCH,
+        })
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    for (method, function) in [
+        (None, "ARTIKEL.GET"),
+        (None, "ARTIKEL.PUT"),
+        (Some(Method::PUT), "ARTIKEL.GET"),
+    ] {
+        client
+            .request(method, function, 1, Parameters::new(), None)
+            .await
+            .unwrap();
+    }
+
+    let methods = server
+        .requests()
+        .iter()
+        .map(|r| r.method.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(methods, vec!["POST", "PATCH", "PUT"]);
+
+    // streamed uploads use the method policy as well
+    let content = b"\x00\x01binary content".to_vec();
+    let response = client
+        .upload_stream(
+            "ARTIKELBILD.INSERT",
+            1,
+            Parameters::new().param("ARTNR", "A1"),
+            "bild.png",
+            std::io::Cursor::new(content.clone()),
+        )
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, "PATCH");
+    assert_eq!(request.header("Transfer-Encoding"), Some("chunked"));
+    assert!(request
+        .header("Content-Type")
+        .unwrap()
+        .starts_with("multipart/form-data; boundary="));
+    let body = String::from_utf8_lossy(&request.body);
+    assert!(body.contains("name=\"WWSVC_REQUEST\""));
+    assert!(body.contains("\"FUNCTIONNAME\":\"ARTIKELBILD.INSERT\""));
+    assert!(body.contains("name=\"FILE\"; filename=\"bild.png\""));
+    assert!(request
+        .body
+        .windows(content.len())
+        .any(|window| window == content.as_slice()));
+}
+
+#[tokio::test]
+async fn test_uuid_request_ids() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .request_ids(UuidIds)
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        client
+            .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+            .await
+            .unwrap();
+    }
+    assert_eq!(client.current_request_id(), 2);
+
+    let ids = server
+        .requests()
+        .iter()
+        .map(|request| {
+            let id = request.header("WWSVC-REQID").unwrap().to_string();
+            assert_eq!(request.json()["WWSVC_PASSINFO"]["REQUESTID"], id.as_str());
+            assert_eq!(id.len(), 36);
+            id
+        })
+        .collect::<Vec<_>>();
+    assert_ne!(ids[0], ids[1]);
+}
+
+#[derive(Debug)]
+struct FailingIds;
+
+impl RequestIdGenerator for FailingIds {
+    fn generate(&self, _sequence: u32) -> Result<RequestId, WWSVCError> {
+        Err(WWSVCError::InvalidHeader)
+    }
+}
+
+#[tokio::test]
+async fn test_failing_request_ids() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .request_ids(FailingIds)
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    let result = client
+        .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await;
+    assert!(matches!(result, Err(WWSVCError::InvalidHeader)));
+    assert_eq!(client.current_request_id(), 0);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_endpoint_override() {
+    let server = MockServer::start().await;
+    let canary = MockServer::start().await;
+    let mut client = server.client().await;
+    assert_eq!(
+        client.execjson_url().unwrap().as_str(),
+        format!("{}/WWSVC/EXECJSON", server.url)
+    );
+
+    let canary_url = reqwest::Url::parse(&format!("{}/WWSVC/EXECJSON", canary.url)).unwrap();
+    client.override_next_endpoint(canary_url);
+    for _ in 0..2 {
+        client
+            .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(canary.requests().len(), 1);
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(canary.requests()[0].header("WWSVC-REQID"), Some("1"));
+    assert_eq!(server.requests()[0].header("WWSVC-REQID"), Some("2"));
+}
+
+#[tokio::test]
+async fn test_webware_url_types() {
+    let server = MockServer::start().await;
+    let address = server.url.trim_start_matches("http://");
+    let (host, port) = address.split_once(':').unwrap();
+    let port = port.parse::<u16>().unwrap();
+
+    let ip = host.parse::<std::net::IpAddr>().unwrap();
+    let clients = [
+        WebwareClient::builder().webware_url((ip, port, "http")),
+        WebwareClient::builder().webware_url(reqwest::Url::parse(&server.url).unwrap()),
+    ];
+    for builder in clients {
+        let mut client = builder
+            .vendor_hash("vendor")
+            .app_hash("app")
+            .secret("1")
+            .revision(1)
+            .credentials(Credentials::new("service-pass", "app-id"))
+            .build()
+            .register()
+            .await
+            .unwrap();
+        assert_eq!(
+            client.execjson_url().unwrap().as_str(),
+            format!("{}/WWSVC/EXECJSON", server.url)
+        );
+        client
+            .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+            .await
+            .unwrap();
+    }
+    assert_eq!(server.requests().len(), 2);
+
+    let mut client = WebwareClient::builder()
+        .webware_url("not a url")
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .build()
+        .register()
+        .await
+        .unwrap();
+    let result = client
+        .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await;
+    assert!(matches!(result, Err(WWSVCError::UrlParseError(_))));
+}
+
+#[tokio::test]
+async fn test_read_only() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .read_only(true)
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    client
+        .request(None, "artikel.get", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let result = client
+        .request(None, "ARTIKEL.PUT", 1, Parameters::new(), None)
+        .await;
+    assert!(matches!(result, Err(WWSVCError::ReadOnly(function)) if function == "ARTIKEL.PUT"));
+    // unknown functions are refused, even if they aren't classified as mutations
+    let result = client
+        .request(None, "BELEG.BUCHEN", 1, Parameters::new(), None)
+        .await;
+    assert!(matches!(result, Err(WWSVCError::ReadOnly(function)) if function == "BELEG.BUCHEN"));
+    assert!(client
+        .sign(&client.prepare("ARTIKEL.DELETE", 1, Parameters::new()))
+        .is_err());
+
+    assert_eq!(server.requests().len(), 1);
+    assert_eq!(client.current_request_id(), 1);
+
+    // reading functions which don't end in `.GET` are permitted as well
+    client.service_pass_info().await.unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(
+        request.json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "SERVICEPASS.INFO"
+    );
+}
+
+#[test]
+fn test_is_read_only_function() {
+    use wwsvc_rs::requests::is_read_only_function;
+
+    for function in ["ARTIKEL.GET", "artikel.get", "SERVICEPASS.INFO"] {
+        assert!(is_read_only_function(function), "{}", function);
+    }
+    for function in [
+        "ARTIKEL.PUT",
+        "BELEG.BUCHEN",
+        "ARTIKEL.GETALL",
+        "REPORT.EXEC",
+    ] {
+        assert!(!is_read_only_function(function), "{}", function);
+    }
+}
+
+#[test]
+fn test_is_mutation() {
+    use wwsvc_rs::events::is_mutation;
+
+    for function in [
+        "ARTIKEL.PUT",
+        "artikel.insert",
+        "ARTIKEL.DELETE",
+        "ARTIKEL.UPDATE",
+    ] {
+        assert!(is_mutation(function), "{}", function);
+    }
+    for function in [
+        "ARTIKEL.GET",
+        "SERVICEPASS.INFO",
+        "TICKET.GET",
+        "ARTIKEL.GETALL",
+    ] {
+        assert!(!is_mutation(function), "{}", function);
+    }
+    assert_eq!(
+        wwsvc_rs::requests::MethodPolicy {
+            get: Method::PUT,
+            mutate: Method::POST,
+        }
+        .method_for("SERVICEPASS.INFO"),
+        Method::PUT
+    );
+}
+
+#[tokio::test]
+async fn test_function_policy() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .function_policy(FunctionPolicy::default().allow("ARTIKEL.*"))
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    client
+        .request(None, "ARTIKEL.PUT", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let result = client
+        .request(None, "BELEG.GET", 1, Parameters::new(), None)
+        .await;
+    assert!(
+        matches!(result, Err(WWSVCError::FunctionNotPermitted(function)) if function == "BELEG.GET")
+    );
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_body_size_limit() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .body_size_limit(BodySizeLimit::max(1024))
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let keys = (0..500)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let result = client
+        .request(
+            None,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new()
+                .param("FELDER", "ART_1_25")
+                .param("ARTNR", keys),
+            None,
+        )
+        .await;
+    match result {
+        Err(WWSVCError::BodyTooLarge {
+            size,
+            limit,
+            parameter,
+            ..
+        }) => {
+            assert!(size > 1024);
+            assert_eq!(limit, 1024);
+            assert_eq!(parameter, "ARTNR");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_batch_body_size_limit() {
+    use wwsvc_rs::requests::{BatchRequest, ServiceFunction};
+
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .body_size_limit(BodySizeLimit::max(1024))
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    // each function is small, but the batch as a whole exceeds the limit
+    let batch = (0..20)
+        .map(|i| {
+            ServiceFunction::new("ARTIKEL.INSERT", 1)
+                .parameter("ARTNR", format!("A{}", i))
+                .parameter("ART_1_25", if i == 7 { "long name" } else { "name" })
+        })
+        .collect::<BatchRequest>();
+    match client.send_batch(&batch).await {
+        Err(WWSVCError::BodyTooLarge {
+            function,
+            size,
+            limit,
+            parameter,
+        }) => {
+            assert_eq!(function, "ARTIKEL.INSERT");
+            assert!(size > 1024);
+            assert_eq!(limit, 1024);
+            assert_eq!(parameter, "ART_1_25");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_request_chunks() {
+    let server = MockServer::start().await;
+    for numbers in [["A1", "A2"], ["A3", "A4"]] {
+        server.enqueue(common::MockResponse::json(serde_json::json!({
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": numbers.iter().map(|n| serde_json::json!({"ART_1_25": n})).collect::<Vec<_>>()}
+        })));
+    }
+    let mut client = server.client().await;
+
+    let chunks = Parameters::new().param_in_chunked("ARTNR", ["A1", "A2", "A3", "A4"], 2);
+    let articles = client
+        .request_chunks::<ArticleResponse<ArticleData>>(None, "ARTIKEL.GET", 1, chunks)
+        .await
+        .unwrap();
+    let numbers = articles
+        .into_iter()
+        .map(|a| a.article_number)
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, vec!["A1", "A2", "A3", "A4"]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].json()["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "A3,A4"
+    );
+}
+
+#[tokio::test]
+async fn test_with_registered_timeout() {
+    let server = MockServer::start().await;
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .build();
+    let deregistrations = || {
+        server
+            .requests()
+            .iter()
+            .filter(|r| r.path.contains("/DEREGISTER/"))
+            .count()
+    };
+
+    let value = client
+        .clone()
+        .with_registered_timeout(std::time::Duration::from_secs(5), |_| async { 42 }.boxed())
+        .await
+        .unwrap();
+    assert_eq!(value, 42);
+    assert_eq!(deregistrations(), 1);
+
+    let result = client
+        .clone()
+        .with_registered_timeout(std::time::Duration::from_millis(50), |_| {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).boxed()
+        })
+        .await;
+    assert!(matches!(result, Err(WWSVCError::Timeout(_))));
+    assert_eq!(deregistrations(), 2);
+
+    let panicked = std::panic::AssertUnwindSafe(
+        client
+            .clone()
+            .with_registered_timeout(std::time::Duration::from_secs(5), |_| {
+                async { panic!("job failed") }.boxed()
+            }),
+    )
+    .catch_unwind()
+    .await;
+    assert!(panicked.is_err());
+    assert_eq!(deregistrations(), 3);
+
+    server.enqueue(default_response().delay(std::time::Duration::from_secs(60)));
+    let started = std::time::Instant::now();
+    let result = client
+        .with_registered_timeout(std::time::Duration::from_millis(200), |_| {
+            async { 42 }.boxed()
+        })
+        .await;
+    assert!(matches!(result, Err(WWSVCError::Timeout(_))));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert_eq!(deregistrations(), 4);
+}
+
+#[tokio::test]
+async fn test_with_registered_panic() {
+    let server = MockServer::start().await;
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .build();
+
+    let panicked = std::panic::AssertUnwindSafe(
+        client.with_registered(|_| async { panic!("job failed") }.boxed()),
+    )
+    .catch_unwind()
+    .await;
+    let panic = panicked.err().unwrap();
+    assert_eq!(panic.downcast_ref::<&str>(), Some(&"job failed"));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].path.contains("/DEREGISTER/service-pass/"));
+}
+
+#[tokio::test]
+async fn test_send_batch() {
+    use wwsvc_rs::requests::{BatchRequest, ServiceFunction};
+
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .function_policy(FunctionPolicy::default().allow("ARTIKEL.*"))
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    let response = client.send_batch(&BatchRequest::new()).await.unwrap();
+    assert!(response.results.is_empty());
+    assert_eq!(server.requests().len(), 0);
+
+    server.enqueue(common::MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "RESULTS": [
+            {"FUNCTIONNAME": "ARTIKEL.INSERT", "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}, "ARTIKEL": {"ARTNR": "A1"}},
+            {"FUNCTIONNAME": "ARTIKEL.INSERT", "COMRESULT": {"STATUS": 409, "CODE": "Conflict", "INFO": "exists"}}
+        ]
+    })));
+    let batch = ["A1", "A2"]
+        .into_iter()
+        .map(|number| ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", number))
+        .collect::<BatchRequest>();
+    let response = client.send_batch(&batch).await.unwrap();
+    assert!(!response.is_success());
+    assert_eq!(
+        response.com_results().map(|r| r.status).collect::<Vec<_>>(),
+        vec![200, 409]
+    );
+    assert_eq!(response.results[0].data["ARTIKEL"]["ARTNR"], "A1");
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, "PUT");
+    let body = request.json();
+    assert_eq!(body["WWSVC_TRANSACTION"], "ATOMIC");
+    assert_eq!(body["WWSVC_FUNCTION"][1]["PARAMETER"][0]["PCONTENT"], "A2");
+    assert_eq!(body["WWSVC_PASSINFO"]["SERVICEPASS"], "service-pass");
+
+    let requests = server.requests().len();
+    let result = client
+        .send_batch(&batch.function(ServiceFunction::new("BELEG.INSERT", 1)))
+        .await;
+    assert!(
+        matches!(result, Err(WWSVCError::FunctionNotPermitted(function)) if function == "BELEG.INSERT")
+    );
+    assert_eq!(server.requests().len(), requests);
+}
+
+#[tokio::test]
+async fn test_recent_results() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .recent_results(2)
+        .build()
+        .register()
+        .await
+        .unwrap();
+    assert!(client.recent_results().is_empty());
+
+    for status in [200, 404, 500] {
+        server.enqueue(common::MockResponse::json(serde_json::json!({
+            "COMRESULT": {"STATUS": status, "CODE": "", "INFO": ""}
+        })));
+    }
+    let mut clone = client.clone();
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    client
+        .request(None, "BELEG.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    clone
+        .request(None, "ADRESSE.PUT", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let results = client.recent_results();
+    assert_eq!(
+        results
+            .iter()
+            .map(|result| (result.function.as_str(), result.com_result.status))
+            .collect::<Vec<_>>(),
+        vec![("BELEG.GET", 404), ("ADRESSE.PUT", 500)]
+    );
+}
+
+#[tokio::test]
+async fn test_unexpected_content_type() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    server.enqueue(common::MockResponse::text(
+        "text/html; charset=utf-8",
+        "<!DOCTYPE html>\n<html><body>\n  <h1>Wartungsarbeiten</h1>\n</body></html>",
+    ));
+    let error = client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap_err();
+    match error {
+        WWSVCError::UnexpectedContentType {
+            status,
+            ref content_type,
+            ref snippet,
+        } => {
+            assert_eq!(status, 200);
+            assert_eq!(content_type, "text/html; charset=utf-8");
+            assert_eq!(
+                snippet,
+                "<!DOCTYPE html> <html><body> <h1>Wartungsarbeiten</h1> </body></html>"
+            );
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(error.category(), wwsvc_rs::ErrorCategory::Server);
+
+    // HTML declared as JSON is refused as well
+    server.enqueue(common::MockResponse::text(
+        "application/json",
+        "<html></html>",
+    ));
+    assert!(matches!(
+        client
+            .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+            .await,
+        Err(WWSVCError::UnexpectedContentType { .. })
+    ));
+
+    // JSON with a wrong content type is accepted
+    server.enqueue(common::MockResponse::text(
+        "text/plain",
+        r#"{"COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}}"#,
+    ));
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_put_patch() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    server.enqueue(common::MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+    })));
+    let com_result = client
+        .put_patch(
+            "ARTIKEL",
+            collection! { "ARTNR" => "A1" },
+            serde_json::json!({
+                "ART_1_10": "Schraube",
+                "ART_1_80": 12,
+                "ART_2_10": null,
+                "ARTNR": "B2"
+            }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(com_result.status, 200);
+
+    let request = server.requests().pop().unwrap();
+    let body = request.json();
+    assert_eq!(body["WWSVC_FUNCTION"]["FUNCTIONNAME"], "ARTIKEL.PUT");
+    let parameters = body["WWSVC_FUNCTION"]["PARAMETER"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|parameter| {
+            (
+                parameter["PNAME"].as_str().unwrap().to_string(),
+                parameter["PCONTENT"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(parameters["ART_1_10"], "Schraube");
+    assert_eq!(parameters["ART_1_80"], "12");
+    assert_eq!(parameters["ART_2_10"], "");
+    assert_eq!(parameters["ARTNR"], "A1");
+
+    server.enqueue(common::MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 404, "CODE": "Not Found", "INFO": ""}
+    })));
+    let error = client
+        .put_patch(
+            "ARTIKEL.UPDATE",
+            collection! { "ARTNR" => "A1" },
+            serde_json::json!({}),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, WWSVCError::FunctionFailed { ref function, .. } if function == "ARTIKEL.UPDATE")
+    );
+
+    let requests = server.requests().len();
+    let error = client
+        .put_patch("ARTIKEL", Parameters::new(), serde_json::json!([1, 2]))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WWSVCError::InvalidPatch(_)));
+    assert_eq!(error.category(), wwsvc_rs::ErrorCategory::Client);
+    assert_eq!(
+        error.to_string(),
+        "A patch has to be a JSON object, but is an array."
+    );
+    assert_eq!(server.requests().len(), requests);
+}
+
+#[tokio::test]
+async fn test_request_generic_with_headers() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    server.enqueue(
+        common::MockResponse::json(serde_json::json!({
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+        }))
+        .header("X-RateLimit-Remaining", "42"),
+    );
+    let (response, headers) = client
+        .request_generic_with_headers::<serde_json::Value>(
+            None,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response["COMRESULT"]["STATUS"], 200);
+    assert_eq!(headers["X-RateLimit-Remaining"], "42");
+    assert_eq!(headers["Content-Type"], "application/json");
+}
+
+#[tokio::test]
+async fn test_count() {
+    let server = MockServer::start().await;
+    let client = server.client().await;
+    let mut client = client.create_cursor(100);
+
+    server.enqueue(
+        common::MockResponse::json(serde_json::json!({
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": [{"ARTNR": "A1"}]}
+        }))
+        .header("WWSVC-CURSOR", "4711")
+        .header("WWSVC-CURSOR-COUNT", "1234"),
+    );
+    let count = client
+        .count("ARTIKEL.GET", collection! { "ARTNR" => "A*" })
+        .await
+        .unwrap();
+    assert_eq!(count, 1234);
+
+    let mut requests = server.requests();
+    // the cursor is closed right after the count has been read
+    let close = requests.pop().unwrap();
+    assert_eq!(
+        close.json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ARTIKEL.GET"
+    );
+    assert_eq!(close.header("WWSVC-CURSOR"), Some("4711"));
+    assert_eq!(close.header("WWSVC-CURSOR-STATE"), Some("CLOSED"));
+    let request = requests.pop().unwrap();
+    assert_eq!(request.header("WWSVC-CURSOR"), Some("CREATE"));
+    assert_eq!(request.header("WWSVC-ACCEPT-RESULT-MAX-LINES"), Some("1"));
+    // the cursor of the client is untouched
+    assert!(!client.cursor_closed());
+
+    server.enqueue(common::MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+    })));
+    assert!(matches!(
+        client.count("ARTIKEL.GET", Parameters::new()).await,
+        Err(WWSVCError::MissingField(field)) if field == "WWSVC-CURSOR-COUNT"
+    ));
+}
+
+#[tokio::test]
+async fn test_mutation_events() {
+    use wwsvc_rs::requests::{BatchRequest, ServiceFunction};
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let mut events = client.subscribe_mutations();
+
+    client
+        .request(
+            None,
+            "ARTIKEL.PUT",
+            1,
+            Parameters::new().param("ARTNR", "A1"),
+            None,
+        )
+        .await
+        .unwrap();
+    let event = events.try_recv().unwrap();
+    assert_eq!(event.function, "ARTIKEL.PUT");
+    assert_eq!(event.version, 1);
+    assert_eq!(event.parameters.get("ARTNR"), Some("A1"));
+    assert_eq!(event.com_result.status, 200);
+
+    // failed mutations and reading functions aren't reported
+    server.enqueue(common::MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 409, "CODE": "Conflict", "INFO": ""}
+    })));
+    client
+        .request(None, "ARTIKEL.PUT", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    assert!(events.try_recv().is_err());
+
+    // only the successful functions of a batch are reported
+    server.enqueue(common::MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "RESULTS": [
+            {"FUNCTIONNAME": "ARTIKEL.INSERT", "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}},
+            {"FUNCTIONNAME": "ARTIKEL.INSERT", "COMRESULT": {"STATUS": 409, "CODE": "Conflict", "INFO": ""}}
+        ]
+    })));
+    let batch = ["A2", "A3"]
+        .into_iter()
+        .map(|number| ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", number))
+        .collect::<BatchRequest>();
+    client.send_batch(&batch).await.unwrap();
+    let event = events.try_recv().unwrap();
+    assert_eq!(event.function, "ARTIKEL.INSERT");
+    assert_eq!(event.parameters.get("ARTNR"), Some("A2"));
+    assert!(events.try_recv().is_err());
+}
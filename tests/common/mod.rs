@@ -0,0 +1,248 @@
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use wwsvc_rs::{Credentials, Registered, WebwareClient};
+
+/// A request received by the mock server.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names as they were sent on the wire, with their values.
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).expect("request body is not JSON")
+    }
+}
+
+/// A canned response of the mock server.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Time to wait before the response is written.
+    pub delay: Option<std::time::Duration>,
+}
+
+impl MockResponse {
+    pub fn json(body: serde_json::Value) -> MockResponse {
+        MockResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string().into_bytes(),
+            delay: None,
+        }
+    }
+
+    pub fn text(content_type: &str, body: &str) -> MockResponse {
+        MockResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), content_type.to_string())],
+            body: body.as_bytes().to_vec(),
+            delay: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> MockResponse {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn status(mut self, status: u16) -> MockResponse {
+        self.status = status;
+        self
+    }
+
+    pub fn delay(mut self, delay: std::time::Duration) -> MockResponse {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+/// Minimal HTTP/1.1 server answering requests with queued responses.
+///
+/// Once the queue is empty, an empty successful COMRESULT is returned.
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    responses: Arc<Mutex<VecDeque<MockResponse>>>,
+}
+
+impl MockServer {
+    pub async fn start() -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(Mutex::new(VecDeque::new()));
+
+        let (task_requests, task_responses) = (requests.clone(), responses.clone());
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let (requests, responses) = (task_requests.clone(), task_responses.clone());
+                tokio::spawn(async move {
+                    let mut stream = BufReader::new(stream);
+                    while let Some(request) = read_request(&mut stream).await {
+                        requests.lock().unwrap().push(request);
+                        let response = responses
+                            .lock()
+                            .unwrap()
+                            .pop_front()
+                            .unwrap_or_else(default_response);
+                        if write_response(stream.get_mut(), response).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        MockServer {
+            url,
+            requests,
+            responses,
+        }
+    }
+
+    pub fn enqueue(&self, response: MockResponse) {
+        self.responses.lock().unwrap().push_back(response);
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Returns a client which is registered with dummy credentials against this server.
+    pub async fn client(&self) -> WebwareClient<Registered> {
+        WebwareClient::builder()
+            .webware_url(&self.url)
+            .vendor_hash("vendor")
+            .app_hash("app")
+            .secret("1")
+            .revision(1)
+            .credentials(Credentials::new("service-pass", "app-id"))
+            .build()
+            .register()
+            .await
+            .unwrap()
+    }
+}
+
+pub fn default_response() -> MockResponse {
+    MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+    }))
+}
+
+async fn read_request<S>(stream: &mut BufReader<S>) -> Option<RecordedRequest>
+where
+    S: tokio::io::AsyncRead + Unpin,
+{
+    let mut line = String::new();
+    if stream.read_line(&mut line).await.ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        headers.push((name.to_string(), value.trim().to_string()));
+    }
+
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let body = if header("transfer-encoding").is_some_and(|value| value.contains("chunked")) {
+        read_chunked_body(stream).await?
+    } else {
+        let content_length = header("content-length")
+            .and_then(|value| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; content_length];
+        stream.read_exact(&mut body).await.ok()?;
+        body
+    };
+
+    Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Reads a body sent with `Transfer-Encoding: chunked`, e.g. a streamed upload.
+async fn read_chunked_body<S>(stream: &mut BufReader<S>) -> Option<Vec<u8>>
+where
+    S: tokio::io::AsyncRead + Unpin,
+{
+    let mut body = Vec::new();
+    loop {
+        let mut line = String::new();
+        stream.read_line(&mut line).await.ok()?;
+        let size = line.trim_end().split(';').next()?;
+        let size = usize::from_str_radix(size, 16).ok()?;
+        if size == 0 {
+            // trailers, terminated by an empty line
+            loop {
+                let mut line = String::new();
+                stream.read_line(&mut line).await.ok()?;
+                if line.trim_end().is_empty() {
+                    return Some(body);
+                }
+            }
+        }
+        let start = body.len();
+        body.resize(start + size, 0);
+        stream.read_exact(&mut body[start..]).await.ok()?;
+        let mut line_end = [0; 2];
+        stream.read_exact(&mut line_end).await.ok()?;
+    }
+}
+
+async fn write_response<S>(stream: &mut S, response: MockResponse) -> std::io::Result<()>
+where
+    S: tokio::io::AsyncWrite + Unpin,
+{
+    if let Some(delay) = response.delay {
+        tokio::time::sleep(delay).await;
+    }
+    let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!("Content-Length: {}\r\n\r\n", response.body.len()));
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.flush().await
+}
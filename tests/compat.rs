@@ -0,0 +1,132 @@
+mod common;
+
+use common::MockServer;
+use wwsvc_rs::{Compat, Credentials, Parameters, WebwareClient};
+
+#[tokio::test]
+async fn test_latest_compat() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    client
+        .request(
+            reqwest::Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("WWSVC-EXECUTE-MODE"), Some("SYNCHRON"));
+    assert_eq!(request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"], "SYNCHRON");
+    assert!(request
+        .headers
+        .iter()
+        .any(|(name, _)| name == "wwsvc-reqid"));
+}
+
+#[tokio::test]
+async fn test_ww2021_compat() {
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .compat(Compat::Ww2021)
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    client
+        .request(
+            reqwest::Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let request = &server.requests()[0];
+    assert_eq!(request.header("WWSVC-EXECUTE-MODE"), None);
+    assert_eq!(request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"], "SYNCHRON");
+    assert!(request
+        .headers
+        .iter()
+        .any(|(name, _)| name == "Wwsvc-Reqid"));
+}
+
+#[tokio::test]
+async fn test_execute_mode() {
+    use wwsvc_rs::requests::ExecuteMode;
+
+    let server = MockServer::start().await;
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .execute_mode(ExecuteMode::Batch)
+        .build()
+        .register()
+        .await
+        .unwrap();
+    assert_eq!(client.execute_mode(), &ExecuteMode::Batch);
+
+    client
+        .request(
+            reqwest::Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            None,
+        )
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.header("WWSVC-EXECUTE-MODE"), Some("BATCH"));
+    assert_eq!(request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"], "BATCH");
+
+    client
+        .request(
+            reqwest::Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            Some(wwsvc_rs::collection! { "WWSVC-EXECUTE-MODE" => "synchron" }),
+        )
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.header("WWSVC-EXECUTE-MODE"), Some("SYNCHRON"));
+    assert_eq!(request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"], "SYNCHRON");
+
+    client.set_execute_mode("QUEUED");
+    assert_eq!(
+        client.headers_for(1, None).unwrap()["WWSVC-EXECUTE-MODE"],
+        "QUEUED"
+    );
+    client
+        .request(
+            reqwest::Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            None,
+        )
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.header("WWSVC-EXECUTE-MODE"), Some("QUEUED"));
+    assert_eq!(request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"], "QUEUED");
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use std::io::Read;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::compression::RequestCompression;
+use wwsvc_rs::requests::BodySizeLimit;
+use wwsvc_rs::{Credentials, Parameters, WebwareClient};
+
+async fn compressing_client(
+    server: &MockServer,
+    min_size: usize,
+) -> WebwareClient<wwsvc_rs::Registered> {
+    WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .body_size_limit(BodySizeLimit::unlimited())
+        .request_compression(RequestCompression::Gzip { min_size })
+        .build()
+        .register()
+        .await
+        .unwrap()
+}
+
+fn decompress(body: &[u8]) -> serde_json::Value {
+    let mut json = String::new();
+    flate2::read::GzDecoder::new(body)
+        .read_to_string(&mut json)
+        .unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[tokio::test]
+async fn test_gzip_compression() {
+    let server = MockServer::start().await;
+    let mut client = compressing_client(&server, 1024).await;
+    let positions = "POS;".repeat(1000);
+
+    client
+        .request(
+            None,
+            "BELEG.INSERT",
+            1,
+            Parameters::new().param("POSITIONEN", &positions),
+            None,
+        )
+        .await
+        .unwrap();
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("Content-Encoding"), Some("gzip"));
+    assert!(requests[0].body.len() < positions.len());
+    let body = decompress(&requests[0].body);
+    assert_eq!(body["WWSVC_FUNCTION"]["FUNCTIONNAME"], "BELEG.INSERT");
+    assert_eq!(
+        body["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        positions
+    );
+
+    // bodies below the minimum size are sent uncompressed
+    assert_eq!(requests[1].header("Content-Encoding"), None);
+    assert_eq!(
+        requests[1].json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ARTIKEL.GET"
+    );
+}
+
+#[tokio::test]
+async fn test_gzip_compression_rejected() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::text("text/plain", "Unsupported Media Type").status(415));
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+    })));
+    let mut client = compressing_client(&server, 0).await;
+    let clone = client.clone();
+
+    let response = client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    assert_eq!(response["COMRESULT"]["STATUS"], 200);
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].header("Content-Encoding"), Some("gzip"));
+    assert_eq!(requests[1].header("Content-Encoding"), None);
+    assert_eq!(requests[2].header("Content-Encoding"), None);
+    assert_ne!(
+        requests[0].header("WWSVC-REQID"),
+        requests[1].header("WWSVC-REQID")
+    );
+    assert_eq!(client.request_compression(), RequestCompression::None);
+    assert_eq!(clone.request_compression(), RequestCompression::None);
+}
+
+#[tokio::test]
+async fn test_gzip_compression_batch() {
+    use wwsvc_rs::requests::{BatchRequest, ServiceFunction};
+
+    let server = MockServer::start().await;
+    let mut client = compressing_client(&server, 0).await;
+    let batch = ["A1", "A2"]
+        .into_iter()
+        .map(|number| ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", number))
+        .collect::<BatchRequest>();
+
+    client.send_batch(&batch).await.unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.header("Content-Encoding"), Some("gzip"));
+    let body = decompress(&request.body);
+    assert_eq!(body["WWSVC_FUNCTION"][1]["PARAMETER"][0]["PCONTENT"], "A2");
+
+    // batches fall back to uncompressed bodies like single function calls
+    server.enqueue(MockResponse::text("text/plain", "Unsupported Media Type").status(415));
+    client.send_batch(&batch).await.unwrap();
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[1].header("Content-Encoding"), Some("gzip"));
+    assert_eq!(requests[2].header("Content-Encoding"), None);
+    assert_eq!(requests[2].json()["WWSVC_TRANSACTION"], "ATOMIC");
+    assert_eq!(client.request_compression(), RequestCompression::None);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::conformance::CheckOutcome;
+use wwsvc_rs::WebwareClient;
+
+fn articles(count: usize) -> serde_json::Value {
+    json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {
+            "ARTIKEL": (0..count).map(|i| json!({"ART_1_25": format!("A{}", i)})).collect::<Vec<_>>()
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_conformance_run() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "SERVICEPASS": {"PASSID": "service-pass", "APPID": "app-id"}
+    })));
+    server.enqueue(MockResponse::json(articles(3)));
+    server.enqueue(MockResponse::json(articles(2)));
+    server.enqueue(MockResponse::json(articles(1)).header("WWSVC-CURSOR", "ABC"));
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 404, "CODE": "Not Found", "INFO": "unknown function"}
+    })));
+
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+    let report = wwsvc_rs::conformance::run(client).await;
+
+    let outcomes = report
+        .checks
+        .iter()
+        .map(|check| (check.name, check.outcome.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(outcomes[0], ("register", CheckOutcome::Passed));
+    assert_eq!(outcomes[1], ("hash", CheckOutcome::Passed));
+    assert!(matches!(
+        outcomes[2],
+        ("max_lines", CheckOutcome::Failed(_))
+    ));
+    assert_eq!(outcomes[3], ("cursor", CheckOutcome::Passed));
+    assert_eq!(outcomes[4], ("error_shape", CheckOutcome::Passed));
+    assert_eq!(outcomes[5], ("deregister", CheckOutcome::Passed));
+    assert!(!report.passed());
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[2].header("WWSVC-ACCEPT-RESULT-MAX-LINES"),
+        Some("1")
+    );
+    assert_eq!(requests[3].header("WWSVC-CURSOR"), Some("CREATE"));
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use common::{default_response, MockResponse, MockServer};
+use futures::future::BoxFuture;
+use serde_json::json;
+use wwsvc_rs::{
+    Credentials, CredentialsProvider, EnvCredentials, ErrorCategory, FileCredentials, Parameters,
+    WWClientResult, WWSVCError, WebwareClient,
+};
+
+#[derive(Debug)]
+struct RotatedCredentials;
+
+impl CredentialsProvider for RotatedCredentials {
+    fn credentials(&self) -> BoxFuture<'_, WWClientResult<Option<Credentials>>> {
+        Box::pin(async { Ok(Some(Credentials::new("new-pass", "new-app"))) })
+    }
+}
+
+#[tokio::test]
+async fn test_file_credentials() {
+    let server = MockServer::start().await;
+    let path =
+        std::env::temp_dir().join(format!("wwsvc-rs-credentials-{}.json", std::process::id()));
+    tokio::fs::write(
+        &path,
+        r#"{"service_pass": "file-pass", "app_id": "file-app"}"#,
+    )
+    .await
+    .unwrap();
+
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials_provider(FileCredentials::new(&path))
+        .build()
+        .register()
+        .await
+        .unwrap();
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    assert!(server.requests().is_empty());
+    assert_eq!(client.credentials().unwrap().service_pass, "file-pass");
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        server.requests()[0].json()["WWSVC_PASSINFO"]["SERVICEPASS"],
+        "file-pass"
+    );
+
+    let missing = FileCredentials::new(path);
+    assert!(missing.credentials().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_env_credentials() {
+    std::env::set_var("WWSVC_RS_TEST_PASS", "env-pass");
+    std::env::set_var("WWSVC_RS_TEST_APP", "env-app");
+
+    let credentials = EnvCredentials::new("WWSVC_RS_TEST_PASS", "WWSVC_RS_TEST_APP")
+        .credentials()
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(credentials == Credentials::new("env-pass", "env-app"));
+
+    let missing = EnvCredentials::new("WWSVC_RS_TEST_PASS", "WWSVC_RS_TEST_MISSING");
+    assert!(missing.credentials().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_refresh_on_auth_failure() {
+    let server = MockServer::start().await;
+    server.enqueue(default_response().status(401));
+    server.enqueue(default_response());
+
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("old-pass", "old-app"))
+        .credentials_provider(RotatedCredentials)
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let passes = server
+        .requests()
+        .iter()
+        .map(|r| {
+            r.json()["WWSVC_PASSINFO"]["SERVICEPASS"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(passes, vec!["old-pass", "new-pass"]);
+    assert_eq!(client.credentials().unwrap().app_id, "new-app");
+}
+
+#[tokio::test]
+async fn test_replace_credentials() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let mut operator = client.clone();
+
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    operator.replace_credentials(Credentials::new("rotated-pass", "rotated-app"));
+    assert_eq!(client.credentials().unwrap().service_pass, "service-pass");
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let passes = server
+        .requests()
+        .iter()
+        .map(|r| {
+            r.json()["WWSVC_PASSINFO"]["SERVICEPASS"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(passes, vec!["service-pass", "rotated-pass"]);
+    assert_eq!(client.credentials().unwrap().app_id, "rotated-app");
+}
+
+#[tokio::test]
+async fn test_credentials_binding() {
+    let server = MockServer::start().await;
+    let builder = || {
+        WebwareClient::builder()
+            .webware_url(&server.url)
+            .vendor_hash("vendor")
+            .app_hash("app")
+            .secret("1")
+            .revision(1)
+    };
+
+    let bound = Credentials::new("service-pass", "app-id").bound_to("vendor", "app", 1);
+    assert!(builder()
+        .credentials(bound.clone())
+        .build()
+        .register()
+        .await
+        .is_ok());
+
+    let other_revision = Credentials::new("service-pass", "app-id").bound_to("vendor", "app", 2);
+    let error = builder()
+        .credentials(other_revision)
+        .build()
+        .register()
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(
+        &error,
+        WWSVCError::CredentialsMismatch { field, expected, found }
+            if field == "revision" && expected == "1" && found == "2"
+    ));
+    assert_eq!(error.category(), ErrorCategory::Auth);
+
+    let mut other_vendor = bound;
+    other_vendor.vendor_hash = Some("other-vendor".to_string());
+    let result = builder().credentials(other_vendor).build().register().await;
+    assert!(matches!(
+        result,
+        Err(WWSVCError::CredentialsMismatch { field, .. }) if field == "vendor hash"
+    ));
+    assert!(server.requests().is_empty());
+
+    // credentials returned by REGISTER are bound to the client
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "SERVICEPASS": {"PASSID": "new-pass", "APPID": "new-app"}
+    })));
+    let client = builder().build().register().await.unwrap();
+    let credentials = client.credentials().unwrap();
+    assert_eq!(credentials.vendor_hash.as_deref(), Some("vendor"));
+    assert_eq!(credentials.app_hash.as_deref(), Some("app"));
+    assert_eq!(credentials.revision, Some(1));
+    let persisted = serde_json::to_value(credentials).unwrap();
+    assert_eq!(persisted["revision"], 1);
+    client.deregister().await.unwrap();
+}
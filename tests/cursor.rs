@@ -0,0 +1,723 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use futures::StreamExt;
+use reqwest::Method;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use wwsvc_rs::responses::ArtikelGetResponse;
+use wwsvc_rs::{Cursor, CursorSnapshot, CursorState, PaginationStrategy, Parameters, WWSVCError};
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+pub fn articles(numbers: &[&str]) -> serde_json::Value {
+    json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {
+            "ARTIKEL": numbers.iter().map(|n| json!({"ART_1_25": n})).collect::<Vec<_>>()
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_cursored_request() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+
+    let mut numbers = Vec::new();
+    while let Some(page) = pages.next().await {
+        numbers.extend(page.unwrap().into_iter().map(|a| a.article_number));
+    }
+    assert_eq!(numbers, vec!["A1", "A2", "A3"]);
+
+    let stats = pages.stats();
+    assert_eq!(stats.pages, 2);
+    assert_eq!(stats.rows, 3);
+    assert!(stats.bytes > 0);
+
+    let cursors = server
+        .requests()
+        .iter()
+        .map(|r| r.header("WWSVC-CURSOR").unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(cursors, vec!["CREATE", "C1"]);
+}
+
+#[tokio::test]
+async fn test_cursor_lost() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3", "A4"])));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+
+    assert_eq!(pages.next().await.unwrap().unwrap().len(), 2);
+    assert!(matches!(
+        pages.next().await,
+        Some(Err(WWSVCError::CursorLost))
+    ));
+    assert!(pages.next().await.is_none());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_page_size_ramp() {
+    let server = MockServer::start().await;
+    for cursor in ["C1", "C2", "C3", "CLOSED"] {
+        server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", cursor));
+    }
+
+    let mut client = server.client().await.create_cursor(500);
+    let mut pages = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .with_strategy(PaginationStrategy::ramp(10, 30, Duration::from_secs(60)));
+    while let Some(page) = pages.next().await {
+        page.unwrap();
+    }
+
+    let page_sizes = server
+        .requests()
+        .iter()
+        .map(|r| {
+            r.header("WWSVC-ACCEPT-RESULT-MAX-LINES")
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(page_sizes, vec!["10", "20", "30", "30"]);
+}
+
+#[tokio::test]
+async fn test_page_size_ramp_slow() {
+    let server = MockServer::start().await;
+    for cursor in ["C1", "CLOSED"] {
+        server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", cursor));
+    }
+
+    let mut client = server.client().await.create_cursor(500);
+    let mut pages = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .with_strategy(PaginationStrategy::ramp(10, 30, Duration::ZERO));
+    while let Some(page) = pages.next().await {
+        page.unwrap();
+    }
+
+    let page_sizes = server
+        .requests()
+        .iter()
+        .map(|r| {
+            r.header("WWSVC-ACCEPT-RESULT-MAX-LINES")
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(page_sizes, vec!["10", "10"]);
+}
+
+static FAIL_DESERIALIZATION: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone)]
+pub struct FlakyArticle(pub String);
+
+impl<'de> serde::Deserialize<'de> for FlakyArticle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let article = ArticleData::deserialize(deserializer)?;
+        if FAIL_DESERIALIZATION.swap(false, Ordering::SeqCst) {
+            return Err(serde::de::Error::custom("transient failure"));
+        }
+        Ok(FlakyArticle(article.article_number))
+    }
+}
+
+#[tokio::test]
+async fn test_retry_last_page() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client.cursored_request::<FlakyArticle, ArtikelGetResponse<FlakyArticle>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    assert!(pages.retry_last_page().is_none());
+
+    FAIL_DESERIALIZATION.store(true, Ordering::SeqCst);
+    assert!(pages.next().await.unwrap().is_err());
+    assert!(pages.last_page().is_some());
+    assert_eq!(pages.stats().pages, 0);
+
+    let page = pages.retry_last_page().unwrap().unwrap();
+    assert_eq!(page.len(), 2);
+    assert!(pages.last_page().is_none());
+    assert!(pages.retry_last_page().is_none());
+
+    assert_eq!(pages.next().await.unwrap().unwrap()[0].0, "A3");
+    assert!(pages.next().await.is_none());
+    assert_eq!(pages.stats().pages, 2);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_cursor_id_not_ascii() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "CÜRSOR"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let result = client
+        .request(Some(Method::PUT), "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await;
+    assert!(matches!(result, Err(WWSVCError::HeaderValueToStrError)));
+    assert_eq!(client.cursor().unwrap().cursor_id, "CREATE");
+}
+
+#[tokio::test]
+async fn test_cursor_state_headers() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::json(articles(&["A1", "A2"]))
+            .header("WWSVC-CURSOR", "C1")
+            .header("WWSVC-CURSOR-STATE", "open")
+            .header("WWSVC-CURSOR-REMAINING", "1")
+            .header("WWSVC-CURSOR-COUNT", "3"),
+    );
+    server.enqueue(
+        MockResponse::json(articles(&["A3"]))
+            .header("WWSVC-CURSOR", "C2")
+            .header("WWSVC-CURSOR-STATE", "CLOSED")
+            .header("WWSVC-CURSOR-REMAINING", "none"),
+    );
+
+    let mut client = server.client().await.create_cursor(2);
+    client
+        .request(Some(Method::PUT), "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let cursor = client.cursor().unwrap();
+    assert_eq!(cursor.cursor_id, "C1");
+    assert_eq!(cursor.state, Some(CursorState::Open));
+    assert_eq!(cursor.remaining_rows, Some(1));
+    assert_eq!(cursor.total_rows, Some(3));
+    assert!(!client.cursor_closed());
+
+    client
+        .request(Some(Method::PUT), "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let cursor = client.cursor().unwrap();
+    assert_eq!(cursor.cursor_id, "C2");
+    assert_eq!(cursor.state, Some(CursorState::Closed));
+    assert_eq!(cursor.remaining_rows, None);
+    assert_eq!(cursor.total_rows, Some(3));
+    assert!(client.cursor_closed());
+}
+
+#[tokio::test]
+async fn test_into_channel() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    let (rx, driver) = pages.into_channel(1);
+    let consumer = rx.map(|article| article.article_number).collect::<Vec<_>>();
+    let (stats, numbers) = futures::join!(driver, consumer);
+
+    assert_eq!(numbers, vec!["A1", "A2", "A3"]);
+    assert_eq!(stats.unwrap().rows, 3);
+}
+
+#[tokio::test]
+async fn test_into_channel_backpressure() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    let (mut rx, driver) = pages.into_channel(0);
+    let consumer = async move {
+        let first = rx.next().await.unwrap();
+        drop(rx);
+        first.article_number
+    };
+    let (stats, first) = futures::join!(driver, consumer);
+
+    assert_eq!(first, "A1");
+    assert_eq!(stats.unwrap().pages, 1);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_into_channel_error() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A2"])));
+
+    let mut client = server.client().await.create_cursor(1);
+    let pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    let (rx, driver) = pages.into_channel(10);
+    let (stats, articles) = futures::join!(driver, rx.collect::<Vec<_>>());
+
+    assert_eq!(articles.len(), 1);
+    assert!(matches!(stats, Err(WWSVCError::CursorLost)));
+}
+
+#[tokio::test]
+async fn test_next_page() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+
+    let page = pages.next_page().await.unwrap().unwrap();
+    assert_eq!(page.page_index, 0);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.cursor_id, "C1");
+    assert_eq!(page.com_result.unwrap().status, 200);
+
+    let page = pages.next_page().await.unwrap().unwrap();
+    assert_eq!(page.page_index, 1);
+    assert_eq!(page.items[0].article_number, "A3");
+    assert_eq!(page.cursor_id, "CLOSED");
+    assert!(pages.next_page().await.is_none());
+}
+
+#[tokio::test]
+async fn test_into_stream() {
+    use futures::TryStreamExt;
+
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let numbers: Vec<String> = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .into_stream()
+        .map_ok(|article| article.article_number)
+        .try_filter(|number| std::future::ready(number != "A2"))
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(numbers, vec!["A1", "A3"]);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_into_stream_error() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A2"])));
+
+    let mut client = server.client().await.create_cursor(1);
+    let items = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .into_stream()
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].as_ref().unwrap().article_number, "A1");
+    assert!(matches!(items[1], Err(WWSVCError::CursorLost)));
+}
+
+#[test]
+fn test_cursor_display() {
+    let mut cursor = Cursor::new(500);
+    assert_eq!(cursor.to_string(), "CREATE (500 lines)");
+
+    cursor.set_cursor_id("C1".to_string());
+    cursor.state = Some(CursorState::Open);
+    cursor.remaining_rows = Some(20);
+    cursor.total_rows = Some(520);
+    assert_eq!(
+        cursor.to_string(),
+        "C1 (500 lines, OPEN, 20 of 520 rows remaining)"
+    );
+}
+
+#[tokio::test]
+async fn test_sorted_by() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new().param("FELDER", "ART_1_25,ART_2_80"),
+        )
+        .sorted_by(&["art_1_25"]);
+    while let Some(page) = pages.next().await {
+        page.unwrap();
+    }
+    let parameters = server.requests()[0].json()["WWSVC_FUNCTION"]["PARAMETER"].clone();
+    assert!(parameters
+        .as_array()
+        .unwrap()
+        .contains(&json!({"PNAME": "SORTIERUNG", "PCONTENT": "art_1_25"})));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new().param("FELDER", "ART_2_80"),
+        )
+        .sorted_by(&["ART_1_25"]);
+    assert!(matches!(
+        pages.next().await,
+        Some(Err(WWSVCError::SortFieldNotSelected(field))) if field == "ART_1_25"
+    ));
+    assert!(pages.next().await.is_none());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_restore_cursor() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::json(articles(&["A1", "A2"]))
+            .header("WWSVC-CURSOR", "C1")
+            .header("WWSVC-CURSOR-COUNT", "3"),
+    );
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(2);
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    pages.next().await.unwrap().unwrap();
+    let saved = serde_json::to_string(&pages.cursor_state()).unwrap();
+    drop(pages);
+    drop(client);
+
+    let state: CursorSnapshot = serde_json::from_str(&saved).unwrap();
+    assert_eq!(state.cursor_id, "C1");
+    assert_eq!(state.pages, 1);
+    assert_eq!(state.total_rows, Some(3));
+
+    let mut client = server.client().await;
+    let mut pages = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .with_cursor(Cursor::from_state(state));
+    let page = pages.next_page().await.unwrap().unwrap();
+    assert_eq!(page.page_index, 1);
+    assert_eq!(page.items[0].article_number, "A3");
+    assert!(pages.next().await.is_none());
+
+    let requests = server.requests();
+    assert_eq!(requests[1].header("WWSVC-CURSOR"), Some("C1"));
+    assert_eq!(
+        requests[1].header("WWSVC-ACCEPT-RESULT-MAX-LINES"),
+        Some("2")
+    );
+}
+
+#[test]
+fn test_cursor_state_round_trip() {
+    let mut cursor = Cursor::new(100);
+    cursor.set_cursor_id("C7".to_string());
+    cursor.state = Some(CursorState::Other("PAUSED".to_string()));
+    cursor.remaining_rows = Some(40);
+    cursor.pages = 3;
+
+    let json = serde_json::to_value(cursor.to_state()).unwrap();
+    assert_eq!(json["state"], "PAUSED");
+    let restored = Cursor::from_state(serde_json::from_value(json).unwrap());
+    assert_eq!(restored.to_state(), cursor.to_state());
+    assert_eq!(restored.to_string(), cursor.to_string());
+}
+
+#[tokio::test]
+async fn test_cursors_are_isolated() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "A"));
+    server.enqueue(MockResponse::json(articles(&["B1"])).header("WWSVC-CURSOR", "B"));
+    server.enqueue(MockResponse::json(articles(&["A2"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await;
+    let mut first = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    first.next().await.unwrap().unwrap();
+    let first_cursor = first.into_cursor();
+
+    let mut second = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ADRESSE.GET",
+        1,
+        Parameters::new(),
+    );
+    second.next().await.unwrap().unwrap();
+    assert_eq!(second.into_cursor().cursor_id, "B");
+
+    let mut first = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .with_cursor(first_cursor);
+    let page = first.next_page().await.unwrap().unwrap();
+    assert_eq!(page.items[0].article_number, "A2");
+    assert_eq!(page.page_index, 1);
+    assert!(first.next().await.is_none());
+    drop(first);
+
+    // requests outside of the cursored requests don't carry a cursor
+    client
+        .request(Some(Method::PUT), "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let cursors = server
+        .requests()
+        .iter()
+        .map(|r| r.header("WWSVC-CURSOR").map(str::to_string))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        cursors,
+        vec![
+            Some("CREATE".to_string()),
+            Some("CREATE".to_string()),
+            Some("A".to_string()),
+            None
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_abandoned_cursor_is_closed() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "C1"));
+
+    let mut client = server.client().await;
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    pages.next().await.unwrap().unwrap();
+    drop(pages);
+
+    client
+        .request(Some(Method::PUT), "ADRESSE.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[1].json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ARTIKEL.GET"
+    );
+    assert_eq!(requests[1].header("WWSVC-CURSOR"), Some("C1"));
+    assert_eq!(requests[1].header("WWSVC-CURSOR-STATE"), Some("CLOSED"));
+    assert_eq!(
+        requests[2].json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ADRESSE.GET"
+    );
+    assert_eq!(requests[2].header("WWSVC-CURSOR"), None);
+
+    // the cursor is only closed once
+    client.close_abandoned_cursors().await;
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_finished_and_detached_cursors_are_not_closed() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "CLOSED"));
+    server.enqueue(MockResponse::json(articles(&["B1"])).header("WWSVC-CURSOR", "C2"));
+
+    let mut client = server.client().await;
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    while let Some(page) = pages.next().await {
+        page.unwrap();
+    }
+    drop(pages);
+
+    let mut pages = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    pages.next().await.unwrap().unwrap();
+    let cursor = pages.into_cursor();
+    assert_eq!(cursor.cursor_id, "C2");
+
+    // a response which hasn't requested a page doesn't have a cursor to close
+    drop(
+        client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        ),
+    );
+
+    client.close_abandoned_cursors().await;
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_interleaved_cursors_on_open_cursor_client() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "A"));
+    server.enqueue(MockResponse::json(articles(&["B1"])).header("WWSVC-CURSOR", "B"));
+    server.enqueue(MockResponse::json(articles(&["A2"])).header("WWSVC-CURSOR", "CLOSED"));
+    server.enqueue(MockResponse::json(articles(&["B2"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let mut client = server.client().await.create_cursor(1);
+    let mut first = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    first.next().await.unwrap().unwrap();
+    let first_cursor = first.into_cursor();
+
+    let mut second = client.cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+        Method::PUT,
+        "ADRESSE.GET",
+        1,
+        Parameters::new(),
+    );
+    assert_eq!(second.cursor().cursor_id, "CREATE");
+    assert_eq!(second.cursor().max_lines, 1);
+    second.next().await.unwrap().unwrap();
+    let second_cursor = second.into_cursor();
+
+    let mut first = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+        )
+        .with_cursor(first_cursor);
+    assert_eq!(first.next().await.unwrap().unwrap()[0].article_number, "A2");
+    assert!(first.next().await.is_none());
+    drop(first);
+
+    let mut second = client
+        .cursored_request::<ArticleData, ArtikelGetResponse<ArticleData>>(
+            Method::PUT,
+            "ADRESSE.GET",
+            1,
+            Parameters::new(),
+        )
+        .with_cursor(second_cursor);
+    assert_eq!(
+        second.next().await.unwrap().unwrap()[0].article_number,
+        "B2"
+    );
+    drop(second);
+
+    // the cursor of the client isn't touched by the cursored responses
+    assert_eq!(client.cursor().unwrap().cursor_id, "CREATE");
+    let cursors = server
+        .requests()
+        .iter()
+        .map(|r| r.header("WWSVC-CURSOR").map(str::to_string))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        cursors,
+        vec![
+            Some("CREATE".to_string()),
+            Some("CREATE".to_string()),
+            Some("A".to_string()),
+            Some("B".to_string()),
+        ]
+    );
+}
@@ -1,4 +1,7 @@
-use wwsvc_rs::{collection, WWSVCGetData};
+mod common;
+
+use common::{MockResponse, MockServer};
+use wwsvc_rs::{collection, Parameters, WWSVCGetData};
 
 #[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
 #[wwsvc(function = "ARTIKEL")]
@@ -41,3 +44,825 @@ async fn test_articles() {
 
     registered_client.deregister().await.unwrap();
 }
+
+#[test]
+fn test_derived_response() {
+    use wwsvc_rs::WWResponse;
+
+    let response: <ArticleData as WWSVCGetData>::Response = serde_json::from_str(
+        r#"{
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent"}]}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(response.com_result().unwrap().status, 200);
+    assert_eq!(response.items()[0].article_number, "Artikel19Prozent");
+
+    let response: <ArticleData as WWSVCGetData>::Response =
+        serde_json::from_str(r#"{"COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}}"#)
+            .unwrap();
+    assert!(response.items().is_empty());
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "ARTIKEL")]
+pub struct FullArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[wwsvc(extra)]
+    #[serde(flatten)]
+    pub extra: std::collections::BTreeMap<String, serde_json::Value>,
+}
+
+#[test]
+fn test_extra_fields() {
+    use wwsvc_rs::WWResponse;
+
+    assert_eq!(ArticleData::FIELDS, "ART_1_25");
+    assert_eq!(FullArticleData::FIELDS, "");
+
+    let response: <FullArticleData as WWSVCGetData>::Response = serde_json::from_str(
+        r#"{
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent", "ART_2_80": "Musterartikel"}]}
+        }"#,
+    )
+    .unwrap();
+
+    let article = &response.items()[0];
+    assert_eq!(article.article_number, "Artikel19Prozent");
+    assert_eq!(article.extra.len(), 1);
+    assert_eq!(article.extra["ART_2_80"], "Musterartikel");
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "ARTIKEL", fields_merge = "union")]
+pub struct UnionArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[tokio::test]
+async fn test_fields_merge() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    ArticleData::get(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    ArticleData::get(&mut client, Parameters::new().param("FELDER", "ART_2_80"))
+        .await
+        .unwrap();
+    UnionArticleData::get(
+        &mut client,
+        Parameters::new().param("FELDER", "ART_2_80,ART_1_25,ART_3_10"),
+    )
+    .await
+    .unwrap();
+    UnionArticleData::get(&mut client, Parameters::new().param("FELDER", "ART_2_80"))
+        .await
+        .unwrap();
+
+    let fields = server
+        .requests()
+        .iter()
+        .map(|request| {
+            request.json()["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        vec![
+            "ART_1_25",
+            "ART_2_80",
+            "ART_2_80,ART_1_25,ART_3_10",
+            "ART_2_80,ART_1_25"
+        ]
+    );
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "ARTIKEL", strict)]
+pub struct StrictArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[test]
+fn test_strict_response() {
+    use wwsvc_rs::WWResponse;
+
+    let response: <StrictArticleData as WWSVCGetData>::Response = serde_json::from_str(
+        r#"{
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent"}]}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(response.items().len(), 1);
+
+    let renamed = r#"{
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLIST": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent"}]}
+    }"#;
+    assert!(serde_json::from_str::<<ArticleData as WWSVCGetData>::Response>(renamed).is_ok());
+    assert!(
+        serde_json::from_str::<<StrictArticleData as WWSVCGetData>::Response>(renamed).is_err()
+    );
+}
+
+#[derive(wwsvc_rs::WWSVCPutData, serde::Serialize)]
+#[wwsvc(function = "ARTIKEL", version = 2)]
+pub struct ArticleUpdate {
+    #[serde(rename = "ARTNR")]
+    pub article_number: String,
+    #[serde(rename = "ART_1_80")]
+    pub stock: u32,
+    #[serde(rename = "ART_2_10")]
+    pub comment: Option<String>,
+}
+
+#[tokio::test]
+async fn test_put_data() {
+    use wwsvc_rs::WWSVCPutData;
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let update = ArticleUpdate {
+        article_number: "Artikel19Prozent".to_string(),
+        stock: 3,
+        comment: None,
+    };
+    assert_eq!(ArticleUpdate::FUNCTION, "ARTIKEL.PUT");
+    assert_eq!(ArticleUpdate::VERSION, 2);
+
+    let com_result = update
+        .put(&mut client, Parameters::new().param("ART_1_80", "4"))
+        .await
+        .unwrap();
+    assert_eq!(com_result.status, 200);
+
+    let requests = server.requests();
+    let request = requests.last().unwrap();
+    assert_eq!(request.method, "PUT");
+    let function = &request.json()["WWSVC_FUNCTION"];
+    assert_eq!(function["FUNCTIONNAME"], "ARTIKEL.PUT");
+    assert_eq!(function["REVISION"], 2);
+    assert_eq!(
+        function["PARAMETER"],
+        serde_json::json!([
+            {"PNAME": "ARTNR", "PCONTENT": "Artikel19Prozent"},
+            {"PNAME": "ART_1_80", "PCONTENT": "4"}
+        ])
+    );
+
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 404, "CODE": "Not Found", "INFO": "Artikel nicht gefunden"}
+    })));
+    let error = update
+        .put(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        wwsvc_rs::WWSVCError::FunctionFailed { ref function, ref com_result }
+            if function == "ARTIKEL.PUT" && com_result.status == 404
+    ));
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "ARTIKEL", max_lines = 50, timeout_secs = 1)]
+pub struct TunedArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[tokio::test]
+async fn test_max_lines_and_timeout() {
+    assert_eq!(TunedArticleData::MAX_LINES, Some(50));
+    assert_eq!(
+        TunedArticleData::TIMEOUT,
+        Some(std::time::Duration::from_secs(1))
+    );
+    assert_eq!(ArticleData::MAX_LINES, None);
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    TunedArticleData::get(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    let requests = server.requests();
+    assert_eq!(
+        requests
+            .last()
+            .unwrap()
+            .header("WWSVC-ACCEPT-RESULT-MAX-LINES"),
+        Some("50")
+    );
+
+    server.enqueue(common::default_response().delay(std::time::Duration::from_secs(3)));
+    let error = TunedArticleData::get(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, wwsvc_rs::WWSVCError::ReqwestError(ref e) if e.is_timeout()));
+
+    let mut client = client.create_cursor(500);
+    server.enqueue(
+        MockResponse::json(serde_json::json!({
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent"}]}
+        }))
+        .header("WWSVC-CURSOR", "CLOSED"),
+    );
+    let mut pages = TunedArticleData::get_cursored(&mut client, Parameters::new());
+    let page = pages.next().await.unwrap().unwrap();
+    assert_eq!(page[0].article_number, "Artikel19Prozent");
+    let requests = server.requests();
+    let request = requests.last().unwrap();
+    assert_eq!(request.header("WWSVC-ACCEPT-RESULT-MAX-LINES"), Some("50"));
+    assert_eq!(request.header("WWSVC-CURSOR"), Some("CREATE"));
+    assert_eq!(
+        request.json()["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "ART_1_25"
+    );
+}
+
+#[derive(Debug, serde::Deserialize, PartialEq)]
+pub struct ArticleKey {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[derive(wwsvc_rs::WWSVCInsertData, serde::Serialize)]
+#[wwsvc(function = "ARTIKEL", created = "ArticleKey")]
+pub struct NewArticle {
+    #[serde(rename = "ART_1_10")]
+    pub description: String,
+}
+
+#[tokio::test]
+async fn test_insert_data() {
+    use wwsvc_rs::WWSVCInsertData;
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    assert_eq!(NewArticle::FUNCTION, "ARTIKEL.INSERT");
+    assert_eq!(NewArticle::CONTAINER, "ARTIKEL");
+
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKEL": {"ART_1_25": "A1000"}
+    })));
+    let article = NewArticle {
+        description: "Neuer Artikel".to_string(),
+    };
+    let response = article
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    assert_eq!(response.com_result.status, 200);
+    assert_eq!(
+        response.record,
+        ArticleKey {
+            article_number: "A1000".to_string()
+        }
+    );
+
+    let requests = server.requests();
+    let function = &requests.last().unwrap().json()["WWSVC_FUNCTION"];
+    assert_eq!(function["FUNCTIONNAME"], "ARTIKEL.INSERT");
+    assert_eq!(function["PARAMETER"][0]["PNAME"], "ART_1_10");
+    assert_eq!(function["PARAMETER"][0]["PCONTENT"], "Neuer Artikel");
+
+    // lists and missing records
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKEL": [{"ART_1_25": "A1001"}]
+    })));
+    let response = article
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    assert_eq!(response.record.article_number, "A1001");
+    let error = article
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, wwsvc_rs::WWSVCError::MissingField(ref field) if field == "ARTIKEL"));
+
+    // failed inserts
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 409, "CODE": "Conflict", "INFO": "Artikel existiert bereits"}
+    })));
+    let error = article
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        wwsvc_rs::WWSVCError::FunctionFailed { ref function, ref com_result }
+            if function == "ARTIKEL.INSERT" && com_result.status == 409
+    ));
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "ARTIKEL", version = "auto")]
+pub struct AutoVersionArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[tokio::test]
+async fn test_auto_version() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let revision = |server: &MockServer| {
+        server.requests().last().unwrap().json()["WWSVC_FUNCTION"]["REVISION"].clone()
+    };
+
+    AutoVersionArticleData::get(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    assert_eq!(revision(&server), 1);
+
+    client.set_function_version("artikel.get", 3);
+    assert_eq!(client.function_version("ARTIKEL.GET"), Some(3));
+    AutoVersionArticleData::get(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    assert_eq!(revision(&server), 3);
+    // fixed versions ignore the client
+    ArticleData::get(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    assert_eq!(revision(&server), 1);
+}
+
+#[derive(wwsvc_rs::WWSVCDeleteData)]
+#[wwsvc(function = "ARTIKEL")]
+pub struct Article;
+
+#[tokio::test]
+async fn test_delete_data() {
+    use wwsvc_rs::{ErrorCategory, WWSVCDeleteData, WWSVCError};
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    assert_eq!(Article::FUNCTION, "ARTIKEL.DELETE");
+
+    let com_result = Article::delete(&mut client, collection! { "ARTNR" => "A1000" })
+        .await
+        .unwrap();
+    assert!(com_result.is_success());
+    let requests = server.requests();
+    let function = &requests.last().unwrap().json()["WWSVC_FUNCTION"];
+    assert_eq!(function["FUNCTIONNAME"], "ARTIKEL.DELETE");
+    assert_eq!(function["PARAMETER"][0]["PNAME"], "ARTNR");
+
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 404, "CODE": "Not Found", "INFO": "Artikel nicht gefunden"}
+    })));
+    let error = Article::delete(&mut client, collection! { "ARTNR" => "A1001" })
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        WWSVCError::FunctionFailed { ref function, ref com_result }
+            if function == "ARTIKEL.DELETE" && com_result.status == 404
+    ));
+    assert_eq!(error.category(), ErrorCategory::Client);
+    assert_eq!(
+        error.to_string(),
+        "`ARTIKEL.DELETE` failed with status 404 (Not Found): Artikel nicht gefunden"
+    );
+}
+
+#[tokio::test]
+async fn test_get_cursored() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await.create_cursor(1);
+    for (article, cursor) in [("A1", "C1"), ("A2", "CLOSED")] {
+        server.enqueue(
+            MockResponse::json(serde_json::json!({
+                "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+                "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": article}]}
+            }))
+            .header("WWSVC-CURSOR", cursor),
+        );
+    }
+
+    let mut pages =
+        ArticleData::get_cursored(&mut client, Parameters::new().param("FELDER", "ART_1_10"));
+    let mut articles = Vec::new();
+    while let Some(page) = pages.next().await {
+        articles.extend(page.unwrap().into_iter().map(|a| a.article_number));
+    }
+    assert_eq!(articles, ["A1", "A2"]);
+    assert_eq!(pages.stats().pages, 2);
+
+    let requests = server.requests();
+    let cursors = requests
+        .iter()
+        .rev()
+        .take(2)
+        .map(|request| request.header("WWSVC-CURSOR").unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(cursors, ["C1", "CREATE"]);
+    // FELDER of the caller are respected
+    assert_eq!(
+        requests.last().unwrap().json()["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "ART_1_10"
+    );
+}
+
+#[derive(wwsvc_rs::WWSVCRecord, Debug, serde::Serialize, serde::Deserialize)]
+#[wwsvc(
+    function = "ARTIKEL",
+    ops(get, put, delete),
+    suffixes(put = "UPDATE"),
+    methods(get = "post")
+)]
+pub struct ArticleRecord {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[serde(rename = "ART_1_10", default)]
+    pub description: String,
+}
+
+#[tokio::test]
+async fn test_record() {
+    use wwsvc_rs::traits::RecordOperation;
+    use wwsvc_rs::{WWSVCError, WWSVCRecord};
+
+    assert_eq!(ArticleRecord::FIELDS, "ART_1_25,ART_1_10");
+    assert_eq!(ArticleRecord::suffix(RecordOperation::Update), "UPDATE");
+    assert_eq!(ArticleRecord::suffix(RecordOperation::Get), "GET");
+    assert_eq!(
+        ArticleRecord::method(RecordOperation::Get),
+        wwsvc_rs::Method::POST
+    );
+    assert_eq!(
+        ArticleRecord::method(RecordOperation::Delete),
+        wwsvc_rs::Method::PUT
+    );
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "A1", "ART_1_10": "Artikel"}]}
+    })));
+    let mut records = <ArticleRecord as WWSVCRecord>::get(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    assert_eq!(records[0].article_number, "A1");
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, "POST");
+    assert_eq!(
+        request.json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ARTIKEL.GET"
+    );
+    assert_eq!(
+        request.json()["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "ART_1_25,ART_1_10"
+    );
+
+    records[0].description = "Geändert".to_string();
+    records[0]
+        .update(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, "PUT");
+    assert_eq!(
+        request.json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ARTIKEL.UPDATE"
+    );
+
+    <ArticleRecord as WWSVCRecord>::delete(&mut client, collection! { "ARTNR" => "A1" })
+        .await
+        .unwrap();
+    assert_eq!(
+        server.requests().pop().unwrap().json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ARTIKEL.DELETE"
+    );
+
+    let requests = server.requests().len();
+    let error = records[0]
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, WWSVCError::FunctionNotPermitted(ref function) if function == "ARTIKEL.INSERT")
+    );
+    assert_eq!(server.requests().len(), requests);
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "LAGER", key(artnr = "ARTNR", lager = "LAGER"))]
+pub struct StockData {
+    #[serde(rename = "LAG_1_80")]
+    pub stock: String,
+}
+
+#[tokio::test]
+async fn test_get_by_key() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let key = StockDataKey {
+        artnr: "A1".to_string(),
+        lager: "2".to_string(),
+    };
+    assert_eq!(
+        Parameters::from(key.clone()).to_string(),
+        "ARTNR=A1, LAGER=2"
+    );
+
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "LAGERLISTE": {"LAGER": [{"LAG_1_80": "12"}]}
+    })));
+    let stock = StockData::get_by_key(&mut client, key.clone())
+        .await
+        .unwrap();
+    assert_eq!(stock.unwrap().stock, "12");
+    let request = server.requests().pop().unwrap();
+    let parameters = &request.json()["WWSVC_FUNCTION"]["PARAMETER"];
+    assert_eq!(parameters[0]["PNAME"], "ARTNR");
+    assert_eq!(parameters[1]["PNAME"], "LAGER");
+    assert_eq!(parameters[1]["PCONTENT"], "2");
+
+    assert!(StockData::get_by_key(&mut client, key)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+#[wwsvc(function = "ARTIKEL")]
+pub struct DescribedArticle {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[serde(rename = "ART_1_40")]
+    pub stock: Option<f64>,
+    #[serde(rename = "ART_1_41")]
+    pub blocked: bool,
+}
+
+#[test]
+fn test_openapi_description() {
+    use wwsvc_rs::schema::{EntityRegistry, SchemaType};
+
+    let description = DescribedArticle::description();
+    assert_eq!(description.name, "DescribedArticle");
+    assert_eq!(description.function, "ARTIKEL.GET");
+    assert_eq!(description.fields[1].field, "stock");
+    assert_eq!(description.fields[1].schema_type, SchemaType::Number);
+    assert!(description.fields[1].nullable);
+
+    let document = EntityRegistry::new()
+        .register::<DescribedArticle>()
+        .register::<StockData>()
+        .register::<DescribedArticle>()
+        .to_openapi("Articles", "1.0.0");
+    assert_eq!(document["info"]["title"], "Articles");
+    let schemas = document["components"]["schemas"].as_object().unwrap();
+    assert_eq!(schemas.len(), 2);
+    let article = &schemas["DescribedArticle"];
+    assert_eq!(article["x-wwsvc-function"], "ARTIKEL.GET");
+    assert_eq!(article["properties"]["ART_1_25"]["type"], "string");
+    assert_eq!(article["properties"]["ART_1_41"]["type"], "boolean");
+    assert_eq!(article["properties"]["ART_1_40"]["nullable"], true);
+    assert_eq!(
+        article["required"],
+        serde_json::json!(["ART_1_25", "ART_1_41"])
+    );
+}
+
+#[derive(wwsvc_rs::WWSVCRecord, Debug, serde::Serialize, serde::Deserialize)]
+#[wwsvc(function = "ADRESSE")]
+pub struct AddressRecord {
+    #[serde(rename = "ADR_0_1")]
+    pub number: String,
+    #[serde(rename = "ADR_1_2")]
+    pub name: String,
+}
+
+#[tokio::test]
+async fn test_upsert() {
+    use wwsvc_rs::traits::Upserted;
+    use wwsvc_rs::{WWSVCError, WWSVCRecord};
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let address = AddressRecord {
+        number: "10000".to_string(),
+        name: "Muster GmbH".to_string(),
+    };
+    let ok = serde_json::json!({"STATUS": 200, "CODE": "OK", "INFO": ""});
+
+    server.enqueue(MockResponse::json(
+        serde_json::json!({"COMRESULT": ok, "ADRESSELISTE": {}}),
+    ));
+    server.enqueue(MockResponse::json(
+        serde_json::json!({"COMRESULT": ok, "ADRESSE": [{"ADR_0_1": "10000"}]}),
+    ));
+    let upserted = AddressRecord::upsert(&mut client, collection! { "ADRNR" => "10000" }, &address)
+        .await
+        .unwrap();
+    assert!(upserted.is_inserted());
+    assert_eq!(upserted.com_result().status, 200);
+    let requests = server.requests();
+    let functions = requests
+        .iter()
+        .rev()
+        .take(2)
+        .map(|request| request.json()["WWSVC_FUNCTION"]["FUNCTIONNAME"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(functions, vec!["ADRESSE.INSERT", "ADRESSE.GET"]);
+
+    // the list member is omitted when no rows match
+    server.enqueue(MockResponse::json(serde_json::json!({"COMRESULT": ok})));
+    server.enqueue(MockResponse::json(
+        serde_json::json!({"COMRESULT": ok, "ADRESSE": {"ADR_0_1": "10001"}}),
+    ));
+    let upserted = AddressRecord::upsert(&mut client, collection! { "ADRNR" => "10001" }, &address)
+        .await
+        .unwrap();
+    assert!(upserted.is_inserted());
+    assert_eq!(
+        server.requests().pop().unwrap().json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "ADRESSE.INSERT"
+    );
+
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": ok,
+        "ADRESSELISTE": {"ADRESSE": [{"ADR_0_1": "10000", "ADR_1_2": "Alt"}]}
+    })));
+    server.enqueue(MockResponse::json(serde_json::json!({"COMRESULT": ok})));
+    let upserted = AddressRecord::upsert(&mut client, collection! { "ADRNR" => "10000" }, &address)
+        .await
+        .unwrap();
+    assert!(matches!(upserted, Upserted::Updated(_)));
+    let request = server.requests().pop().unwrap();
+    let body = request.json();
+    assert_eq!(body["WWSVC_FUNCTION"]["FUNCTIONNAME"], "ADRESSE.PUT");
+    assert!(body["WWSVC_FUNCTION"]["PARAMETER"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|parameter| parameter["PNAME"] == "ADRNR"));
+
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 409, "CODE": "Conflict", "INFO": "Adresse existiert bereits"}
+    })));
+    let error = address
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        WWSVCError::FunctionFailed { ref function, ref com_result }
+            if function == "ADRESSE.INSERT" && com_result.status == 409
+    ));
+
+    // a failed existence check doesn't lead to an insert
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 500, "CODE": "Internal Server Error", "INFO": ""},
+        "ADRESSELISTE": {}
+    })));
+    let requests = server.requests().len();
+    let error = AddressRecord::upsert(&mut client, collection! { "ADRNR" => "10000" }, &address)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        WWSVCError::FunctionFailed { ref function, .. } if function == "ADRESSE.GET"
+    ));
+    assert_eq!(server.requests().len(), requests + 1);
+
+    let requests = server.requests().len();
+    let record = ArticleRecord {
+        article_number: "A1".to_string(),
+        description: String::new(),
+    };
+    let error = ArticleRecord::upsert(&mut client, collection! { "ARTNR" => "A1" }, &record)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, WWSVCError::FunctionNotPermitted(ref function) if function == "ARTIKEL.INSERT")
+    );
+    assert_eq!(server.requests().len(), requests);
+}
+
+#[derive(wwsvc_rs::WWSVCPutData, wwsvc_rs::WWSVCInsertData, serde::Serialize)]
+#[wwsvc(function = "ARTIKEL", validate)]
+pub struct ValidatedArticle {
+    #[serde(rename = "ARTNR")]
+    pub article_number: String,
+    #[serde(rename = "ART_1_10")]
+    pub description: String,
+}
+
+impl wwsvc_rs::validation::Validate for ValidatedArticle {
+    fn validate(&self) -> wwsvc_rs::WWClientResult<()> {
+        wwsvc_rs::validation::required("ARTNR", &self.article_number)?;
+        wwsvc_rs::validation::max_chars("ART_1_10", &self.description, 10)
+    }
+}
+
+#[tokio::test]
+async fn test_validate_before_write() {
+    use wwsvc_rs::{WWSVCError, WWSVCInsertData, WWSVCPutData};
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let requests = server.requests().len();
+
+    let article = ValidatedArticle {
+        article_number: " ".to_string(),
+        description: "Schraube".to_string(),
+    };
+    let error = article
+        .put(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WWSVCError::InvalidField { ref field, .. } if field == "ARTNR"));
+
+    let article = ValidatedArticle {
+        article_number: "A1".to_string(),
+        description: "Sechskantschraube".to_string(),
+    };
+    let error = article
+        .insert(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WWSVCError::InvalidField { ref field, .. } if field == "ART_1_10"));
+    assert_eq!(server.requests().len(), requests);
+
+    let article = ValidatedArticle {
+        article_number: "A1".to_string(),
+        description: "Schraube".to_string(),
+    };
+    article.put(&mut client, Parameters::new()).await.unwrap();
+    assert_eq!(server.requests().len(), requests + 1);
+}
+
+#[derive(wwsvc_rs::WWSVCPutData, serde::Serialize)]
+#[wwsvc(function = "ARTIKEL")]
+pub struct SizedArticle {
+    #[wwsvc(field = "ART_1_25", max_len = 5)]
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[wwsvc(max_len = 8, truncate)]
+    #[serde(rename = "ART_1_10")]
+    pub description: String,
+}
+
+#[tokio::test]
+async fn test_field_widths() {
+    use wwsvc_rs::validation::FieldWidth;
+    use wwsvc_rs::{WWSVCError, WWSVCPutData};
+
+    assert_eq!(
+        SizedArticle::FIELD_WIDTHS,
+        &[
+            FieldWidth {
+                name: "ART_1_25",
+                max_len: 5,
+                truncate: false,
+            },
+            FieldWidth {
+                name: "ART_1_10",
+                max_len: 8,
+                truncate: true,
+            },
+        ]
+    );
+
+    let article = SizedArticle {
+        article_number: "A1".to_string(),
+        description: "Sechskantschraube".to_string(),
+    };
+    let parameters = article.to_parameters().unwrap();
+    assert_eq!(parameters.get("ART_1_10"), Some("Sechskan"));
+
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let requests = server.requests().len();
+    let article = SizedArticle {
+        article_number: "A12345".to_string(),
+        description: String::new(),
+    };
+    let error = article
+        .put(&mut client, Parameters::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WWSVCError::InvalidField { ref field, .. } if field == "ART_1_25"));
+    assert_eq!(server.requests().len(), requests);
+}
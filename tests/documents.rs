@@ -0,0 +1,86 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use wwsvc_rs::documents::{detect_content_type, Document, DocumentCache};
+use wwsvc_rs::headers::ACCEPT_RESULT_TYPE;
+
+#[test]
+fn test_detect_content_type() {
+    assert_eq!(
+        detect_content_type(b"\x89PNG\r\n\x1a\n\x00\x00"),
+        Some("image/png")
+    );
+    assert_eq!(detect_content_type(b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+    assert_eq!(
+        detect_content_type(b"RIFF\x00\x00\x00\x00WEBPVP8 "),
+        Some("image/webp")
+    );
+    assert_eq!(detect_content_type(b"%PDF-1.7"), Some("application/pdf"));
+    assert_eq!(detect_content_type(b"plain text"), None);
+}
+
+#[tokio::test]
+async fn test_document_cache() {
+    let directory = std::env::temp_dir().join(format!("wwsvc-rs-cache-{}", std::process::id()));
+    let cache = DocumentCache::new(&directory);
+    let document = Document::new(b"GIF89a...".to_vec().into(), None);
+
+    assert!(cache.get("ART/1", "2024-01-01").await.unwrap().is_none());
+    cache.put("ART/1", "2024-01-01", &document).await.unwrap();
+
+    let cached = cache.get("ART/1", "2024-01-01").await.unwrap().unwrap();
+    assert_eq!(cached.bytes, document.bytes);
+    assert_eq!(cached.content_type, "image/gif");
+    assert!(cache.get("ART/1", "2024-01-02").await.unwrap().is_none());
+    // keys which only differ in special characters have distinct entries
+    for key in ["ART-1", "ART_1", "ART.1"] {
+        assert!(cache.get(key, "2024-01-01").await.unwrap().is_none());
+    }
+
+    // declared content types are kept
+    let csv = Document::new(b"ARTNR;BEZ".to_vec().into(), Some("text/csv"));
+    cache.put("ART-1", "2024-01-01", &csv).await.unwrap();
+    let cached = cache.get("ART-1", "2024-01-01").await.unwrap().unwrap();
+    assert_eq!(cached.content_type, "text/csv");
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[tokio::test]
+async fn test_fetch_article_image() {
+    let directory = std::env::temp_dir().join(format!("wwsvc-rs-images-{}", std::process::id()));
+    let cache = DocumentCache::new(&directory);
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    server.enqueue(MockResponse {
+        status: 200,
+        headers: vec![("Content-Type".to_string(), "image/svg+xml".to_string())],
+        body: b"<svg/>".to_vec(),
+        delay: None,
+    });
+
+    let image = client
+        .fetch_article_image("A-1", Some("2024-01-01"), Some(&cache))
+        .await
+        .unwrap();
+    assert_eq!(&image.bytes[..], b"<svg/>");
+    assert_eq!(image.content_type, "image/svg+xml");
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "PUT");
+    assert_eq!(requests[0].header(ACCEPT_RESULT_TYPE), Some("BIN"));
+    let function = &requests[0].json()["WWSVC_FUNCTION"];
+    assert_eq!(function["FUNCTIONNAME"], "ARTIKELBILD.GET");
+    assert_eq!(function["PARAMETER"][0]["PCONTENT"], "A-1");
+
+    // the second call is answered from the cache
+    let cached = client
+        .fetch_article_image("A-1", Some("2024-01-01"), Some(&cache))
+        .await
+        .unwrap();
+    assert_eq!(cached.bytes, image.bytes);
+    assert_eq!(cached.content_type, "image/svg+xml");
+    assert_eq!(server.requests().len(), 1);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
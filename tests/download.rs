@@ -0,0 +1,47 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use wwsvc_rs::Parameters;
+
+#[tokio::test]
+async fn test_request_to_file() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "A1"}]}
+    })));
+    server.enqueue(MockResponse::json(json!({})).status(500));
+
+    let directory = std::env::temp_dir().join(format!("wwsvc-rs-download-{}", std::process::id()));
+    tokio::fs::create_dir_all(&directory).await.unwrap();
+    let path = directory.join("articles.json");
+
+    let mut client = server.client().await;
+    let download = client
+        .request_to_file(None, "ARTIKEL.GET", 1, Parameters::new(), &path)
+        .await
+        .unwrap();
+
+    let content = tokio::fs::read(&path).await.unwrap();
+    assert_eq!(download.path, path);
+    assert_eq!(download.size, content.len() as u64);
+    let expected = Sha256::digest(&content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    assert_eq!(download.sha256, expected);
+    let value: serde_json::Value = serde_json::from_slice(&content).unwrap();
+    assert_eq!(value["ARTIKELLISTE"]["ARTIKEL"][0]["ART_1_25"], "A1");
+
+    // a failed request leaves the previous file in place
+    let result = client
+        .request_to_file(None, "ARTIKEL.GET", 1, Parameters::new(), &path)
+        .await;
+    assert!(result.is_err());
+    assert_eq!(tokio::fs::read(&path).await.unwrap(), content);
+    assert!(!directory.join("articles.json.part").exists());
+
+    tokio::fs::remove_dir_all(&directory).await.unwrap();
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[serde(rename = "ART_2_80")]
+    pub description: String,
+}
+
+#[tokio::test]
+async fn test_resolve_by_ean() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "EANCODELISTE": {"EANCODE": [{"ART_1_25": "Artikel19Prozent", "EAN_2_13": "4006381333931"}]}
+    })));
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent", "ART_2_80": "Schraube"}]}
+    })));
+
+    let mut client = server.client().await;
+    let found = client
+        .resolve_by_ean::<ArticleData>("4006381333931")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(found.article_number, "Artikel19Prozent");
+    assert_eq!(found.article.description, "Schraube");
+    assert_eq!(found.ean["EAN_2_13"], "4006381333931");
+
+    let requests = server.requests();
+    let first = requests[0].json();
+    assert_eq!(first["WWSVC_FUNCTION"]["FUNCTIONNAME"], "EANCODE.GET");
+    assert_eq!(
+        first["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "4006381333931"
+    );
+    let second = requests[1].json();
+    assert_eq!(second["WWSVC_FUNCTION"]["FUNCTIONNAME"], "ARTIKEL.GET");
+    assert_eq!(
+        second["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "Artikel19Prozent"
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_by_ean_not_found() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "EANCODELISTE": {}
+    })));
+
+    let mut client = server.client().await;
+    let found = client
+        .resolve_by_ean::<ArticleData>("0000000000000")
+        .await
+        .unwrap();
+    assert!(found.is_none());
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,28 @@
+use std::error::Error;
+
+use wwsvc_rs::{ErrorCategory, WWSVCError};
+
+#[test]
+fn test_error_categories() {
+    assert_eq!(WWSVCError::NotAuthenticated.category(), ErrorCategory::Auth);
+    assert_eq!(
+        WWSVCError::MissingCredentials.category(),
+        ErrorCategory::Auth
+    );
+    assert_eq!(WWSVCError::InvalidHeader.category(), ErrorCategory::Client);
+
+    let url_error: WWSVCError = url::Url::parse("not a url").unwrap_err().into();
+    assert_eq!(url_error.category(), ErrorCategory::Client);
+
+    let json_error: WWSVCError = serde_json::from_str::<u32>("{}").unwrap_err().into();
+    assert_eq!(json_error.category(), ErrorCategory::Protocol);
+}
+
+#[test]
+fn test_error_source_chain() {
+    let url_error: WWSVCError = url::Url::parse("not a url").unwrap_err().into();
+    let source = url_error.source().expect("missing source");
+    assert!(source.downcast_ref::<url::ParseError>().is_some());
+
+    assert!(WWSVCError::NotAuthenticated.source().is_none());
+}
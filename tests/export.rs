@@ -0,0 +1,73 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use futures::StreamExt;
+use serde_json::json;
+use wwsvc_rs::{Parameters, WWSVCError};
+
+fn articles(numbers: &[&str]) -> serde_json::Value {
+    json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {
+            "ARTIKEL": numbers.iter().map(|n| json!({"ART_1_25": n})).collect::<Vec<_>>()
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_json_stream() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1", "A2"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&[])).header("WWSVC-CURSOR", "C2"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let client = server.client().await.create_cursor(2);
+    let chunks = client
+        .into_json_stream(None, "ARTIKEL.GET", 1, Parameters::new())
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(chunks.len(), 4);
+
+    let body = chunks.concat();
+    let rows: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        rows,
+        json!([{"ART_1_25": "A1"}, {"ART_1_25": "A2"}, {"ART_1_25": "A3"}])
+    );
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_json_stream_error() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A2"])));
+
+    let client = server.client().await.create_cursor(1);
+    let chunks = client
+        .into_json_stream(None, "ARTIKEL.GET", 1, Parameters::new())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(chunks.len(), 3);
+    assert!(matches!(chunks[2], Err(WWSVCError::CursorLost)));
+}
+
+#[tokio::test]
+async fn test_json_stream_is_static() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "CLOSED"));
+
+    let client = server.client().await.create_cursor(10);
+    let stream = client.into_json_stream(None, "ARTIKEL.GET", 1, Parameters::new());
+    let body = tokio::spawn(async move {
+        stream
+            .map(|chunk| chunk.unwrap())
+            .collect::<Vec<_>>()
+            .await
+            .concat()
+    })
+    .await
+    .unwrap();
+    assert_eq!(body, br#"[{"ART_1_25":"A1"}]"#);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{MockResponse, MockServer};
+use reqwest::Method;
+use serde_json::json;
+use wwsvc_rs::test_util::{inject_faults, CursorFault, FaultInjection, INVALID_CURSOR_ID};
+use wwsvc_rs::{ErrorCategory, Parameters, WWSVCError};
+
+fn articles(numbers: &[&str]) -> serde_json::Value {
+    json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {
+            "ARTIKEL": numbers.iter().map(|n| json!({"ART_1_25": n})).collect::<Vec<_>>()
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_drop_every() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let _guard = inject_faults(FaultInjection::new().drop_every(2));
+
+    let mut results = Vec::new();
+    for _ in 0..4 {
+        results.push(
+            client
+                .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+                .await,
+        );
+    }
+    assert!(results[0].is_ok());
+    assert!(matches!(&results[1], Err(WWSVCError::Timeout(_))));
+    assert_eq!(
+        results[1].as_ref().unwrap_err().category(),
+        ErrorCategory::Network
+    );
+    assert!(results[2].is_ok());
+    assert!(results[3].is_err());
+    assert_eq!(server.requests().len(), 2);
+
+    // batches are subject to the same faults
+    let batch = wwsvc_rs::requests::BatchRequest::new()
+        .function(wwsvc_rs::requests::ServiceFunction::new("ARTIKEL.GET", 1));
+    assert!(client.send_batch(&batch).await.is_ok());
+    assert!(matches!(
+        client.send_batch(&batch).await,
+        Err(WWSVCError::Timeout(_))
+    ));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_latency() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    {
+        let _guard = inject_faults(FaultInjection::new().latency(Duration::from_millis(200)));
+        let started = Instant::now();
+        client
+            .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    // faults end with the guard
+    let started = Instant::now();
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn test_corrupt_cursor() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(articles(&["A1"])).header("WWSVC-CURSOR", "C1"));
+    server.enqueue(MockResponse::json(articles(&["A2"])).header("WWSVC-CURSOR", "C2"));
+    server.enqueue(MockResponse::json(articles(&["A3"])).header("WWSVC-CURSOR", "C3"));
+
+    let _guard = inject_faults(FaultInjection::new().corrupt_cursor(1, CursorFault::Invalid));
+    let mut client = server.client().await.create_cursor(1);
+    let mut pages = client.cursored_request::<serde_json::Value, serde_json::Value>(
+        Method::PUT,
+        "ARTIKEL.GET",
+        1,
+        Parameters::new(),
+    );
+    pages.next().await.unwrap().unwrap();
+    pages.next().await.unwrap().unwrap();
+    assert_eq!(
+        server.requests()[1].header("WWSVC-CURSOR"),
+        Some(INVALID_CURSOR_ID)
+    );
+
+    let _guard = inject_faults(FaultInjection::new().corrupt_cursor(1, CursorFault::Missing));
+    assert!(matches!(
+        pages.next().await,
+        Some(Err(WWSVCError::CursorLost))
+    ));
+}
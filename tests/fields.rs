@@ -0,0 +1,44 @@
+use serde_json::json;
+use wwsvc_rs::fields::{FieldCode, FieldList};
+use wwsvc_rs::WWSVCError;
+
+#[test]
+fn test_field_code() {
+    let code: FieldCode = "ART_1_25".parse().unwrap();
+    assert_eq!(code.table, "ART");
+    assert_eq!(code.number, 1);
+    assert_eq!(code.length, 25);
+    assert_eq!(code.to_string(), "ART_1_25");
+
+    for invalid in ["ART_1", "ART_X_25", "_1_25", ""] {
+        assert!(matches!(
+            invalid.parse::<FieldCode>(),
+            Err(WWSVCError::InvalidFieldCode(_))
+        ));
+    }
+}
+
+#[test]
+fn test_field_list() {
+    let response = json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "FELDLISTE": {
+            "FELD": [
+                {"FELDNAME": "ART_1_25", "BESCHREIBUNG": "Artikelnummer", "TYP": "C"},
+                {"FELD": "ART_2_80", "BEZEICHNUNG": "Bezeichnung"},
+                {"NAME": "ADR_1_10"}
+            ]
+        }
+    });
+
+    let fields = FieldList::from_response(&response).unwrap();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields.get("ART_1_25").unwrap().description, "Artikelnummer");
+    assert_eq!(
+        fields.get("ART_1_25").unwrap().field_type.as_deref(),
+        Some("C")
+    );
+    assert_eq!(fields.get("ART_2_80").unwrap().description, "Bezeichnung");
+    assert!(fields.contains("ADR_1_10"));
+    assert_eq!(fields.for_table("art").count(), 2);
+}
@@ -0,0 +1,61 @@
+use wwsvc_rs::responses::{AdresseGetResponse, ArtikelGetResponse, BelegGetResponse, ComResult};
+use wwsvc_rs::test_util::{fixture, fixtures};
+use wwsvc_rs::{Value, WWResponse};
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[test]
+fn test_all_fixtures_have_com_result() {
+    for fixture in fixtures() {
+        let com_result: ComResult = serde_json::from_value(fixture.value()["COMRESULT"].clone())
+            .unwrap_or_else(|e| panic!("{}: {}", fixture.name, e));
+        assert!(com_result.status >= 200, "{}", fixture.name);
+    }
+}
+
+#[test]
+fn test_preset_responses() {
+    let articles: ArtikelGetResponse<ArticleData> =
+        serde_json::from_str(fixture("artikel_get").unwrap().json).unwrap();
+    assert_eq!(articles.items().len(), 2);
+    assert_eq!(articles.items()[0].article_number, "Artikel19Prozent");
+
+    let empty: ArtikelGetResponse<ArticleData> =
+        serde_json::from_str(fixture("artikel_get_empty").unwrap().json).unwrap();
+    assert!(empty.items().is_empty());
+
+    let addresses: AdresseGetResponse<Value> =
+        serde_json::from_str(fixture("adresse_get").unwrap().json).unwrap();
+    assert_eq!(addresses.items().len(), 1);
+
+    let documents: BelegGetResponse<Value> =
+        serde_json::from_str(fixture("beleg_get").unwrap().json).unwrap();
+    assert_eq!(documents.items().len(), 2);
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derived_response() {
+    use wwsvc_rs::WWSVCGetData;
+
+    #[derive(WWSVCGetData, Debug, serde::Deserialize, Clone)]
+    #[wwsvc(function = "ARTIKEL")]
+    pub struct DerivedArticle {
+        #[serde(rename = "ART_1_25")]
+        pub article_number: String,
+        #[serde(rename = "ART_2_80")]
+        pub description: String,
+    }
+
+    let articles: <DerivedArticle as WWSVCGetData>::Response =
+        serde_json::from_str(fixture("artikel_get").unwrap().json).unwrap();
+    assert_eq!(articles.items()[1].article_number, "Artikel7Prozent");
+    assert_eq!(
+        articles.items()[1].description,
+        "Musterartikel mit 7 % MwSt."
+    );
+}
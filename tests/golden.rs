@@ -0,0 +1,171 @@
+use std::fmt::Debug;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wwsvc_rs::asynchron::{AsyncResult, AsyncState, AsyncTicketResponse};
+use wwsvc_rs::idb::IdbGetResponse;
+use wwsvc_rs::requests::{
+    BodyOrder, ExecJsonBatchRequest, ExecJsonRequest, ServiceFunction, ServicePassInfo,
+};
+use wwsvc_rs::responses::{
+    ArtikelGetResponse, BatchResponse, ComResult, InsertResponse, RegisterResponse,
+};
+use wwsvc_rs::{Value, WWResponse};
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[serde(rename = "ART_2_80")]
+    pub description: String,
+}
+
+/// Reads the golden file with the given name from `tests/golden`.
+fn golden(name: &str) -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name)
+        .with_extension("json");
+    let json =
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+/// Deserializes the golden file into `T` and asserts that serializing it yields the golden file
+/// again, so renamed or dropped members are caught.
+fn assert_round_trip<T>(name: &str) -> T
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let expected = golden(name);
+    let typed: T =
+        serde_json::from_value(expected.clone()).unwrap_or_else(|e| panic!("{}: {}", name, e));
+    assert_eq!(serde_json::to_value(&typed).unwrap(), expected, "{}", name);
+    let again: T = serde_json::from_value(serde_json::to_value(&typed).unwrap()).unwrap();
+    assert_eq!(again, typed, "{}", name);
+    typed
+}
+
+#[test]
+fn test_exec_json_request_golden() {
+    let request = ExecJsonRequest::new(
+        ServiceFunction::new("ARTIKEL.GET", 1)
+            .parameter("ARTNR", "Artikel19Prozent")
+            .parameter("FELDER", "ART_1_25,ART_2_80"),
+        ServicePassInfo::new("service-pass", "hash", "Thu, 01 Jan 1970 00:00:00 GMT", 1)
+            .execute_mode("SYNCHRON"),
+    );
+    for order in [BodyOrder::Protocol, BodyOrder::Alphabetical] {
+        let body: Value = serde_json::from_slice(&request.to_vec(order).unwrap()).unwrap();
+        assert_eq!(body, golden("exec_json_request"));
+    }
+
+    assert_eq!(
+        assert_round_trip::<ExecJsonRequest>("exec_json_request"),
+        request
+    );
+}
+
+#[test]
+fn test_exec_json_batch_request_golden() {
+    let request = ExecJsonBatchRequest::new(
+        vec![
+            ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", "A1"),
+            ServiceFunction::new("ARTIKEL.INSERT", 1).parameter("ARTNR", "A2"),
+        ],
+        ServicePassInfo::new("service-pass", "hash", "Thu, 01 Jan 1970 00:00:00 GMT", 2),
+    );
+    let body: Value =
+        serde_json::from_slice(&request.to_vec(BodyOrder::Protocol).unwrap()).unwrap();
+    assert_eq!(body, golden("exec_json_batch_request"));
+
+    assert_eq!(
+        assert_round_trip::<ExecJsonBatchRequest>("exec_json_batch_request"),
+        request
+    );
+}
+
+#[test]
+fn test_com_result_golden() {
+    let com_result = assert_round_trip::<ComResult>("com_result_error");
+    assert_eq!(com_result.status, 404);
+    assert_eq!(com_result.errno.as_deref(), Some("1234"));
+    assert_eq!(com_result.info2, None);
+}
+
+#[test]
+fn test_register_response_golden() {
+    let response = assert_round_trip::<RegisterResponse>("register_response");
+    assert_eq!(response.service_pass.pass_id, "service-pass");
+    assert_eq!(response.service_pass.app_id, "app-id");
+}
+
+#[test]
+fn test_get_response_golden() {
+    let response = assert_round_trip::<ArtikelGetResponse<ArticleData>>("artikel_get_response");
+    assert_eq!(response.items().len(), 2);
+    assert_eq!(response.items()[1].article_number, "Artikel7Prozent");
+
+    let response = assert_round_trip::<ArtikelGetResponse<Value>>("artikel_get_response");
+    assert_eq!(response.items()[0]["ART_1_25"], "Artikel19Prozent");
+}
+
+#[test]
+fn test_batch_response_golden() {
+    let response = assert_round_trip::<BatchResponse>("batch_response");
+    assert!(!response.is_success());
+    assert_eq!(response.results[0].data["ARTIKEL"]["ART_1_25"], "A1");
+    assert_eq!(response.results[1].com_result.status, 409);
+}
+
+#[test]
+fn test_async_responses_golden() {
+    let response = assert_round_trip::<AsyncTicketResponse>("async_ticket_response");
+    assert_eq!(response.ticket.id, "T-42");
+    assert_eq!(response.ticket.state, AsyncState::Queued);
+
+    let result = assert_round_trip::<AsyncResult<ArtikelGetResponse<Value>>>("async_result");
+    assert!(result.is_terminal());
+    assert_eq!(result.result.unwrap().items().len(), 1);
+}
+
+#[test]
+fn test_idb_get_response_golden() {
+    let response = assert_round_trip::<IdbGetResponse<Value>>("idb_get_response");
+    assert_eq!(response.items().len(), 2);
+    assert_eq!(response.items()[1]["IDB_1_20"], "T2");
+}
+
+#[test]
+fn test_insert_response_round_trip() {
+    let response = InsertResponse {
+        com_result: assert_round_trip::<ComResult>("com_result_error"),
+        record: ArticleData {
+            article_number: "A1".to_string(),
+            description: "Neuer Artikel".to_string(),
+        },
+    };
+    let cached = serde_json::to_string(&response).unwrap();
+    assert_eq!(
+        serde_json::from_str::<InsertResponse<ArticleData>>(&cached).unwrap(),
+        response
+    );
+}
+
+#[cfg(feature = "models")]
+#[test]
+fn test_model_round_trip() {
+    use wwsvc_rs::models::{Artikel, Model};
+
+    let response = assert_round_trip::<ArtikelGetResponse<Value>>("artikel_get_response");
+    let articles: ArtikelGetResponse<Artikel> =
+        serde_json::from_value(serde_json::to_value(&response).unwrap()).unwrap();
+    let article = &articles.items()[0];
+    assert_eq!(article.article_number, "Artikel19Prozent");
+    assert_eq!(serde_json::to_value(article).unwrap(), response.items()[0]);
+    assert_eq!(
+        article.field::<String>("ART_2_80").unwrap().as_deref(),
+        Some("Musterartikel mit 19 % MwSt.")
+    );
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::MockServer;
+use wwsvc_rs::headers::{ExecuteMode, ResultType, ACCEPT_RESULT_TYPE, EXECUTE_MODE};
+use wwsvc_rs::{collection, Parameters};
+
+#[test]
+fn test_result_type_names() {
+    assert_eq!(ResultType::Json.as_str(), "JSON");
+    assert_eq!(ResultType::Bin.to_string(), "BIN");
+    assert_eq!(ResultType::from(" xml "), ResultType::Xml);
+    assert_eq!(
+        ResultType::from("csv"),
+        ResultType::Other("CSV".to_string())
+    );
+    assert_eq!(
+        serde_json::to_value(ResultType::Bin).unwrap(),
+        serde_json::json!("BIN")
+    );
+    assert_eq!(ExecuteMode::Asynchron.header_value().unwrap(), "ASYNCHRON");
+}
+
+#[tokio::test]
+async fn test_result_type_headers() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    let headers = client.get_default_headers(None).unwrap();
+    assert_eq!(headers[ACCEPT_RESULT_TYPE], ResultType::Json.as_str());
+    assert_eq!(headers[EXECUTE_MODE], ExecuteMode::Synchron.as_str());
+
+    let headers = client.get_bin_headers(None).unwrap();
+    assert_eq!(headers.get_all(ACCEPT_RESULT_TYPE).iter().count(), 1);
+    assert_eq!(headers[ACCEPT_RESULT_TYPE], "BIN");
+
+    let headers = client
+        .get_result_type_headers(ResultType::Xml, None)
+        .unwrap();
+    assert_eq!(headers[ACCEPT_RESULT_TYPE], "XML");
+}
+
+#[tokio::test]
+async fn test_manual_execute_mode_header() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    client
+        .request(
+            reqwest::Method::PUT,
+            "ARTIKEL.GET",
+            1,
+            Parameters::new(),
+            Some(collection! { EXECUTE_MODE => ExecuteMode::Batch.as_str() }),
+        )
+        .await
+        .unwrap();
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.header(EXECUTE_MODE), Some("BATCH"));
+    assert_eq!(request.json()["WWSVC_PASSINFO"]["EXECUTE_MODE"], "BATCH");
+}
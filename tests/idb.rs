@@ -0,0 +1,101 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::fields::FieldCode;
+use wwsvc_rs::idb::{IdbGetResponse, IdbTable};
+use wwsvc_rs::{Parameters, WWResponse};
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct Tracking {
+    #[serde(rename = "IDB_1_20")]
+    pub tracking_number: String,
+}
+
+#[test]
+fn test_idb_table_names() {
+    let table = IdbTable::new(26);
+    assert_eq!(table.name(), "IDBID0026");
+    assert_eq!(table.function("get"), "IDBID0026.GET");
+    assert_eq!(table.container(), "IDBID0026LISTE");
+    assert_eq!(table.to_string(), "IDBID0026");
+    assert_eq!(table.with_version(2).version(), 2);
+}
+
+#[tokio::test]
+async fn test_idb_get() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "IDBID0026LISTE": {"IDBID0026": [{"IDB_1_20": "T1"}, {"IDB_1_20": "T2"}]}
+    })));
+
+    let mut client = server.client().await;
+    let table = IdbTable::new(26);
+    let response = table
+        .get::<Tracking>(&mut client, Parameters::new())
+        .await
+        .unwrap();
+    let numbers = response
+        .into_items()
+        .into_iter()
+        .map(|t| t.tracking_number)
+        .collect::<Vec<_>>();
+    assert_eq!(numbers, vec!["T1", "T2"]);
+    assert_eq!(
+        server.requests()[0].json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "IDBID0026.GET"
+    );
+
+    let empty: IdbGetResponse<Tracking> = serde_json::from_value(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+    }))
+    .unwrap();
+    assert!(empty.items.is_empty());
+}
+
+wwsvc_rs::idb_fields! {
+    pub mod tracking = 26 {
+        TRACKING_NUMBER = 1, 20;
+        CARRIER = 2, 40;
+    }
+}
+
+#[test]
+fn test_idb_fields() {
+    assert_eq!(tracking::TRACKING_NUMBER, "IDB_1_20");
+    assert_eq!(tracking::CARRIER, "IDB_2_40");
+    assert_eq!(tracking::table(), IdbTable::new(26));
+
+    let code = tracking::table().field(1, 20);
+    assert_eq!(code.to_string(), tracking::TRACKING_NUMBER);
+    assert_eq!(
+        tracking::CARRIER.parse::<FieldCode>().unwrap().table,
+        tracking::table().field_prefix()
+    );
+}
+
+#[tokio::test]
+async fn test_idb_query() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "IDBID0026LISTE": {"IDBID0026": [{"IDB_1_20": "T1"}]}
+    })));
+
+    let mut client = server.client().await;
+    let response = tracking::table()
+        .query()
+        .select(&[tracking::TRACKING_NUMBER, tracking::TRACKING_NUMBER])
+        .filter(tracking::CARRIER, "DHL")
+        .send::<Tracking>(&mut client)
+        .await
+        .unwrap();
+    assert_eq!(response.items[0].tracking_number, "T1");
+
+    let parameters = &server.requests()[0].json()["WWSVC_FUNCTION"]["PARAMETER"];
+    assert_eq!(parameters[0]["PNAME"], "IDB_2_40");
+    assert_eq!(parameters[0]["PCONTENT"], "DHL");
+    assert_eq!(parameters[1]["PNAME"], "FELDER");
+    assert_eq!(parameters[1]["PCONTENT"], "IDB_1_20");
+}
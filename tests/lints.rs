@@ -0,0 +1,28 @@
+use wwsvc_rs::lints::Lint;
+use wwsvc_rs::Parameters;
+
+#[test]
+fn test_unpaginated_get() {
+    let fields = Parameters::new().param("FELDER", "ART_1_25");
+    assert_eq!(
+        Lint::unpaginated_get("ARTIKEL.GET", &fields, 1000),
+        Some(Lint::UnpaginatedGet {
+            function: "ARTIKEL.GET".to_string(),
+            max_lines: 1000,
+        })
+    );
+    // filtered, limited explicitly or not a .GET function
+    let filtered = fields.clone().param("ARTNR", "Artikel19Prozent");
+    assert_eq!(Lint::unpaginated_get("ARTIKEL.GET", &filtered, 1000), None);
+    assert_eq!(Lint::unpaginated_get("ARTIKEL.GET", &fields, 50), None);
+    assert_eq!(Lint::unpaginated_get("ARTIKEL.INSERT", &fields, 1000), None);
+
+    let lint = Lint::unpaginated_get("ARTIKEL.GET", &Parameters::new(), 1000).unwrap();
+    assert!(lint.to_string().contains("beyond 1000 rows"));
+}
+
+#[test]
+fn test_insecure_tls() {
+    assert_eq!(Lint::insecure_tls(false), None);
+    assert_eq!(Lint::insecure_tls(true).is_some(), !cfg!(debug_assertions));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use encoding_rs::WINDOWS_1252;
+use serde_json::json;
+use wwsvc_rs::normalization::TextNormalization;
+use wwsvc_rs::{Credentials, Parameters, WebwareClient};
+
+const ADDRESSES: &[&str] = &[
+    "Müller GmbH & Co. KG",
+    "Jürgen Weiß",
+    "Königstraße 12",
+    "Äußere Spitalhofstraße 3",
+    "Österreichische Bundesbahnen",
+    "Übersee-Großhandel",
+    "Gänsemarkt, Düsseldorf",
+    "Café Crème",
+    "Preis: 12,50 €",
+    "„Zitat“ – Gedankenstrich",
+];
+
+/// Decodes the UTF-8 bytes of `text` as WINDOWS-1252, like misconfigured gateways do.
+fn garble(text: &str) -> String {
+    WINDOWS_1252.decode(text.as_bytes()).0.into_owned()
+}
+
+/// Decomposes umlauts into the base letter and a combining diaeresis.
+fn decompose(text: &str) -> String {
+    text.replace('ü', "u\u{308}")
+        .replace('ö', "o\u{308}")
+        .replace('ä', "a\u{308}")
+        .replace('Ä', "A\u{308}")
+        .replace('Ö', "O\u{308}")
+        .replace('Ü', "U\u{308}")
+}
+
+#[test]
+fn test_repair_mojibake() {
+    let normalization = TextNormalization {
+        repair_mojibake: true,
+        ..TextNormalization::none()
+    };
+    for address in ADDRESSES {
+        let garbled = garble(address);
+        assert_ne!(&garbled, address);
+        assert_eq!(normalization.apply(&garbled), *address);
+        // clean text is left alone
+        assert_eq!(normalization.apply(address), *address);
+    }
+
+    // mixed clean and garbled text
+    assert_eq!(
+        normalization.apply(&format!("Müller, {}", garble("Königstraße"))),
+        "Müller, Königstraße"
+    );
+    // lead characters without a valid sequence are kept
+    assert_eq!(normalization.apply("Ã la carte, Â!"), "Ã la carte, Â!");
+}
+
+#[test]
+fn test_nfc() {
+    let normalization = TextNormalization {
+        nfc: true,
+        ..TextNormalization::none()
+    };
+    for address in ADDRESSES {
+        let decomposed = decompose(address);
+        assert_eq!(normalization.apply(&decomposed), *address);
+    }
+    assert_eq!(
+        TextNormalization::none().apply(&decompose("Jürgen")),
+        decompose("Jürgen")
+    );
+}
+
+#[tokio::test]
+async fn test_normalized_response() {
+    let server = MockServer::start().await;
+    let body = json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ADRESSELISTE": {
+            "ADRESSE": ADDRESSES
+                .iter()
+                .map(|address| json!({"ADR_1_80": garble(&decompose(address))}))
+                .collect::<Vec<_>>()
+        }
+    });
+    server.enqueue(MockResponse::json(body.clone()));
+    server.enqueue(MockResponse::json(body));
+
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .text_normalization(TextNormalization::all())
+        .build()
+        .register()
+        .await
+        .unwrap();
+    let response = client
+        .request(None, "ADRESSE.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let names = response["ADRESSELISTE"]["ADRESSE"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|address| address["ADR_1_80"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ADDRESSES);
+
+    let mut client = server.client().await;
+    let response = client
+        .request(None, "ADRESSE.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        response["ADRESSELISTE"]["ADRESSE"][0]["ADR_1_80"],
+        garble(&decompose(ADDRESSES[0]))
+    );
+}
@@ -0,0 +1,133 @@
+use wwsvc_rs::parameters::MergePrecedence;
+use wwsvc_rs::requests::ServiceFunctionParameter;
+use wwsvc_rs::{binary, collection, Parameters};
+
+#[test]
+fn test_parameters_from_collection() {
+    let parameters: Parameters = collection! {
+        "ARTNR" => "Artikel19Prozent",
+        "FELDER" => "ART_1_25",
+    };
+
+    let names = parameters
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["ARTNR", "FELDER"]);
+}
+
+#[test]
+fn test_parameters_replace_existing() {
+    let parameters = Parameters::new()
+        .param("ARTNR", "A")
+        .param("FELDER", "ART_1_25")
+        .param("ARTNR", "B");
+
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters.iter().next().unwrap().content, "B");
+}
+
+#[test]
+fn test_parameters_bytes() {
+    let parameters = Parameters::new().param_bytes("DATEI", b"\x00\x01binary\xff");
+
+    let parameter = parameters.iter().next().unwrap();
+    assert!(parameters.is_binary("DATEI"));
+    assert_eq!(parameter.content, "AAFiaW5hcnn/");
+    assert_eq!(
+        binary::decode(&parameter.content).unwrap(),
+        b"\x00\x01binary\xff"
+    );
+
+    // the binary flag isn't sent
+    let sent: Vec<ServiceFunctionParameter> = (&parameters).into();
+    assert_eq!(
+        serde_json::to_value(sent).unwrap(),
+        serde_json::json!([{"PNAME": "DATEI", "PCONTENT": "AAFiaW5hcnn/"}])
+    );
+}
+
+#[test]
+fn test_parameters_get_remove() {
+    let mut parameters = Parameters::new()
+        .param("ARTNR", "A")
+        .param("FELDER", "ART_1_25");
+
+    assert_eq!(parameters.get("ARTNR"), Some("A"));
+    assert!(parameters.contains("FELDER"));
+    assert_eq!(parameters.remove("ARTNR").unwrap().content, "A");
+    assert!(parameters.remove("ARTNR").is_none());
+    assert!(!parameters.contains("ARTNR"));
+    assert_eq!(parameters.len(), 1);
+}
+
+#[test]
+fn test_parameters_merge() {
+    let base = Parameters::new()
+        .param("ARTNR", "A")
+        .param("FELDER", "ART_1_25");
+    let other = Parameters::new()
+        .param("FELDER", "ART_2_80")
+        .param("MAXLINES", 10);
+
+    let mut kept = base.clone();
+    kept.merge(&other, MergePrecedence::KeepExisting);
+    assert_eq!(kept.get("FELDER"), Some("ART_1_25"));
+    assert_eq!(kept.get("MAXLINES"), Some("10"));
+
+    let mut overwritten = base;
+    overwritten.merge(&other, MergePrecedence::Overwrite);
+    assert_eq!(overwritten.get("FELDER"), Some("ART_2_80"));
+
+    let names = overwritten.into_iter().map(|p| p.name).collect::<Vec<_>>();
+    assert_eq!(names, vec!["ARTNR", "FELDER", "MAXLINES"]);
+}
+
+#[test]
+fn test_parameters_from_iterator() {
+    let parameters = Parameters::new()
+        .param("ARTNR", "A")
+        .param("FELDER", "ART_1_25");
+
+    let filtered: Parameters = parameters
+        .into_iter()
+        .filter(|p| p.name != "FELDER")
+        .collect();
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered.get("ARTNR"), Some("A"));
+}
+
+#[test]
+fn test_parameters_in_chunked() {
+    let base = Parameters::new().param("FELDER", "ART_1_25");
+    let chunks = base.param_in_chunked("ARTNR", 1..=5, 2);
+    let keys = chunks
+        .iter()
+        .map(|chunk| chunk.get("ARTNR").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(keys, vec!["1,2", "3,4", "5"]);
+    assert!(chunks
+        .iter()
+        .all(|chunk| chunk.get("FELDER") == Some("ART_1_25")));
+
+    assert!(base
+        .param_in_chunked("ARTNR", Vec::<String>::new(), 2)
+        .is_empty());
+    assert_eq!(base.param_in_chunked("ARTNR", ["A", "B"], 0).len(), 2);
+}
+
+#[test]
+fn test_parameters_display() {
+    let parameters = Parameters::new()
+        .param("ARTNR", "A1")
+        .param("BESCHREIBUNG", "x".repeat(100))
+        .param_bytes("DATEI", b"content");
+    assert_eq!(
+        parameters.to_string(),
+        format!(
+            "ARTNR=A1, BESCHREIBUNG={}… (100 bytes), DATEI=<12 bytes base64>",
+            "x".repeat(64)
+        )
+    );
+    assert_eq!(Parameters::new().to_string(), "");
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::pass_info::ServicePassStatus;
+
+#[tokio::test]
+async fn test_service_pass_info() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "SERVICEPASS": {
+            "GUELTIGBIS": "31.12.2026",
+            "BENUTZER": "SERVICE",
+            "MODULE": "ARTIKEL, ADRESSE"
+        }
+    })));
+
+    let mut client = server.client().await;
+    let info = client.service_pass_info().await.unwrap();
+    assert!(info.is_valid());
+    assert_eq!(info.expires_at.as_deref(), Some("31.12.2026"));
+    assert_eq!(info.user.as_deref(), Some("SERVICE"));
+    assert_eq!(info.modules, vec!["ARTIKEL", "ADRESSE"]);
+    assert_eq!(
+        server.requests()[0].json()["WWSVC_FUNCTION"]["FUNCTIONNAME"],
+        "SERVICEPASS.INFO"
+    );
+}
+
+#[test]
+fn test_service_pass_info_invalid() {
+    let info = ServicePassStatus::from_response(json!({
+        "COMRESULT": {"STATUS": 401, "CODE": "Unauthorized", "INFO": "Servicepass ungültig"},
+        "MODULES": ["ARTIKEL"]
+    }))
+    .unwrap();
+    assert!(!info.is_valid());
+    assert_eq!(info.modules, vec!["ARTIKEL"]);
+    assert!(info.expires_at.is_none());
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{default_response, MockResponse, MockServer};
+use serde_json::json;
+use tokio::net::TcpListener;
+use wwsvc_rs::pool::ClientPool;
+use wwsvc_rs::registry::active_registrations;
+use wwsvc_rs::request_id::{RequestId, RequestIdGenerator};
+use wwsvc_rs::{Credentials, WWSVCError, WebwareClient};
+
+fn register_response(pass_id: &str) -> MockResponse {
+    MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "SERVICEPASS": {"PASSID": pass_id, "APPID": "app-id"}
+    }))
+}
+
+#[tokio::test]
+async fn test_pool_shutdown() {
+    let server = MockServer::start().await;
+    let pool = ClientPool::new();
+    pool.insert("tenant-b", server.client().await);
+    pool.insert("tenant-a", server.client().await);
+    assert_eq!(pool.tenants(), vec!["tenant-a", "tenant-b"]);
+    assert!(pool.get("tenant-a").is_some());
+
+    let report = pool.shutdown(Duration::from_secs(5)).await;
+    assert!(report.is_success());
+    assert_eq!(report.deregistered, vec!["tenant-a", "tenant-b"]);
+    assert!(pool.is_empty());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|r| r.path.contains("/DEREGISTER/service-pass/")));
+}
+
+#[tokio::test]
+async fn test_pool_shutdown_timeout() {
+    let server = MockServer::start().await;
+    // accepts connections but never answers
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let silent_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let pool = ClientPool::new();
+    pool.insert("responsive", server.client().await);
+    pool.insert(
+        "silent",
+        WebwareClient::builder()
+            .webware_url(&silent_url)
+            .vendor_hash("vendor")
+            .app_hash("app")
+            .secret("1")
+            .revision(1)
+            .credentials(Credentials::new("silent-pass", "app-id"))
+            .build()
+            .register()
+            .await
+            .unwrap(),
+    );
+
+    let report = pool.shutdown(Duration::from_millis(200)).await;
+    assert!(!report.is_success());
+    assert_eq!(report.deregistered, vec!["responsive"]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "silent");
+    assert!(matches!(report.failed[0].1, WWSVCError::Timeout(_)));
+    drop(listener);
+}
+
+#[tokio::test]
+async fn test_pool_shutdown_timeout_releases_pass() {
+    let server = MockServer::start().await;
+    server.enqueue(register_response("slow-pass"));
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("slow-vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build()
+        .register()
+        .await
+        .unwrap();
+    assert_eq!(active_registrations("slow-vendor", "app"), 1);
+
+    let pool = ClientPool::new();
+    pool.insert("slow", client);
+    server.enqueue(default_response().delay(Duration::from_secs(5)));
+    let report = pool.shutdown(Duration::from_millis(200)).await;
+    assert!(matches!(report.failed[0].1, WWSVCError::Timeout(_)));
+    assert_eq!(active_registrations("slow-vendor", "app"), 0);
+}
+
+#[derive(Debug)]
+struct FailingIds;
+
+impl RequestIdGenerator for FailingIds {
+    fn generate(&self, _sequence: u32) -> Result<RequestId, WWSVCError> {
+        Err(WWSVCError::InvalidHeader)
+    }
+}
+
+#[tokio::test]
+async fn test_pool_register_previous_fails() {
+    let server = MockServer::start().await;
+    let pool = ClientPool::new();
+    pool.insert(
+        "tenant",
+        WebwareClient::builder()
+            .webware_url(&server.url)
+            .vendor_hash("vendor")
+            .app_hash("app")
+            .secret("1")
+            .revision(1)
+            .credentials(Credentials::new("old-pass", "app-id"))
+            .request_ids(FailingIds)
+            .build()
+            .register()
+            .await
+            .unwrap(),
+    );
+
+    server.enqueue(register_response("new-pass"));
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+    pool.register("tenant", client).await.unwrap();
+
+    let registered = pool.get("tenant").unwrap();
+    assert_eq!(registered.credentials().unwrap().service_pass, "new-pass");
+    // the previous client couldn't build its DEREGISTER request
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use wwsvc_rs::prepared::{PreparedQuery, PreparedRequest};
+use wwsvc_rs::{Parameters, WebwareClient};
+
+#[tokio::test]
+async fn test_prepare_offline() {
+    let server = MockServer::start().await;
+    let unregistered = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+
+    let prepared = unregistered.prepare(
+        "ARTIKEL.GET",
+        1,
+        Parameters::new().param("ARTNR", "Artikel19Prozent"),
+    );
+    let body = serde_json::to_value(prepared.unsigned_body()).unwrap();
+    assert_eq!(body["WWSVC_FUNCTION"]["FUNCTIONNAME"], "ARTIKEL.GET");
+    assert_eq!(body["WWSVC_PASSINFO"]["SERVICEPASS"], "");
+    assert!(server.requests().is_empty());
+
+    // pass the prepared request to the service holding the credentials
+    let prepared: PreparedRequest =
+        serde_json::from_str(&serde_json::to_string(&prepared).unwrap()).unwrap();
+    let mut client = server.client().await;
+
+    let (url, headers, signed) = client.sign(&prepared).unwrap();
+    assert_eq!(url, client.execjson_url().unwrap());
+    assert_eq!(headers.get("WWSVC-REQID").unwrap(), "1");
+    assert_eq!(signed.function, prepared.function);
+    assert_eq!(signed.pass_info.service_pass, "service-pass");
+
+    client.execute_prepared(None, &prepared).await.unwrap();
+    let request = &server.requests()[0];
+    assert_eq!(request.header("WWSVC-REQID"), Some("2"));
+    assert_eq!(
+        request.json()["WWSVC_FUNCTION"]["PARAMETER"][0]["PCONTENT"],
+        "Artikel19Prozent"
+    );
+}
+
+#[tokio::test]
+async fn test_preview_request() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    let preview = client
+        .preview_request(
+            None,
+            "ARTIKEL.GET",
+            1,
+            &Parameters::new().param("ARTNR", "A1"),
+            None,
+        )
+        .unwrap();
+    assert_eq!(preview.url, format!("{}/WWSVC/EXECJSON", server.url));
+    assert_eq!(preview.method, "PUT");
+    assert_eq!(
+        preview.headers.get("wwsvc-reqid").map(String::as_str),
+        Some("1")
+    );
+    assert_eq!(preview.body.function.function_name, "ARTIKEL.GET");
+    assert_eq!(preview.body.pass_info.service_pass, "service-pass");
+    assert!(server.requests().is_empty());
+
+    // the preview doesn't use up the request ID
+    client
+        .request(None, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    assert_eq!(server.requests()[0].header("WWSVC-REQID"), Some("1"));
+}
+
+#[tokio::test]
+async fn test_prepared_query() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let query = PreparedQuery::new(
+        "ARTIKEL.GET",
+        1,
+        Parameters::new()
+            .param("FELDER", "ART_1_25")
+            .param("ARTNR", "default"),
+    );
+    assert_eq!(query.function(), "ARTIKEL.GET");
+    assert_eq!(query.version(), 1);
+
+    for number in ["A1", "A2"] {
+        server.enqueue(MockResponse::json(
+            serde_json::json!({"ARTIKEL": [{"ARTNR": number}]}),
+        ));
+        let response: serde_json::Value = query
+            .execute(&mut client, Parameters::new().param("ARTNR", number))
+            .await
+            .unwrap();
+        assert_eq!(response["ARTIKEL"][0]["ARTNR"], number);
+    }
+
+    let requests = server.requests();
+    let parameters = &requests[requests.len() - 1].json()["WWSVC_FUNCTION"]["PARAMETER"];
+    assert_eq!(parameters[0]["PNAME"], "FELDER");
+    assert_eq!(parameters[1]["PNAME"], "ARTNR");
+    assert_eq!(parameters[1]["PCONTENT"], "A2");
+    // the base parameters are left unchanged
+    assert_eq!(
+        query.parameters_with(&Parameters::new()).get("ARTNR"),
+        Some("default")
+    );
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use serde_json::json;
+use wwsvc_rs::registry::{active_registrations, set_registration_limit};
+use wwsvc_rs::{ErrorCategory, WWSVCError, WebwareClient};
+
+fn register_response(pass_id: &str) -> MockResponse {
+    MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "SERVICEPASS": {"PASSID": pass_id, "APPID": "app-id"}
+    }))
+}
+
+#[tokio::test]
+async fn test_registration_limit() {
+    let server = MockServer::start().await;
+    server.enqueue(register_response("pass-1"));
+    set_registration_limit("limited-vendor", "app", Some(1));
+
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("limited-vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+
+    let registered = client.clone().register().await.unwrap();
+    assert_eq!(active_registrations("limited-vendor", "app"), 1);
+
+    let error = client.clone().register().await.err().unwrap();
+    assert!(matches!(
+        error,
+        WWSVCError::RegistrationLimitExceeded { limit: 1, .. }
+    ));
+    assert_eq!(error.category(), ErrorCategory::Client);
+    assert_eq!(server.requests().len(), 1);
+
+    registered.deregister().await.unwrap();
+    assert_eq!(active_registrations("limited-vendor", "app"), 0);
+
+    server.enqueue(register_response("pass-2"));
+    let registered = client.register().await.unwrap();
+    assert_eq!(registered.credentials().unwrap().service_pass, "pass-2");
+    assert_eq!(active_registrations("limited-vendor", "app"), 1);
+}
+
+#[tokio::test]
+async fn test_failed_registration_releases_slot() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({"COMRESULT": {"STATUS": 500}})));
+    set_registration_limit("failing-vendor", "app", Some(1));
+
+    let client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("failing-vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .build();
+
+    assert!(client.clone().register().await.is_err());
+    assert_eq!(active_registrations("failing-vendor", "app"), 0);
+
+    server.enqueue(register_response("pass-1"));
+    client.register().await.unwrap();
+    assert_eq!(active_registrations("failing-vendor", "app"), 1);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{MockResponse, MockServer};
+use reqwest::Method;
+use serde_json::json;
+use wwsvc_rs::request_id::RequestId;
+use wwsvc_rs::request_log::{FileRequestLog, RequestLogEntry};
+use wwsvc_rs::{Credentials, Parameters, WebwareClient};
+
+#[tokio::test]
+async fn test_request_log() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}
+    })));
+    server.enqueue(MockResponse::json(json!({})).status(500));
+
+    let path =
+        std::env::temp_dir().join(format!("wwsvc-rs-request-log-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let log = Arc::new(FileRequestLog::new(&path));
+
+    let mut client = WebwareClient::builder()
+        .webware_url(&server.url)
+        .vendor_hash("vendor")
+        .app_hash("app")
+        .secret("1")
+        .revision(1)
+        .credentials(Credentials::new("service-pass", "app-id"))
+        .request_log(log.clone())
+        .build()
+        .register()
+        .await
+        .unwrap();
+
+    client
+        .request(Some(Method::PUT), "ARTIKEL.GET", 1, Parameters::new(), None)
+        .await
+        .unwrap();
+    let _ = client
+        .request(Some(Method::PUT), "ARTIKEL.PUT", 1, Parameters::new(), None)
+        .await;
+
+    let requests = server.requests();
+    let pending = log.pending();
+    assert_eq!(pending.len(), 2);
+    assert_eq!(pending[0].request_id, RequestId::Number(1));
+    assert_eq!(pending[0].function, "ARTIKEL.GET");
+    assert_eq!(pending[0].status, Some(200));
+    assert_eq!(
+        Some(pending[0].timestamp.as_str()),
+        requests[0].header("WWSVC-TS")
+    );
+    assert_eq!(pending[1].request_id, RequestId::Number(2));
+    assert_eq!(pending[1].status, Some(500));
+
+    client.flush_request_log().await.unwrap();
+    assert!(log.pending().is_empty());
+    let content = std::fs::read_to_string(&path).unwrap();
+    let entries = content
+        .lines()
+        .map(|line| serde_json::from_str::<RequestLogEntry>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(entries, pending);
+    std::fs::remove_file(&path).unwrap();
+}
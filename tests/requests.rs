@@ -0,0 +1,116 @@
+use wwsvc_rs::requests::{
+    BodyOrder, BodySizeLimit, ExecJsonRequest, FunctionPolicy, ServiceFunction, ServicePassInfo,
+};
+use wwsvc_rs::responses::ComResult;
+
+#[test]
+fn test_request_golden() {
+    let expected: ExecJsonRequest = serde_json::from_str(
+        r#"{
+            "WWSVC_FUNCTION": {
+                "FUNCTIONNAME": "ARTIKEL.GET",
+                "PARAMETER": [{"PNAME": "ARTNR", "PCONTENT": "Artikel19Prozent"}],
+                "REVISION": 1
+            },
+            "WWSVC_PASSINFO": {
+                "SERVICEPASS": "service-pass",
+                "APPHASH": "hash",
+                "TIMESTAMP": "Thu, 01 Jan 1970 00:00:00 GMT",
+                "REQUESTID": 1,
+                "EXECUTE_MODE": "SYNCHRON"
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let request = ExecJsonRequest::new(
+        ServiceFunction::new("ARTIKEL.GET", 1).parameter("ARTNR", "Artikel19Prozent"),
+        ServicePassInfo::new("service-pass", "hash", "Thu, 01 Jan 1970 00:00:00 GMT", 1)
+            .execute_mode("SYNCHRON"),
+    );
+    assert_eq!(request, expected);
+    assert_ne!(request, ExecJsonRequest::default());
+}
+
+#[test]
+fn test_com_result_eq() {
+    let com_result: ComResult =
+        serde_json::from_str(r#"{"STATUS": 200, "CODE": "OK", "INFO": ""}"#).unwrap();
+    assert_eq!(
+        com_result,
+        ComResult {
+            status: 200,
+            code: "OK".to_string(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_function_policy() {
+    let policy = FunctionPolicy::default();
+    assert!(policy.permits("ARTIKEL.DELETE"));
+
+    let policy = FunctionPolicy::default()
+        .allow("*.GET")
+        .allow("IDBID*.INSERT")
+        .deny("BELEG*");
+    assert!(policy.permits("ARTIKEL.GET"));
+    assert!(policy.permits("idbid0026.insert"));
+    assert!(!policy.permits("IDBID0026.DELETE"));
+    assert!(!policy.permits("BELEG.GET"));
+    assert!(!policy.permits("ARTIKEL.GETALL"));
+    assert!(!policy.permits(".GE"));
+
+    let policy = FunctionPolicy::default().allow("A*A");
+    assert!(policy.permits("AA"));
+    assert!(!policy.permits("A"));
+}
+
+#[test]
+fn test_body_size() {
+    let request = ExecJsonRequest::new(
+        ServiceFunction::new("ARTIKEL.GET", 1).parameter("ARTNR", "A".repeat(100)),
+        ServicePassInfo::default(),
+    );
+    let size = request.size(BodyOrder::Protocol).unwrap();
+    assert_eq!(size, request.to_vec(BodyOrder::Protocol).unwrap().len());
+    assert!(size > 100);
+
+    assert!(BodySizeLimit::default().check(&request, size).is_ok());
+    assert!(BodySizeLimit::max(size).check(&request, size).is_ok());
+    assert!(BodySizeLimit::max(size - 1).check(&request, size).is_err());
+}
+
+#[test]
+fn test_request_display() {
+    let request = ExecJsonRequest::new(
+        ServiceFunction::new("ARTIKEL.GET", 1)
+            .parameter("ARTNR", "A1")
+            .parameter("FELDER", "ART_1_25"),
+        ServicePassInfo::new("secret-pass", "secret-hash", "timestamp", 3),
+    );
+    let displayed = request.to_string();
+    assert_eq!(
+        displayed,
+        "ARTIKEL.GET (revision 1, request 3, service pass <redacted>): ARTNR=A1, FELDER=ART_1_25"
+    );
+    assert!(!displayed.contains("secret"));
+}
+
+#[test]
+fn test_body_order() {
+    let request = ExecJsonRequest::new(
+        ServiceFunction::new("ARTIKEL.GET", 1).parameter("ARTNR", "A1"),
+        ServicePassInfo::new("service-pass", "hash", "timestamp", 3),
+    );
+    let body = |order| String::from_utf8(request.to_vec(order).unwrap()).unwrap();
+    assert_eq!(
+        body(BodyOrder::Protocol),
+        r#"{"WWSVC_FUNCTION":{"FUNCTIONNAME":"ARTIKEL.GET","PARAMETER":[{"PNAME":"ARTNR","PCONTENT":"A1"}],"REVISION":1},"WWSVC_PASSINFO":{"SERVICEPASS":"service-pass","APPHASH":"hash","TIMESTAMP":"timestamp","REQUESTID":3}}"#
+    );
+    assert_eq!(
+        body(BodyOrder::Alphabetical),
+        r#"{"WWSVC_FUNCTION":{"FUNCTIONNAME":"ARTIKEL.GET","PARAMETER":[{"PCONTENT":"A1","PNAME":"ARTNR"}],"REVISION":1},"WWSVC_PASSINFO":{"APPHASH":"hash","REQUESTID":3,"SERVICEPASS":"service-pass","TIMESTAMP":"timestamp"}}"#
+    );
+}
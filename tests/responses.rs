@@ -0,0 +1,117 @@
+use wwsvc_rs::responses::{ArtikelGetResponse, ComResult};
+use wwsvc_rs::{generate_get_response, Value, WWResponse};
+
+const ARTIKEL_RESPONSE: &str = r#"{
+    "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+    "ARTIKELLISTE": {
+        "ARTIKEL": [
+            {"ART_1_25": "Artikel19Prozent"},
+            {"ART_1_25": "Artikel7Prozent"}
+        ]
+    }
+}"#;
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+fn article_count<R: WWResponse>(response: &R) -> usize {
+    response.items().len()
+}
+
+#[test]
+fn test_typed_response() {
+    let response: ArtikelGetResponse<ArticleData> = serde_json::from_str(ARTIKEL_RESPONSE).unwrap();
+
+    assert_eq!(response.com_result().unwrap().status, 200);
+    assert_eq!(article_count(&response), 2);
+    assert_eq!(response.items()[1].article_number, "Artikel7Prozent");
+    assert!(response.raw().is_none());
+}
+
+#[test]
+fn test_missing_container() {
+    const EMPTY_RESPONSE: &str = r#"{"COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""}}"#;
+
+    let response: ArtikelGetResponse<ArticleData> = serde_json::from_str(EMPTY_RESPONSE).unwrap();
+    assert!(response.container.list.is_none());
+    assert!(response.items().is_empty());
+    assert!(response.into_items().is_empty());
+
+    let response: Value = serde_json::from_str(EMPTY_RESPONSE).unwrap();
+    assert!(response.items().is_empty());
+}
+
+#[test]
+fn test_value_response() {
+    let response: Value = serde_json::from_str(ARTIKEL_RESPONSE).unwrap();
+
+    assert_eq!(response.com_result().unwrap().code, "OK");
+    assert_eq!(article_count(&response), 2);
+    assert_eq!(response.items()[0]["ART_1_25"], "Artikel19Prozent");
+    assert!(response.raw().is_some());
+}
+
+#[test]
+fn test_lenient_com_result() {
+    let com_result: ComResult =
+        serde_json::from_str(r#"{"STATUS": "404", "CODE": "Not Found", "INFO": "", "ERRNO": 17}"#)
+            .unwrap();
+    assert_eq!(com_result.status, 404);
+    assert_eq!(com_result.errno.as_deref(), Some("17"));
+
+    let com_result: ComResult =
+        serde_json::from_str(r#"{"STATUS": 200, "CODE": "OK", "INFO": "", "ERRNO": "0"}"#).unwrap();
+    assert_eq!(com_result.status, 200);
+    assert_eq!(com_result.errno.as_deref(), Some("0"));
+
+    let com_result: ComResult =
+        serde_json::from_str(r#"{"STATUS": 200, "CODE": "OK", "INFO": ""}"#).unwrap();
+    assert_eq!(com_result.errno, None);
+
+    assert!(
+        serde_json::from_str::<ComResult>(r#"{"STATUS": "OK", "CODE": "", "INFO": ""}"#).is_err()
+    );
+}
+
+#[cfg(feature = "models")]
+#[test]
+fn test_models() {
+    use wwsvc_rs::models::{Artikel, Model};
+
+    let response: ArtikelGetResponse<Artikel> = serde_json::from_str(
+        r#"{
+            "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+            "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": "Artikel19Prozent", "ART_2_80": "Test"}]}
+        }"#,
+    )
+    .unwrap();
+
+    let article = &response.items()[0];
+    assert_eq!(article.article_number, "Artikel19Prozent");
+    assert_eq!(
+        article.field::<String>("ART_2_80").unwrap().as_deref(),
+        Some("Test")
+    );
+    assert_eq!(article.field::<String>("ART_3_10").unwrap(), None);
+}
+
+generate_get_response!(ArticleResponse, "ARTIKELLISTE", ArticleContainer, "ARTIKEL");
+generate_get_response!(strict StrictArticleResponse, "ARTIKELLISTE", StrictArticleContainer, "ARTIKEL");
+
+#[test]
+fn test_strict_generated_response() {
+    let response: StrictArticleResponse<ArticleData> =
+        serde_json::from_str(ARTIKEL_RESPONSE).unwrap();
+    assert_eq!(response.items().len(), 2);
+
+    let renamed = ARTIKEL_RESPONSE.replace("ARTIKELLISTE", "ARTIKELLIST");
+    let response: ArticleResponse<ArticleData> = serde_json::from_str(&renamed).unwrap();
+    assert!(response.items().is_empty());
+    assert!(serde_json::from_str::<StrictArticleResponse<ArticleData>>(&renamed).is_err());
+
+    let renamed = ARTIKEL_RESPONSE.replace("\"ARTIKEL\"", "\"ARTIKELN\"");
+    assert!(serde_json::from_str::<StrictArticleResponse<ArticleData>>(&renamed).is_err());
+}
@@ -0,0 +1,17 @@
+use wwsvc_rs::revisions::FunctionRevisions;
+use wwsvc_rs::Parameters;
+
+#[test]
+fn test_unsupported_parameters() {
+    assert_eq!(FunctionRevisions::artikel_get()[0].revision, 1);
+
+    let parameters = Parameters::new()
+        .param("ARTNR", "Artikel19Prozent")
+        .param("UNKNOWN", "1");
+    assert!(FunctionRevisions::unsupported_parameters("ARTIKEL.GET", 1, &parameters).is_empty());
+    assert_eq!(
+        FunctionRevisions::unsupported_parameters("artikel.get", 0, &parameters),
+        vec![("ARTNR", 1)]
+    );
+    assert!(FunctionRevisions::unsupported_parameters("ADRESSE.GET", 0, &parameters).is_empty());
+}
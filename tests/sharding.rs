@@ -0,0 +1,58 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use futures::StreamExt;
+use serde_json::json;
+use wwsvc_rs::responses::ArtikelGetResponse;
+use wwsvc_rs::{Parameters, ShardedExport};
+
+#[derive(Debug, serde::Deserialize, Clone)]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+}
+
+#[tokio::test]
+async fn test_sharded_export() {
+    let server = MockServer::start().await;
+    for number in ["A1", "A2"] {
+        server.enqueue(
+            MockResponse::json(json!({
+                "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+                "ARTIKELLISTE": {"ARTIKEL": [{"ART_1_25": number}]}
+            }))
+            .header("WWSVC-CURSOR", "CLOSED"),
+        );
+    }
+
+    let client = server.client().await;
+    let export = ShardedExport::new(
+        "ARTIKEL.GET",
+        1,
+        Parameters::new().param("FELDER", "ART_1_25"),
+    )
+    .shard(Parameters::new().param("ARTNR", "A*"))
+    .shard(Parameters::new().param("ARTNR", "B*"));
+    assert_eq!(export.shard_count(), 2);
+
+    let mut numbers = export
+        .run::<ArticleData, ArtikelGetResponse<ArticleData>>(&client)
+        .map(|row| row.unwrap().article_number)
+        .collect::<Vec<_>>()
+        .await;
+    numbers.sort();
+    assert_eq!(numbers, vec!["A1", "A2"]);
+
+    let mut shards = server
+        .requests()
+        .iter()
+        .map(|request| {
+            let parameters = &request.json()["WWSVC_FUNCTION"]["PARAMETER"];
+            assert_eq!(parameters[0]["PCONTENT"], "ART_1_25");
+            assert_eq!(request.header("WWSVC-CURSOR"), Some("CREATE"));
+            parameters[1]["PCONTENT"].as_str().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    shards.sort();
+    assert_eq!(shards, vec!["A*", "B*"]);
+}
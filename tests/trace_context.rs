@@ -0,0 +1,41 @@
+mod common;
+
+use common::MockServer;
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use opentelemetry::Context;
+use reqwest::Method;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use wwsvc_rs::Parameters;
+
+#[tokio::test]
+async fn test_trace_context_headers() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer());
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let parent = SpanContext::new(
+        TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+        SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+        TraceFlags::SAMPLED,
+        true,
+        TraceState::from_key_value([("vendor", "value")]).unwrap(),
+    );
+    let span = tracing::info_span!("export");
+    span.set_parent(Context::new().with_remote_span_context(parent))
+        .unwrap();
+    client
+        .request(Method::PUT, "ARTIKEL.GET", 1, Parameters::new(), None)
+        .instrument(span)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert!(requests[0].path.ends_with("/EXECJSON"));
+    let traceparent = requests[0].header("traceparent").unwrap();
+    assert!(traceparent.starts_with("00-4bf92f3577b34da6a3ce929d0e0e4736-"));
+    assert!(traceparent.ends_with("-01"));
+    assert_eq!(requests[0].header("tracestate"), Some("vendor=value"));
+}
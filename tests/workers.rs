@@ -0,0 +1,53 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::MockServer;
+use futures::channel::mpsc;
+use wwsvc_rs::workers::spawn_workers;
+use wwsvc_rs::Parameters;
+
+#[tokio::test]
+async fn test_spawn_workers() {
+    let server = MockServer::start().await;
+    let client = server.client().await;
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+
+    let (tx, rx) = mpsc::unbounded::<u32>();
+    let pool = {
+        let running = running.clone();
+        let max_running = max_running.clone();
+        spawn_workers(client, rx, 2, move |mut client, job| {
+            let running = running.clone();
+            let max_running = max_running.clone();
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                client
+                    .request(
+                        None,
+                        "ARTIKEL.GET",
+                        1,
+                        Parameters::new().param("ARTNR", job),
+                        None,
+                    )
+                    .await
+                    .unwrap();
+                running.fetch_sub(1, Ordering::SeqCst);
+            }
+        })
+    };
+
+    for job in 0..6 {
+        tx.unbounded_send(job).unwrap();
+    }
+    drop(tx);
+    pool.await.unwrap();
+
+    assert_eq!(server.requests().len(), 6);
+    assert_eq!(max_running.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use wwsvc_rs::writes::UpdateBuilder;
+use wwsvc_rs::{collection, Parameters, WWSVCError, WWSVCGetData};
+
+#[derive(WWSVCGetData, Debug, serde::Deserialize, serde::Serialize, Clone)]
+#[wwsvc(function = "ARTIKEL")]
+pub struct ArticleData {
+    #[serde(rename = "ART_1_25")]
+    pub article_number: String,
+    #[serde(rename = "ART_1_10")]
+    pub description: String,
+    #[serde(rename = "ART_2_10")]
+    pub comment: Option<String>,
+    #[serde(rename = "ART_1_80")]
+    pub stock: u32,
+}
+
+fn article_response() -> MockResponse {
+    MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 200, "CODE": "OK", "INFO": ""},
+        "ARTIKELLISTE": {"ARTIKEL": [{
+            "ART_1_25": "A1",
+            "ART_1_10": "Artikel",
+            "ART_2_10": "Kommentar",
+            "ART_1_80": 5
+        }]}
+    }))
+}
+
+#[tokio::test]
+async fn test_update_changed_fields() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+    server.enqueue(article_response());
+
+    let update = UpdateBuilder::<ArticleData>::load(&mut client, collection! { "ARTNR" => "A1" })
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!update.is_dirty().unwrap());
+    assert_eq!(update.function(), "ARTIKEL.PUT");
+
+    let update = update.modify(|article| {
+        article.description = "Neue Bezeichnung".to_string();
+        article.comment = None;
+    });
+    assert_eq!(
+        update.changes().unwrap().to_string(),
+        "ART_1_10=Neue Bezeichnung, ART_2_10="
+    );
+    assert!(update.send(&mut client).await.unwrap().is_some());
+
+    let request = server.requests().pop().unwrap();
+    let function = &request.json()["WWSVC_FUNCTION"];
+    assert_eq!(function["FUNCTIONNAME"], "ARTIKEL.PUT");
+    // the stock hasn't been touched, so it isn't overwritten
+    let names = function["PARAMETER"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|parameter| parameter["PNAME"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["ART_1_10", "ART_2_10", "ARTNR"]);
+}
+
+#[tokio::test]
+async fn test_update_unchanged_and_missing() {
+    let server = MockServer::start().await;
+    let mut client = server.client().await;
+
+    let missing = UpdateBuilder::<ArticleData>::load(&mut client, collection! { "ARTNR" => "X" })
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+
+    server.enqueue(article_response());
+    let update = UpdateBuilder::<ArticleData>::load(&mut client, collection! { "ARTNR" => "A1" })
+        .await
+        .unwrap()
+        .unwrap();
+    let requests = server.requests().len();
+    assert!(update.send(&mut client).await.unwrap().is_none());
+    assert_eq!(server.requests().len(), requests);
+
+    let record = ArticleData {
+        article_number: "A1".to_string(),
+        description: "Artikel".to_string(),
+        comment: None,
+        stock: 5,
+    };
+    let mut update =
+        UpdateBuilder::from_record(record, Parameters::new().param("ARTNR", "A1")).unwrap();
+    update.record_mut().stock = 4;
+    server.enqueue(MockResponse::json(serde_json::json!({
+        "COMRESULT": {"STATUS": 409, "CODE": "Conflict", "INFO": "gesperrt"}
+    })));
+    let error = update.send(&mut client).await.unwrap_err();
+    assert!(
+        matches!(error, WWSVCError::FunctionFailed { ref function, .. } if function == "ARTIKEL.PUT")
+    );
+}
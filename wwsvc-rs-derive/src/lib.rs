@@ -16,11 +16,200 @@ use syn::{parse_macro_input, DeriveInput};
 struct WWSVCGetAttributes {
     function: String,
     #[darling(default)]
-    version: Option<u32>,
+    version: Option<VersionAttribute>,
     #[darling(default)]
     list_name: Option<String>,
     #[darling(default)]
     container_name: Option<String>,
+    #[darling(default)]
+    fields_merge: Option<String>,
+    #[darling(default)]
+    strict: darling::util::Flag,
+    #[darling(default)]
+    max_lines: Option<u32>,
+    #[darling(default)]
+    timeout_secs: Option<u64>,
+    #[darling(default)]
+    key: Option<KeyFields>,
+    /// Only used by the write derives, accepted so it can be shared with them.
+    #[allow(dead_code)]
+    #[darling(default)]
+    validate: darling::util::Flag,
+}
+
+/// Fields of a composite key with their parameter names, in declaration order, e.g.
+/// `key(artnr = "ARTNR", lager = "LAGER")`.
+struct KeyFields(Vec<(syn::Ident, String)>);
+
+impl FromMeta for KeyFields {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                darling::ast::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(parameter),
+                            ..
+                        }),
+                    ..
+                })) if path.get_ident().is_some() => {
+                    Ok((path.get_ident().unwrap().clone(), parameter.value()))
+                }
+                _ => Err(darling::Error::custom(
+                    "key fields must be given as `field = \"PARAMETER\"`",
+                )
+                .with_span(item)),
+            })
+            .collect::<darling::Result<Vec<_>>>()
+            .map(KeyFields)
+    }
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(wwsvc), allow_unknown_fields)]
+struct WWSVCPutAttributes {
+    function: String,
+    #[darling(default)]
+    version: Option<VersionAttribute>,
+    #[darling(default)]
+    validate: darling::util::Flag,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(wwsvc), allow_unknown_fields)]
+struct WWSVCDeleteAttributes {
+    function: String,
+    #[darling(default)]
+    version: Option<VersionAttribute>,
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(wwsvc), allow_unknown_fields)]
+struct WWSVCInsertAttributes {
+    function: String,
+    #[darling(default)]
+    version: Option<VersionAttribute>,
+    #[darling(default)]
+    container_name: Option<String>,
+    #[darling(default)]
+    created: Option<syn::Type>,
+    #[darling(default)]
+    validate: darling::util::Flag,
+}
+
+/// Version of the function, either fixed or `"auto"` to use the version set on the client.
+enum VersionAttribute {
+    Fixed(u32),
+    Auto,
+}
+
+impl FromMeta for VersionAttribute {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        match value {
+            syn::Lit::Int(version) => Ok(VersionAttribute::Fixed(version.base10_parse()?)),
+            syn::Lit::Str(version) if version.value() == "auto" => Ok(VersionAttribute::Auto),
+            _ => Err(darling::Error::custom(
+                "version must be a number or \"auto\"",
+            )),
+        }
+    }
+}
+
+impl VersionAttribute {
+    /// Generates the `VERSION` or `AUTO_VERSION` constant of the trait implementation.
+    fn constant(version: Option<VersionAttribute>) -> proc_macro2::TokenStream {
+        match version {
+            Some(VersionAttribute::Fixed(version)) => quote! {
+                const VERSION: u32 = #version;
+            },
+            Some(VersionAttribute::Auto) => quote! {
+                const AUTO_VERSION: bool = true;
+            },
+            None => quote! {},
+        }
+    }
+}
+
+#[derive(FromDeriveInput)]
+#[darling(attributes(wwsvc), allow_unknown_fields)]
+struct WWSVCRecordAttributes {
+    function: String,
+    #[darling(default)]
+    version: Option<VersionAttribute>,
+    #[darling(default)]
+    list_name: Option<String>,
+    #[darling(default)]
+    container_name: Option<String>,
+    #[darling(default)]
+    ops: Option<darling::util::PathList>,
+    #[darling(default)]
+    suffixes: RecordOperationOptions,
+    #[darling(default)]
+    methods: RecordOperationOptions,
+    #[darling(default)]
+    validate: darling::util::Flag,
+}
+
+/// Names of the HTTP methods accepted by `methods(...)`, the associated constants of
+/// `wwsvc_rs::Method`.
+const HTTP_METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "DELETE", "HEAD", "OPTIONS", "CONNECT", "PATCH", "TRACE",
+];
+
+/// Values per record operation, e.g. `suffixes(update = "UPDATE")`.
+#[derive(Default, FromMeta)]
+struct RecordOperationOptions {
+    #[darling(default)]
+    get: Option<String>,
+    #[darling(default)]
+    insert: Option<String>,
+    #[darling(default, rename = "put")]
+    update: Option<String>,
+    #[darling(default)]
+    delete: Option<String>,
+}
+
+impl RecordOperationOptions {
+    /// Returns the configured values.
+    fn values(&self) -> impl Iterator<Item = &String> {
+        [&self.get, &self.insert, &self.update, &self.delete]
+            .into_iter()
+            .flatten()
+    }
+
+    /// Generates a `match` over the operations, returning the configured values and falling back
+    /// to `default` for the others.
+    fn to_match(
+        &self,
+        value: impl Fn(&str) -> proc_macro2::TokenStream,
+        default: proc_macro2::TokenStream,
+    ) -> Option<proc_macro2::TokenStream> {
+        let options = [
+            (quote! { Get }, &self.get),
+            (quote! { Insert }, &self.insert),
+            (quote! { Update }, &self.update),
+            (quote! { Delete }, &self.delete),
+        ];
+        if options.iter().all(|(_, option)| option.is_none()) {
+            return None;
+        }
+        let arms = options.iter().filter_map(|(variant, option)| {
+            let value = value(option.as_deref()?);
+            Some(quote! { wwsvc_rs::traits::RecordOperation::#variant => #value, })
+        });
+        let default = options
+            .iter()
+            .any(|(_, option)| option.is_none())
+            .then(|| quote! { _ => #default, });
+        Some(quote! {
+            match operation {
+                #(#arms)*
+                #default
+            }
+        })
+    }
 }
 
 struct RenameField(String);
@@ -61,29 +250,218 @@ impl FromMeta for RenameField {
 #[derive(FromField)]
 #[darling(attributes(serde), allow_unknown_fields)]
 struct WWSVCGetFieldAttributes {
-    rename: RenameField,
+    #[darling(default)]
+    rename: Option<RenameField>,
+    #[darling(default)]
+    flatten: darling::util::Flag,
+}
+
+#[derive(FromField)]
+#[darling(attributes(wwsvc))]
+struct WWSVCFieldOptions {
+    #[darling(default)]
+    extra: darling::util::Flag,
+    #[darling(default)]
+    field: Option<String>,
+    #[darling(default)]
+    max_len: Option<usize>,
+    #[darling(default)]
+    truncate: darling::util::Flag,
+}
+
+/// Returns the `FIELD_WIDTHS` constant of a write derive, built from `#[wwsvc(max_len = ..)]` on
+/// the fields. The parameter is named by `#[wwsvc(field = ..)]`, the serde rename or the field.
+fn field_widths(ast: &DeriveInput) -> Option<proc_macro2::TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { named: fields, .. }),
+        ..
+    }) = &ast.data
+    else {
+        return None;
+    };
+    let widths = fields
+        .iter()
+        .filter_map(|field| {
+            let WWSVCFieldOptions {
+                field: parameter,
+                max_len,
+                truncate,
+                ..
+            } = WWSVCFieldOptions::from_field(field).unwrap();
+            let Some(max_len) = max_len else {
+                assert!(
+                    !truncate.is_present(),
+                    "#[wwsvc(truncate)] requires #[wwsvc(max_len = ..)]!"
+                );
+                return None;
+            };
+            let name = parameter.unwrap_or_else(|| {
+                let WWSVCGetFieldAttributes { rename, .. } =
+                    WWSVCGetFieldAttributes::from_field(field).unwrap();
+                match rename {
+                    Some(rename) => rename.0,
+                    None => field.ident.as_ref().unwrap().to_string(),
+                }
+            });
+            let truncate = truncate.is_present();
+            Some(quote! {
+                wwsvc_rs::validation::FieldWidth {
+                    name: #name,
+                    max_len: #max_len,
+                    truncate: #truncate,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    if widths.is_empty() {
+        return None;
+    }
+    Some(quote! {
+        const FIELD_WIDTHS: &'static [wwsvc_rs::validation::FieldWidth] = &[#(#widths),*];
+    })
+}
+
+/// Returns a `to_parameters()` implementation calling `wwsvc_rs::validation::Validate` before
+/// serializing the record, if `#[wwsvc(validate)]` is given.
+fn validated_to_parameters(validate: darling::util::Flag) -> Option<proc_macro2::TokenStream> {
+    validate.is_present().then(|| {
+        quote! {
+            fn to_parameters(&self) -> wwsvc_rs::WWClientResult<wwsvc_rs::Parameters> {
+                wwsvc_rs::validation::Validate::validate(self)?;
+                wwsvc_rs::validation::checked_parameters(self, Self::FIELD_WIDTHS)
+            }
+        }
+    })
+}
+
+/// Returns the variant of `wwsvc_rs::schema::SchemaType` for a field type and whether the field is
+/// nullable. Types which aren't known are described as strings, as most values are.
+fn schema_type(ty: &syn::Type) -> (syn::Ident, bool) {
+    let segment = match ty {
+        syn::Type::Path(path) => path.path.segments.last(),
+        syn::Type::Reference(reference) => return schema_type(&reference.elem),
+        _ => None,
+    };
+    let Some(segment) = segment else {
+        return (
+            syn::Ident::new("String", proc_macro2::Span::call_site()),
+            false,
+        );
+    };
+    let ident = segment.ident.to_string();
+    if ident == "Option" {
+        if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = arguments.args.first() {
+                return (schema_type(inner).0, true);
+            }
+        }
+    }
+    let variant = match ident.as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" => "Integer",
+        "f32" | "f64" => "Number",
+        "bool" => "Boolean",
+        "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => "Array",
+        "HashMap" | "BTreeMap" | "Map" => "Object",
+        _ => "String",
+    };
+    (
+        syn::Ident::new(variant, proc_macro2::Span::call_site()),
+        false,
+    )
 }
 
 /// Generates a response and a container struct based on the name of the struct and the function name.
 ///
 /// ## Example
 /// ```
-/// use wwsvc_rs_proc::WWSVCGetData;
+/// use wwsvc_rs::WWSVCGetData;
 ///
-/// #[derive(WWSVCGetData, serde::Deserialize, Clone)]
+/// #[derive(WWSVCGetData, serde::Deserialize, Debug, Clone)]
 /// #[wwsvc(function = "IDBID0026")]
 /// pub struct TrackingData {
 ///     #[serde(rename = "IDB_0_20")]
 ///     pub index: String
 /// }
 /// ```
+///
+/// ## Function version
+///
+/// `#[wwsvc(version = 2)]` requests a fixed version of the function. With
+/// `#[wwsvc(version = "auto")]`, the version set on the client with
+/// `WebwareClient::set_function_version()` or the builder's `function_versions` is requested,
+/// falling back to version 1 if none is set. The same applies to `WWSVCPutData` and
+/// `WWSVCInsertData`.
+///
+/// ## Combining fields with the caller's `FELDER`
+///
+/// By default, a `FELDER` parameter passed to `get()` is sent unchanged. Use
+/// `#[wwsvc(fields_merge = "union")]` to request the union of both, or
+/// `#[wwsvc(fields_merge = "overwrite")]` to always request the fields of the struct.
+///
+/// ## Page size and timeout
+///
+/// `#[wwsvc(max_lines = 50)]` limits the results of `get()` and sets the page size of
+/// `get_cursored()`, `#[wwsvc(timeout_secs = 120)]` sets the timeout of each request. Both
+/// default to the settings of the client.
+///
+/// ## Composite keys
+///
+/// `#[wwsvc(key(artnr = "ARTNR", lager = "LAGER"))]` generates a key struct named after the
+/// struct with the suffix `Key`, with a `String` field per key parameter, and a `get_by_key()`
+/// function requesting the record with the given key.
+///
+/// ```ignore
+/// let stock = StockData::get_by_key(&mut client, StockDataKey {
+///     artnr: "A1".to_string(),
+///     lager: "1".to_string(),
+/// }).await?;
+/// ```
+///
+/// ## Strict response types
+///
+/// With `#[wwsvc(strict)]`, the response and container structs reject unknown members, e.g. a
+/// container which has been renamed by an update of WEBWARE. Intended for contract tests, since
+/// responses usually contain members which aren't needed.
+///
+/// ## Retaining unknown fields
+///
+/// A map field marked with `#[wwsvc(extra)]` and `#[serde(flatten)]` collects all fields which
+/// aren't declared on the struct. Since the server only returns the fields requested in `FELDER`,
+/// structs with such a field don't restrict the requested fields, so the full records are
+/// returned.
+///
+/// ```ignore
+/// use std::collections::BTreeMap;
+/// use wwsvc_rs::WWSVCGetData;
+///
+/// #[derive(WWSVCGetData, serde::Deserialize, Clone)]
+/// #[wwsvc(function = "ARTIKEL")]
+/// pub struct ArticleData {
+///     #[serde(rename = "ART_1_25")]
+///     pub article_number: String,
+///     #[wwsvc(extra)]
+///     #[serde(flatten)]
+///     pub extra: BTreeMap<String, serde_json::Value>,
+/// }
+/// ```
 #[proc_macro_derive(WWSVCGetData, attributes(wwsvc))]
 pub fn wwsvc_wrapper_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
     let name = &ast.ident;
-    let WWSVCGetAttributes { function, version, list_name, container_name } =
-        WWSVCGetAttributes::from_derive_input(&ast).unwrap();
+    let WWSVCGetAttributes {
+        function,
+        version,
+        list_name,
+        container_name,
+        fields_merge,
+        strict,
+        max_lines,
+        timeout_secs,
+        key,
+        validate: _,
+    } = WWSVCGetAttributes::from_derive_input(&ast).unwrap();
 
     // parse fields and add #[serde(rename = "#name")] to each field
     let fields = if let syn::Data::Struct(syn::DataStruct {
@@ -94,9 +472,19 @@ pub fn wwsvc_wrapper_derive(input: TokenStream) -> TokenStream {
         fields
             .iter()
             .map(|field| {
-                let WWSVCGetFieldAttributes { rename } = WWSVCGetFieldAttributes::from_field(field)
-                    .expect("WWSVCGetData requires serde renames!");
-                rename
+                let WWSVCFieldOptions { extra, .. } = WWSVCFieldOptions::from_field(field).unwrap();
+                let WWSVCGetFieldAttributes { rename, flatten } =
+                    WWSVCGetFieldAttributes::from_field(field).unwrap();
+                if extra.is_present() {
+                    assert!(
+                        flatten.is_present(),
+                        "#[wwsvc(extra)] fields require #[serde(flatten)]!"
+                    );
+                    return None;
+                }
+                let rename = rename.expect("WWSVCGetData requires serde renames!");
+                let ident = field.ident.as_ref().unwrap().to_string();
+                Some((rename, ident, &field.ty))
             })
             .collect::<Vec<_>>()
     } else {
@@ -116,17 +504,101 @@ pub fn wwsvc_wrapper_derive(input: TokenStream) -> TokenStream {
     let full_function_name = format!("{function}.GET");
     let response_ident = syn::Ident::new(&response_type, name.span());
     let container_ident = syn::Ident::new(&container_type, name.span());
-    // collect fields to comma separated string
-    let available_fields = fields
-        .into_iter()
-        .map(|field| field.0)
-        .collect::<Vec<_>>()
-        .join(",");
-
-    let function_version = if let Some(version) = version {
+    // collect fields to comma separated string, requesting all fields if unknown ones are retained
+    let available_fields = if fields.iter().any(Option::is_none) {
+        String::new()
+    } else {
+        fields
+            .iter()
+            .flatten()
+            .map(|(rename, _, _)| rename.0.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let field_descriptions = fields.iter().flatten().map(|(rename, ident, ty)| {
+        let field_name = &rename.0;
+        let (schema_type, nullable) = schema_type(ty);
+        quote! {
+            wwsvc_rs::schema::FieldDescription {
+                name: #field_name.to_string(),
+                field: #ident.to_string(),
+                schema_type: wwsvc_rs::schema::SchemaType::#schema_type,
+                nullable: #nullable,
+            }
+        }
+    });
+    let type_name = name.to_string();
+
+    let function_version = VersionAttribute::constant(version);
+
+    let fields_merge = match fields_merge.as_deref() {
+        None => quote! {},
+        Some(mode) => {
+            let variant = match mode {
+                "caller" => quote! { RespectCaller },
+                "union" => quote! { Union },
+                "overwrite" => quote! { Overwrite },
+                _ => panic!("fields_merge must be one of \"caller\", \"union\" or \"overwrite\"."),
+            };
+            quote! {
+                const FIELDS_MERGE: wwsvc_rs::traits::FieldsMerge = wwsvc_rs::traits::FieldsMerge::#variant;
+            }
+        }
+    };
+
+    let max_lines = max_lines.map(|max_lines| {
         quote! {
-            const VERSION: u32 = #version;
+            const MAX_LINES: Option<u32> = Some(#max_lines);
         }
+    });
+    let timeout = timeout_secs.map(|timeout_secs| {
+        quote! {
+            const TIMEOUT: Option<std::time::Duration> = Some(std::time::Duration::from_secs(#timeout_secs));
+        }
+    });
+
+    let key_lookup = key.map(|KeyFields(fields)| {
+        let key_ident = syn::Ident::new(&format!("{}Key", name), name.span());
+        let field_names = fields.iter().map(|(field, _)| field).collect::<Vec<_>>();
+        let parameters = fields.iter().map(|(_, parameter)| parameter).collect::<Vec<_>>();
+        let field_docs = parameters
+            .iter()
+            .map(|parameter| format!("The `{}` parameter.", parameter));
+        let key_doc = format!("Composite key of `{}`.", name);
+        quote! {
+            #[doc = #key_doc]
+            #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+            pub struct #key_ident {
+                #(
+                    #[doc = #field_docs]
+                    pub #field_names: String,
+                )*
+            }
+
+            impl From<#key_ident> for wwsvc_rs::Parameters {
+                fn from(key: #key_ident) -> Self {
+                    wwsvc_rs::Parameters::new()
+                        #(.param(#parameters, key.#field_names))*
+                }
+            }
+
+            impl #name {
+                /// Requests the record with the given key. Returns `None` if no record matches.
+                pub async fn get_by_key(
+                    client: &mut wwsvc_rs::WebwareClient<impl wwsvc_rs::client::states::Ready + Send>,
+                    key: #key_ident,
+                ) -> wwsvc_rs::WWClientResult<Option<#name>> {
+                    use wwsvc_rs::traits::{WWResponse, WWSVCGetData};
+
+                    let response = <#name as WWSVCGetData>::get(client, key.into()).await?;
+                    Ok(response.into_items().into_iter().next())
+                }
+            }
+        }
+    });
+
+    let deny_unknown_fields = if strict.is_present() {
+        quote! { #[serde(deny_unknown_fields)] }
     } else {
         quote! {}
     };
@@ -134,31 +606,389 @@ pub fn wwsvc_wrapper_derive(input: TokenStream) -> TokenStream {
     let gen = quote! {
         /// A response struct for a WWSVC GET request.
         #[derive(serde::Deserialize, Debug, Clone)]
+        #deny_unknown_fields
         pub struct #response_ident {
             /// The COMRESULT of the request. Contains information about the status of the request.
             #[serde(rename = "COMRESULT")]
             pub com_result: wwsvc_rs::responses::ComResult,
             /// The container struct for the list of items.
-            #[serde(rename = #function_list)]
+            ///
+            /// Some server versions omit the container if no rows match, in which case it is empty.
+            #[serde(rename = #function_list, default)]
             pub container: #container_ident,
         }
 
         /// Container struct for the list of items.
-        #[derive(serde::Deserialize, Debug, Clone)]
+        #[derive(serde::Deserialize, Debug, Clone, Default)]
+        #deny_unknown_fields
         pub struct #container_ident {
             /// The list of items.
             #[serde(rename = #container)]
             pub list: Option<Vec<#name>>,
         }
 
+        impl wwsvc_rs::traits::WWResponse for #response_ident {
+            type Item = #name;
+
+            fn com_result(&self) -> Option<wwsvc_rs::responses::ComResult> {
+                Some(self.com_result.clone())
+            }
+
+            fn items(&self) -> &[#name] {
+                self.container.list.as_deref().unwrap_or(&[])
+            }
+
+            fn into_items(self) -> Vec<#name> {
+                self.container.list.unwrap_or_default()
+            }
+        }
+
         #[wwsvc_rs::async_trait]
         impl wwsvc_rs::traits::WWSVCGetData for #name {
             const FUNCTION: &'static str = #full_function_name;
             #function_version
             const FIELDS: &'static str = #available_fields;
+            #fields_merge
+            #max_lines
+            #timeout
 
             type Response = #response_ident;
             type Container = #container_ident;
+
+            fn description() -> wwsvc_rs::schema::EntityDescription {
+                wwsvc_rs::schema::EntityDescription {
+                    name: #type_name.to_string(),
+                    function: #full_function_name.to_string(),
+                    fields: vec![#(#field_descriptions),*],
+                }
+            }
+        }
+
+        #key_lookup
+    };
+
+    gen.into()
+}
+
+/// Implements `WWSVCDeleteData` for a type, deleting records with the `.DELETE` function of the
+/// given function group.
+///
+/// ## Example
+/// ```ignore
+/// use wwsvc_rs::{collection, WWSVCDeleteData};
+///
+/// #[derive(WWSVCDeleteData)]
+/// #[wwsvc(function = "ARTIKEL")]
+/// pub struct Article;
+///
+/// Article::delete(&mut client, collection! { "ARTNR" => "A1000" }).await?;
+/// ```
+#[proc_macro_derive(WWSVCDeleteData, attributes(wwsvc))]
+pub fn wwsvc_delete_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let name = &ast.ident;
+    let WWSVCDeleteAttributes { function, version } =
+        WWSVCDeleteAttributes::from_derive_input(&ast).unwrap();
+
+    let full_function_name = format!("{function}.DELETE");
+    let function_version = VersionAttribute::constant(version);
+
+    let gen = quote! {
+        #[wwsvc_rs::async_trait]
+        impl wwsvc_rs::traits::WWSVCDeleteData for #name {
+            const FUNCTION: &'static str = #full_function_name;
+            #function_version
+        }
+    };
+
+    gen.into()
+}
+
+/// Implements `WWSVCPutData` for a struct, sending its members as parameters of the `.PUT`
+/// function of the given function group. Parameters are named by the serde names of the fields,
+/// so the struct has to implement `serde::Serialize`.
+///
+/// With `#[wwsvc(validate)]`, the record is checked with its `wwsvc_rs::validation::Validate`
+/// implementation before it is sent. `#[wwsvc(max_len = 25)]` on a field declares its width in
+/// WEBWARE as `FIELD_WIDTHS`; longer values are rejected, or truncated with `#[wwsvc(truncate)]`.
+/// The parameter is named by `#[wwsvc(field = "ART_1_25")]`, falling back to the serde rename.
+/// The same applies to `WWSVCInsertData` and `WWSVCRecord`.
+///
+/// ## Example
+/// ```ignore
+/// use wwsvc_rs::WWSVCPutData;
+///
+/// #[derive(WWSVCPutData, serde::Serialize)]
+/// #[wwsvc(function = "ARTIKEL")]
+/// pub struct ArticleUpdate {
+///     #[serde(rename = "ARTNR")]
+///     pub article_number: String,
+///     #[serde(rename = "ART_1_80", skip_serializing_if = "Option::is_none")]
+///     pub stock: Option<u32>,
+/// }
+/// ```
+#[proc_macro_derive(WWSVCPutData, attributes(wwsvc))]
+pub fn wwsvc_put_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let name = &ast.ident;
+    let WWSVCPutAttributes {
+        function,
+        version,
+        validate,
+    } = WWSVCPutAttributes::from_derive_input(&ast).unwrap();
+    if !matches!(
+        &ast.data,
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(_),
+            ..
+        })
+    ) {
+        panic!("WWSVCPutData can only be derived for structs with named fields.");
+    }
+
+    let full_function_name = format!("{function}.PUT");
+    let function_version = VersionAttribute::constant(version);
+    let to_parameters = validated_to_parameters(validate);
+    let field_widths = field_widths(&ast);
+
+    let gen = quote! {
+        #[wwsvc_rs::async_trait]
+        impl wwsvc_rs::traits::WWSVCPutData for #name {
+            const FUNCTION: &'static str = #full_function_name;
+            #function_version
+            #field_widths
+
+            #to_parameters
+        }
+    };
+
+    gen.into()
+}
+
+/// Implements `WWSVCInsertData` for a struct, sending its members as parameters of the `.INSERT`
+/// function of the given function group. Parameters are named by the serde names of the fields,
+/// so the struct has to implement `serde::Serialize`.
+///
+/// The created record is read from the member named like the function group, or
+/// `container_name`, and deserialized to the type given in `created`, which defaults to
+/// `serde_json::Value`.
+///
+/// ## Example
+/// ```ignore
+/// use wwsvc_rs::WWSVCInsertData;
+///
+/// #[derive(serde::Deserialize)]
+/// pub struct ArticleKey {
+///     #[serde(rename = "ART_1_25")]
+///     pub article_number: String,
+/// }
+///
+/// #[derive(WWSVCInsertData, serde::Serialize)]
+/// #[wwsvc(function = "ARTIKEL", created = "ArticleKey")]
+/// pub struct NewArticle {
+///     #[serde(rename = "ART_1_10")]
+///     pub description: String,
+/// }
+/// ```
+#[proc_macro_derive(WWSVCInsertData, attributes(wwsvc))]
+pub fn wwsvc_insert_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let name = &ast.ident;
+    let WWSVCInsertAttributes {
+        function,
+        version,
+        container_name,
+        created,
+        validate,
+    } = WWSVCInsertAttributes::from_derive_input(&ast).unwrap();
+    if !matches!(
+        &ast.data,
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(_),
+            ..
+        })
+    ) {
+        panic!("WWSVCInsertData can only be derived for structs with named fields.");
+    }
+
+    let full_function_name = format!("{function}.INSERT");
+    let container = match container_name {
+        Some(name) => name,
+        None => function,
+    };
+    let created = match created {
+        Some(created) => quote! { #created },
+        None => quote! { wwsvc_rs::Value },
+    };
+    let function_version = VersionAttribute::constant(version);
+    let to_parameters = validated_to_parameters(validate);
+    let field_widths = field_widths(&ast);
+
+    let gen = quote! {
+        #[wwsvc_rs::async_trait]
+        impl wwsvc_rs::traits::WWSVCInsertData for #name {
+            const FUNCTION: &'static str = #full_function_name;
+            #function_version
+            const CONTAINER: &'static str = #container;
+            #field_widths
+
+            type Created = #created;
+
+            #to_parameters
+        }
+    };
+
+    gen.into()
+}
+
+/// Implements `WWSVCRecord` for a struct, reading, creating, updating and deleting the records of
+/// the given function group. The struct has to implement `serde::Serialize` and
+/// `serde::Deserialize`. The fields with serde renames are requested as `FELDER` by `get()`.
+///
+/// `ops(...)` restricts the permitted operations to the listed ones of `get`, `insert`, `put` and
+/// `delete`, all are permitted by default. `suffixes(...)` and `methods(...)` replace the function
+/// suffix and the HTTP method of single operations. Methods other than the constants of
+/// `wwsvc_rs::Method`, e.g. `GET` or `POST`, are rejected at compile time.
+///
+/// ## Example
+/// ```ignore
+/// use wwsvc_rs::WWSVCRecord;
+///
+/// #[derive(WWSVCRecord, serde::Serialize, serde::Deserialize)]
+/// #[wwsvc(function = "ARTIKEL", ops(get, put, delete), methods(get = "GET"))]
+/// pub struct Article {
+///     #[serde(rename = "ART_1_25")]
+///     pub article_number: String,
+/// }
+/// ```
+#[proc_macro_derive(WWSVCRecord, attributes(wwsvc))]
+pub fn wwsvc_record_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    let name = &ast.ident;
+    let WWSVCRecordAttributes {
+        function,
+        version,
+        list_name,
+        container_name,
+        ops,
+        suffixes,
+        methods,
+        validate,
+    } = WWSVCRecordAttributes::from_derive_input(&ast).unwrap();
+
+    let fields = if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { named: fields, .. }),
+        ..
+    }) = &ast.data
+    {
+        fields
+            .iter()
+            .filter_map(|field| {
+                let WWSVCGetFieldAttributes { rename, .. } =
+                    WWSVCGetFieldAttributes::from_field(field).unwrap();
+                rename.map(|rename| rename.0)
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    } else {
+        panic!("WWSVCRecord can only be derived for structs with named fields.");
+    };
+
+    if let Some(method) = methods
+        .values()
+        .find(|method| !HTTP_METHODS.contains(&method.to_uppercase().as_str()))
+    {
+        let attribute = ast.attrs.iter().find(|attr| attr.path().is_ident("wwsvc"));
+        return syn::Error::new_spanned(
+            attribute,
+            format!(
+                "`{}` is not an HTTP method, expected one of {}.",
+                method,
+                HTTP_METHODS.join(", ")
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let function_version = VersionAttribute::constant(version);
+    let operations = ops.map(|ops| {
+        let operations = ops.iter().map(|op| {
+            let variant = match op.get_ident().map(|ident| ident.to_string()).as_deref() {
+                Some("get") => quote! { Get },
+                Some("insert") => quote! { Insert },
+                Some("put") => quote! { Update },
+                Some("delete") => quote! { Delete },
+                _ => panic!("ops must only contain get, insert, put and delete."),
+            };
+            quote! { wwsvc_rs::traits::RecordOperation::#variant }
+        });
+        quote! {
+            const OPERATIONS: &'static [wwsvc_rs::traits::RecordOperation] = &[#(#operations),*];
+        }
+    });
+    let suffix = suffixes
+        .to_match(
+            |suffix| quote! { #suffix },
+            quote! { operation.default_suffix() },
+        )
+        .map(|suffix| {
+            quote! {
+                fn suffix(operation: wwsvc_rs::traits::RecordOperation) -> &'static str {
+                    #suffix
+                }
+            }
+        });
+    let method = methods
+        .to_match(
+            |method| {
+                let method = syn::Ident::new(&method.to_uppercase(), name.span());
+                quote! { wwsvc_rs::Method::#method }
+            },
+            quote! { wwsvc_rs::Method::PUT },
+        )
+        .map(|method| {
+            quote! {
+                fn method(operation: wwsvc_rs::traits::RecordOperation) -> wwsvc_rs::Method {
+                    #method
+                }
+            }
+        });
+    let list_name = list_name.map(|list_name| {
+        quote! {
+            fn list_name() -> String {
+                #list_name.to_string()
+            }
+        }
+    });
+    let container_name = container_name.map(|container_name| {
+        quote! {
+            fn container_name() -> String {
+                #container_name.to_string()
+            }
+        }
+    });
+    let to_parameters = validated_to_parameters(validate);
+    let field_widths = field_widths(&ast);
+
+    let gen = quote! {
+        #[wwsvc_rs::async_trait]
+        impl wwsvc_rs::traits::WWSVCRecord for #name {
+            const FUNCTION: &'static str = #function;
+            #function_version
+            const FIELDS: &'static str = #fields;
+            #operations
+            #field_widths
+
+            #suffix
+            #method
+            #list_name
+            #container_name
+            #to_parameters
         }
     };
 